@@ -0,0 +1,78 @@
+//! `arbitrary` support for fuzzing, generating networks with valid prefixes only.
+
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+impl<'a> Arbitrary<'a> for Ipv4Network {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let addr = Ipv4Addr::arbitrary(u)?;
+        let prefix = u.int_in_range(0..=32)?;
+        Ok(Ipv4Network::new(addr, prefix).unwrap())
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(Ipv4Addr::size_hint(depth), (1, Some(1)))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Ipv6Network {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let addr = Ipv6Addr::arbitrary(u)?;
+        let prefix = u.int_in_range(0..=128)?;
+        Ok(Ipv6Network::new(addr, prefix).unwrap())
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(Ipv6Addr::size_hint(depth), (1, Some(1)))
+    }
+}
+
+impl<'a> Arbitrary<'a> for IpNetwork {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.arbitrary()? {
+            Ok(IpNetwork::V6(u.arbitrary()?))
+        } else {
+            Ok(IpNetwork::V4(u.arbitrary()?))
+        }
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(
+            bool::size_hint(depth),
+            arbitrary::size_hint::or(Ipv4Network::size_hint(depth), Ipv6Network::size_hint(depth)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arbitrary_networks_are_valid() {
+        let bytes: Vec<u8> = (0..4096u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect();
+        let mut u = Unstructured::new(&bytes);
+        let mut families = (0, 0);
+        while !u.is_empty() {
+            match IpNetwork::arbitrary(&mut u).unwrap() {
+                IpNetwork::V4(net) => {
+                    assert!(net.prefix() <= 32);
+                    families.0 += 1;
+                }
+                IpNetwork::V6(net) => {
+                    assert!(net.prefix() <= 128);
+                    families.1 += 1;
+                }
+            }
+        }
+        assert!(families.0 > 0 && families.1 > 0);
+        let mut u = Unstructured::new(&[]);
+        assert_eq!(
+            Ipv4Network::arbitrary(&mut u).unwrap(),
+            "0.0.0.0/0".parse().unwrap()
+        );
+    }
+}
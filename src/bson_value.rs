@@ -0,0 +1,192 @@
+//! Conversions for storing networks in BSON documents, such as MongoDB collections.
+//!
+//! Networks are stored as BSON strings in CIDR notation, the same form the `serde` feature
+//! produces through `bson::to_bson`. Storing them as strings keeps them readable in the shell
+//! and lets queries match them with plain string equality.
+
+use crate::{IpNetwork, IpNetworkError, Ipv4Network, Ipv6Network};
+use bson::{doc, Bson, Document};
+use std::{convert::TryFrom, str::FromStr};
+
+/// Parses `value` if it is a BSON string, and rejects every other BSON type.
+fn parse_bson<T>(value: &Bson) -> Result<T, IpNetworkError>
+where
+    T: FromStr<Err = IpNetworkError>,
+{
+    match value {
+        Bson::String(s) => s.parse(),
+        other => Err(IpNetworkError::InvalidCidrFormat(format!(
+            "expected a BSON string, found {:?}",
+            other.element_type()
+        ))),
+    }
+}
+
+/// Returns a query filter that matches fields holding a BSON string, the type networks are
+/// stored as.
+///
+/// # Examples
+///
+/// ```
+/// use bson::doc;
+/// use ipnetwork::bson_type_filter;
+///
+/// let filter = doc! { "network": bson_type_filter() };
+/// assert_eq!(filter, doc! { "network": { "$type": "string" } });
+/// ```
+pub fn bson_type_filter() -> Document {
+    doc! { "$type": "string" }
+}
+
+impl Ipv4Network {
+    /// Returns a query filter that matches fields holding a network within this one, such as
+    /// `10.1.2.0/24` for `10.0.0.0/8`.
+    ///
+    /// The filter is an anchored regular expression over the CIDR notation, so it needs no
+    /// extra index and matches networks stored with host bits set. IPv6 networks have no such
+    /// filter, as their compressed notation does not keep the prefix in a fixed position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bson::doc;
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.168.0.0/31".parse().unwrap();
+    /// assert_eq!(
+    ///     doc! { "network": net.bson_subnets_filter() },
+    ///     doc! { "network": { "$regex": r"^192\.168\.0\.(0|1)/(31|32)$" } },
+    /// );
+    /// ```
+    pub fn bson_subnets_filter(&self) -> Document {
+        const ANY_OCTET: &str = r"\d{1,3}";
+
+        let octets = self.network().octets();
+        let fixed = usize::from(self.prefix() / 8);
+        let mut parts: Vec<String> = octets[..fixed].iter().map(u8::to_string).collect();
+        if fixed < 4 {
+            // The octet holding the end of the prefix takes one of a run of values, unless the
+            // prefix ends on an octet boundary.
+            let host_bits = 8 - self.prefix() % 8;
+            if host_bits == 8 {
+                parts.push(ANY_OCTET.to_string());
+            } else {
+                let first = octets[fixed];
+                let values: Vec<String> = (first..=first | ((1 << host_bits) - 1))
+                    .map(|value| value.to_string())
+                    .collect();
+                parts.push(format!("({})", values.join("|")));
+            }
+            parts.resize(4, ANY_OCTET.to_string());
+        }
+        let prefixes: Vec<String> = (self.prefix()..=32).map(|p| p.to_string()).collect();
+        let pattern = format!(r"^{}/({})$", parts.join(r"\."), prefixes.join("|"));
+        doc! { "$regex": pattern }
+    }
+}
+
+impl From<Ipv4Network> for Bson {
+    fn from(net: Ipv4Network) -> Bson {
+        Bson::String(net.to_string())
+    }
+}
+
+impl TryFrom<&Bson> for Ipv4Network {
+    type Error = IpNetworkError;
+
+    fn try_from(value: &Bson) -> Result<Self, Self::Error> {
+        parse_bson(value)
+    }
+}
+
+impl From<Ipv6Network> for Bson {
+    fn from(net: Ipv6Network) -> Bson {
+        Bson::String(net.to_string())
+    }
+}
+
+impl TryFrom<&Bson> for Ipv6Network {
+    type Error = IpNetworkError;
+
+    fn try_from(value: &Bson) -> Result<Self, Self::Error> {
+        parse_bson(value)
+    }
+}
+
+impl From<IpNetwork> for Bson {
+    fn from(net: IpNetwork) -> Bson {
+        Bson::String(net.to_string())
+    }
+}
+
+impl TryFrom<&Bson> for IpNetwork {
+    type Error = IpNetworkError;
+
+    fn try_from(value: &Bson) -> Result<Self, Self::Error> {
+        parse_bson(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bson::doc;
+
+    #[test]
+    fn bson_round_trip() {
+        let net: IpNetwork = "10.1.0.0/16".parse().unwrap();
+        let document = doc! { "network": net };
+        assert_eq!(document.get_str("network"), Ok("10.1.0.0/16"));
+        assert_eq!(
+            IpNetwork::try_from(document.get("network").unwrap()),
+            Ok(net)
+        );
+
+        let binary = Bson::Binary(bson::Binary {
+            subtype: bson::spec::BinarySubtype::Generic,
+            bytes: vec![10, 1, 0, 0, 16],
+        });
+        assert!(Ipv4Network::try_from(&binary).is_err());
+        assert!(Ipv6Network::try_from(&Bson::String("10.0.0.0/8".into())).is_err());
+        assert!(matches!(
+            IpNetwork::try_from(&Bson::Int32(8)),
+            Err(IpNetworkError::InvalidCidrFormat(_))
+        ));
+    }
+
+    #[test]
+    fn bson_subnets_filter() {
+        let regex = |net: &str| {
+            let net: Ipv4Network = net.parse().unwrap();
+            net.bson_subnets_filter()
+                .get_str("$regex")
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(regex("10.1.2.3/32"), r"^10\.1\.2\.3/(32)$");
+        assert_eq!(
+            regex("10.1.0.0/16"),
+            r"^10\.1\.\d{1,3}\.\d{1,3}/(16|17|18|19|20|21|22|23|24|25|26|27|28|29|30|31|32)$"
+        );
+        assert_eq!(
+            regex("172.16.0.0/14"),
+            format!(
+                r"^172\.(16|17|18|19)\.\d{{1,3}}\.\d{{1,3}}/({})$",
+                (14..=32)
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join("|")
+            )
+        );
+        assert!(regex("0.0.0.0/0").starts_with(r"^\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}/(0|1|"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn bson_serde_matches_conversion() {
+        let net: Ipv6Network = "2001:db8::/32".parse().unwrap();
+        let serialized = bson::to_bson(&net).unwrap();
+        assert_eq!(serialized, Bson::from(net));
+        assert_eq!(bson::from_bson::<Ipv6Network>(serialized).unwrap(), net);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::error::IpNetworkError;
+use crate::merge::Merge;
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use std::{fmt, str::FromStr};
+
+/// An [`IpNetwork`] with its host bits cleared, for use as a map or set key.
+///
+/// `IpNetwork` compares and hashes the address it was constructed with, so `10.0.0.1/24` and
+/// `10.0.0.0/24` are different keys. A `CanonicalIpNetwork` clears the host bits on
+/// construction, so both become the key `10.0.0.0/24`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use ipnetwork::{CanonicalIpNetwork, IpNetwork};
+///
+/// let mut owners = HashMap::new();
+/// owners.insert("10.0.0.1/24".parse::<CanonicalIpNetwork>().unwrap(), "alice");
+/// owners.insert("10.0.0.0/24".parse::<CanonicalIpNetwork>().unwrap(), "bob");
+/// assert_eq!(owners.len(), 1);
+///
+/// let key: CanonicalIpNetwork = "10.0.0.99/24".parse().unwrap();
+/// assert_eq!(owners[&key], "bob");
+/// assert_eq!(key.get(), "10.0.0.0/24".parse::<IpNetwork>().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CanonicalIpNetwork(IpNetwork);
+
+impl CanonicalIpNetwork {
+    /// Constructs a new `CanonicalIpNetwork` from `network` with its host bits cleared.
+    pub fn new(network: IpNetwork) -> Self {
+        CanonicalIpNetwork(network.normalize())
+    }
+
+    /// Returns the network, whose address is its network address.
+    pub const fn get(self) -> IpNetwork {
+        self.0
+    }
+}
+
+impl fmt::Display for CanonicalIpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for CanonicalIpNetwork {
+    type Err = IpNetworkError;
+
+    /// Parses a network like [`IpNetwork`] does, then clears its host bits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(CanonicalIpNetwork::new)
+    }
+}
+
+impl From<IpNetwork> for CanonicalIpNetwork {
+    fn from(network: IpNetwork) -> Self {
+        CanonicalIpNetwork::new(network)
+    }
+}
+
+impl From<Ipv4Network> for CanonicalIpNetwork {
+    fn from(network: Ipv4Network) -> Self {
+        CanonicalIpNetwork::new(IpNetwork::V4(network))
+    }
+}
+
+impl From<Ipv6Network> for CanonicalIpNetwork {
+    fn from(network: Ipv6Network) -> Self {
+        CanonicalIpNetwork::new(IpNetwork::V6(network))
+    }
+}
+
+impl From<CanonicalIpNetwork> for IpNetwork {
+    fn from(network: CanonicalIpNetwork) -> Self {
+        network.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CanonicalIpNetwork {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CanonicalIpNetwork {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        IpNetwork::deserialize(deserializer).map(CanonicalIpNetwork::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn canonical_keys() {
+        let keys: BTreeSet<CanonicalIpNetwork> = ["10.0.0.1/24", "10.0.0.0/24", "::1/0", "::/0"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let keys: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
+        assert_eq!(keys, vec!["10.0.0.0/24", "::/0"]);
+
+        let net: Ipv6Network = "2001:db8::1/128".parse().unwrap();
+        assert_eq!(IpNetwork::from(CanonicalIpNetwork::from(net)), net.into());
+    }
+}
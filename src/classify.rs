@@ -0,0 +1,896 @@
+//! Classification of networks against the IANA special-purpose address registries.
+//!
+//! Every predicate answers for the network as a whole: it is true only if all addresses of
+//! the network fall into the category.
+
+use crate::{IpNetwork, Ipv4Network, Ipv6Network, NetworkRelation, TeredoAddr};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+const fn v4(a: u8, b: u8, c: u8, d: u8, prefix: u8) -> Ipv4Network {
+    match Ipv4Network::new_checked(Ipv4Addr::new(a, b, c, d), prefix) {
+        Some(net) => net,
+        None => panic!("invalid IPv4 prefix"),
+    }
+}
+
+const fn v6(segments: [u16; 8], prefix: u8) -> Ipv6Network {
+    let [a, b, c, d, e, f, g, h] = segments;
+    match Ipv6Network::new_checked(Ipv6Addr::new(a, b, c, d, e, f, g, h), prefix) {
+        Some(net) => net,
+        None => panic!("invalid IPv6 prefix"),
+    }
+}
+
+/// Entries of the IANA IPv4 Special-Purpose Address Registry that are not globally reachable.
+const IPV4_NOT_GLOBAL: [Ipv4Network; 14] = [
+    v4(0, 0, 0, 0, 8),          // "This network"
+    v4(10, 0, 0, 0, 8),         // Private-Use
+    v4(100, 64, 0, 0, 10),      // Shared Address Space
+    v4(127, 0, 0, 0, 8),        // Loopback
+    v4(169, 254, 0, 0, 16),     // Link Local
+    v4(172, 16, 0, 0, 12),      // Private-Use
+    v4(192, 0, 0, 0, 24),       // IETF Protocol Assignments
+    v4(192, 0, 2, 0, 24),       // Documentation (TEST-NET-1)
+    v4(192, 168, 0, 0, 16),     // Private-Use
+    v4(198, 18, 0, 0, 15),      // Benchmarking
+    v4(198, 51, 100, 0, 24),    // Documentation (TEST-NET-2)
+    v4(203, 0, 113, 0, 24),     // Documentation (TEST-NET-3)
+    v4(240, 0, 0, 0, 4),        // Reserved
+    v4(255, 255, 255, 255, 32), // Limited Broadcast
+];
+
+/// Globally reachable registry entries nested inside the blocks of `IPV4_NOT_GLOBAL`.
+const IPV4_GLOBAL_EXCEPTIONS: [Ipv4Network; 2] = [
+    v4(192, 0, 0, 9, 32),  // Port Control Protocol Anycast
+    v4(192, 0, 0, 10, 32), // Traversal Using Relays around NAT Anycast
+];
+
+/// Entries of the IANA IPv6 Special-Purpose Address Registry that are not globally reachable.
+///
+/// 6to4 (`2002::/16`) is listed as "N/A" in the registry and, like the standard library,
+/// treated as not globally reachable here.
+const IPV6_NOT_GLOBAL: [Ipv6Network; 13] = [
+    v6([0, 0, 0, 0, 0, 0, 0, 0], 128),         // Unspecified Address
+    v6([0, 0, 0, 0, 0, 0, 0, 1], 128),         // Loopback Address
+    v6([0, 0, 0, 0, 0, 0xffff, 0, 0], 96),     // IPv4-mapped Address
+    v6([0x64, 0xff9b, 1, 0, 0, 0, 0, 0], 48),  // IPv4-IPv6 Translation
+    v6([0x100, 0, 0, 0, 0, 0, 0, 0], 64),      // Discard-Only Address Block
+    v6([0x100, 0, 0, 1, 0, 0, 0, 0], 64),      // Dummy IPv6 Prefix
+    v6([0x2001, 0, 0, 0, 0, 0, 0, 0], 23),     // IETF Protocol Assignments
+    v6([0x2001, 0xdb8, 0, 0, 0, 0, 0, 0], 32), // Documentation
+    v6([0x2002, 0, 0, 0, 0, 0, 0, 0], 16),     // 6to4
+    v6([0x3fff, 0, 0, 0, 0, 0, 0, 0], 20),     // Documentation
+    v6([0x5f00, 0, 0, 0, 0, 0, 0, 0], 16),     // Segment Routing (SRv6) SIDs
+    v6([0xfc00, 0, 0, 0, 0, 0, 0, 0], 7),      // Unique-Local
+    v6([0xfe80, 0, 0, 0, 0, 0, 0, 0], 10),     // Link-Local Unicast
+];
+
+/// Globally reachable registry entries nested inside the blocks of `IPV6_NOT_GLOBAL`, sorted by
+/// first address.
+const IPV6_GLOBAL_EXCEPTIONS: [Ipv6Network; 7] = [
+    v6([0x2001, 1, 0, 0, 0, 0, 0, 1], 128), // Port Control Protocol Anycast
+    v6([0x2001, 1, 0, 0, 0, 0, 0, 2], 128), // Traversal Using Relays around NAT Anycast
+    v6([0x2001, 1, 0, 0, 0, 0, 0, 3], 128), // DNS-SD Service Registration Protocol Anycast
+    v6([0x2001, 3, 0, 0, 0, 0, 0, 0], 32),  // AMT
+    v6([0x2001, 4, 0x112, 0, 0, 0, 0, 0], 48), // AS112-v6
+    v6([0x2001, 0x20, 0, 0, 0, 0, 0, 0], 28), // ORCHIDv2
+    v6([0x2001, 0x30, 0, 0, 0, 0, 0, 0], 28), // Drone Remote ID Protocol Entity Tags
+];
+
+const IPV4_DOCUMENTATION: [Ipv4Network; 3] = [
+    v4(192, 0, 2, 0, 24),    // TEST-NET-1
+    v4(198, 51, 100, 0, 24), // TEST-NET-2
+    v4(203, 0, 113, 0, 24),  // TEST-NET-3
+];
+
+const IPV6_DOCUMENTATION: [Ipv6Network; 2] = [
+    v6([0x2001, 0xdb8, 0, 0, 0, 0, 0, 0], 32),
+    v6([0x3fff, 0, 0, 0, 0, 0, 0, 0], 20),
+];
+
+const IPV4_BENCHMARKING: Ipv4Network = v4(198, 18, 0, 0, 15);
+
+const IPV6_BENCHMARKING: Ipv6Network = v6([0x2001, 2, 0, 0, 0, 0, 0, 0], 48);
+
+const IPV4_SHARED: Ipv4Network = v4(100, 64, 0, 0, 10);
+
+const IPV4_RESERVED: Ipv4Network = v4(240, 0, 0, 0, 4);
+
+const IPV4_LIMITED_BROADCAST: Ipv4Network = v4(255, 255, 255, 255, 32);
+
+/// The private-use blocks of [RFC 1918].
+///
+/// [RFC 1918]: https://www.rfc-editor.org/rfc/rfc1918
+const IPV4_PRIVATE: [Ipv4Network; 3] = [
+    v4(10, 0, 0, 0, 8),
+    v4(172, 16, 0, 0, 12),
+    v4(192, 168, 0, 0, 16),
+];
+
+const IPV4_LOOPBACK: Ipv4Network = v4(127, 0, 0, 0, 8);
+
+const IPV4_LINK_LOCAL: Ipv4Network = v4(169, 254, 0, 0, 16);
+
+/// Blocks marked "Reserved by IETF" in the IANA IPv6 Address Space registry.
+///
+/// `::/8` and `100::/8` are left out since they hold special-purpose assignments such as the
+/// loopback address and the discard prefix.
+const IPV6_RESERVED: [Ipv6Network; 14] = [
+    v6([0x200, 0, 0, 0, 0, 0, 0, 0], 7),
+    v6([0x400, 0, 0, 0, 0, 0, 0, 0], 6),
+    v6([0x800, 0, 0, 0, 0, 0, 0, 0], 5),
+    v6([0x1000, 0, 0, 0, 0, 0, 0, 0], 4),
+    v6([0x4000, 0, 0, 0, 0, 0, 0, 0], 3),
+    v6([0x6000, 0, 0, 0, 0, 0, 0, 0], 3),
+    v6([0x8000, 0, 0, 0, 0, 0, 0, 0], 3),
+    v6([0xa000, 0, 0, 0, 0, 0, 0, 0], 3),
+    v6([0xc000, 0, 0, 0, 0, 0, 0, 0], 3),
+    v6([0xe000, 0, 0, 0, 0, 0, 0, 0], 4),
+    v6([0xf000, 0, 0, 0, 0, 0, 0, 0], 5),
+    v6([0xf800, 0, 0, 0, 0, 0, 0, 0], 6),
+    v6([0xfe00, 0, 0, 0, 0, 0, 0, 0], 9),
+    v6([0xfec0, 0, 0, 0, 0, 0, 0, 0], 10), // Deprecated site-local
+];
+
+/// Assignments nested inside the blocks of `IPV6_RESERVED`.
+const IPV6_RESERVED_EXCEPTIONS: [Ipv6Network; 1] = [
+    v6([0x5f00, 0, 0, 0, 0, 0, 0, 0], 16), // Segment Routing (SRv6) SIDs
+];
+
+const IPV6_UNIQUE_LOCAL: Ipv6Network = v6([0xfc00, 0, 0, 0, 0, 0, 0, 0], 7);
+
+const IPV6_UNICAST_LINK_LOCAL: Ipv6Network = v6([0xfe80, 0, 0, 0, 0, 0, 0, 0], 10);
+
+const IPV6_GLOBAL_UNICAST: Ipv6Network = v6([0x2000, 0, 0, 0, 0, 0, 0, 0], 3);
+
+const IPV6_MULTICAST: Ipv6Network = v6([0xff00, 0, 0, 0, 0, 0, 0, 0], 8);
+
+impl Ipv4Network {
+    /// Returns true if this network lies entirely within one of `blocks`.
+    fn is_within_any(&self, blocks: &[Ipv4Network]) -> bool {
+        blocks.iter().any(|block| {
+            matches!(
+                self.relation(*block),
+                NetworkRelation::Equal | NetworkRelation::SubsetOf
+            )
+        })
+    }
+
+    /// Returns true if every address in this network is globally reachable according to the
+    /// IANA IPv4 Special-Purpose Address Registry.
+    ///
+    /// Unlike the unstable `Ipv4Addr::is_global`, this considers the whole prefix: a network
+    /// that merely overlaps a non-global block, such as `0.0.0.0/0`, is not global. Multicast
+    /// addresses are not part of the registry and count as global.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("8.8.8.0/24".parse::<Ipv4Network>().unwrap().is_global());
+    /// assert!("192.0.0.9/32".parse::<Ipv4Network>().unwrap().is_global());
+    /// assert!(!"192.0.0.8/31".parse::<Ipv4Network>().unwrap().is_global());
+    /// assert!(!"10.0.0.0/8".parse::<Ipv4Network>().unwrap().is_global());
+    /// assert!(!"0.0.0.0/0".parse::<Ipv4Network>().unwrap().is_global());
+    /// ```
+    pub fn is_global(&self) -> bool {
+        self.is_within_any(&IPV4_GLOBAL_EXCEPTIONS)
+            || !IPV4_NOT_GLOBAL.iter().any(|block| self.overlaps(*block))
+    }
+
+    /// Returns true if this network lies within one of the ranges reserved for documentation:
+    /// `192.0.2.0/24` (TEST-NET-1), `198.51.100.0/24` (TEST-NET-2) and `203.0.113.0/24`
+    /// (TEST-NET-3), see [RFC 5737].
+    ///
+    /// [RFC 5737]: https://www.rfc-editor.org/rfc/rfc5737
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("198.51.100.0/25".parse::<Ipv4Network>().unwrap().is_documentation());
+    /// assert!(!"198.51.0.0/16".parse::<Ipv4Network>().unwrap().is_documentation());
+    /// ```
+    pub fn is_documentation(&self) -> bool {
+        self.is_within_any(&IPV4_DOCUMENTATION)
+    }
+
+    /// Returns true if this network lies within `198.18.0.0/15`, the range reserved for
+    /// benchmarking network interconnect devices by [RFC 2544].
+    ///
+    /// [RFC 2544]: https://www.rfc-editor.org/rfc/rfc2544
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("198.19.0.0/16".parse::<Ipv4Network>().unwrap().is_benchmarking());
+    /// assert!(!"198.16.0.0/13".parse::<Ipv4Network>().unwrap().is_benchmarking());
+    /// ```
+    pub fn is_benchmarking(&self) -> bool {
+        self.is_within_any(&[IPV4_BENCHMARKING])
+    }
+
+    /// Returns true if this network lies within `100.64.0.0/10`, the shared address space
+    /// used for carrier-grade NAT as defined in [RFC 6598].
+    ///
+    /// [RFC 6598]: https://www.rfc-editor.org/rfc/rfc6598
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("100.100.0.0/16".parse::<Ipv4Network>().unwrap().is_shared());
+    /// assert!(!"100.0.0.0/8".parse::<Ipv4Network>().unwrap().is_shared());
+    /// assert!(!"10.0.0.0/8".parse::<Ipv4Network>().unwrap().is_shared());
+    /// ```
+    pub fn is_shared(&self) -> bool {
+        self.is_within_any(&[IPV4_SHARED])
+    }
+
+    /// Returns true if this network lies within `240.0.0.0/4`, the former class E space
+    /// reserved for future use, and does not include the limited broadcast address
+    /// `255.255.255.255`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("240.0.0.0/8".parse::<Ipv4Network>().unwrap().is_reserved());
+    /// assert!("255.255.255.254/32".parse::<Ipv4Network>().unwrap().is_reserved());
+    /// assert!(!"255.255.255.255/32".parse::<Ipv4Network>().unwrap().is_reserved());
+    /// assert!(!"240.0.0.0/4".parse::<Ipv4Network>().unwrap().is_reserved());
+    /// ```
+    pub fn is_reserved(&self) -> bool {
+        self.is_within_any(&[IPV4_RESERVED]) && !self.overlaps(IPV4_LIMITED_BROADCAST)
+    }
+
+    /// Returns true if this network lies within one of the private-use ranges of [RFC 1918]:
+    /// `10.0.0.0/8`, `172.16.0.0/12` and `192.168.0.0/16`.
+    ///
+    /// [RFC 1918]: https://www.rfc-editor.org/rfc/rfc1918
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("172.20.0.0/16".parse::<Ipv4Network>().unwrap().is_private());
+    /// assert!(!"172.0.0.0/8".parse::<Ipv4Network>().unwrap().is_private());
+    /// assert!(!"100.64.0.0/10".parse::<Ipv4Network>().unwrap().is_private());
+    /// ```
+    pub fn is_private(&self) -> bool {
+        self.is_within_any(&IPV4_PRIVATE)
+    }
+
+    /// Returns true if this network lies within `127.0.0.0/8`, the loopback range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("127.0.0.1/32".parse::<Ipv4Network>().unwrap().is_loopback());
+    /// assert!(!"126.0.0.0/7".parse::<Ipv4Network>().unwrap().is_loopback());
+    /// ```
+    pub fn is_loopback(&self) -> bool {
+        self.is_within_any(&[IPV4_LOOPBACK])
+    }
+
+    /// Returns true if this network lies within `169.254.0.0/16`, the link-local range defined
+    /// in [RFC 3927].
+    ///
+    /// [RFC 3927]: https://www.rfc-editor.org/rfc/rfc3927
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("169.254.1.0/24".parse::<Ipv4Network>().unwrap().is_link_local());
+    /// assert!(!"169.0.0.0/8".parse::<Ipv4Network>().unwrap().is_link_local());
+    /// ```
+    pub fn is_link_local(&self) -> bool {
+        self.is_within_any(&[IPV4_LINK_LOCAL])
+    }
+
+    /// The IPv4 multicast range `224.0.0.0/4`, see [RFC 5771].
+    ///
+    /// [RFC 5771]: https://www.rfc-editor.org/rfc/rfc5771
+    pub const MULTICAST: Ipv4Network = v4(224, 0, 0, 0, 4);
+
+    /// The Source-Specific Multicast range `232.0.0.0/8`, see [RFC 4607].
+    ///
+    /// [RFC 4607]: https://www.rfc-editor.org/rfc/rfc4607
+    pub const MULTICAST_SSM: Ipv4Network = v4(232, 0, 0, 0, 8);
+
+    /// The GLOP range `233.0.0.0/8`, which maps each 16-bit AS number to a `/24` as defined in
+    /// [RFC 3180].
+    ///
+    /// [RFC 3180]: https://www.rfc-editor.org/rfc/rfc3180
+    pub const MULTICAST_GLOP: Ipv4Network = v4(233, 0, 0, 0, 8);
+
+    /// The administratively scoped multicast range `239.0.0.0/8`, see [RFC 2365].
+    ///
+    /// [RFC 2365]: https://www.rfc-editor.org/rfc/rfc2365
+    pub const MULTICAST_ADMIN_SCOPED: Ipv4Network = v4(239, 0, 0, 0, 8);
+
+    /// Returns true if this network lies within [`Ipv4Network::MULTICAST`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("224.0.0.0/24".parse::<Ipv4Network>().unwrap().is_multicast());
+    /// assert!(!"224.0.0.0/3".parse::<Ipv4Network>().unwrap().is_multicast());
+    /// ```
+    pub fn is_multicast(&self) -> bool {
+        self.is_within_any(&[Self::MULTICAST])
+    }
+
+    /// Returns true if this network lies within [`Ipv4Network::MULTICAST_SSM`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("232.1.2.0/24".parse::<Ipv4Network>().unwrap().is_multicast_ssm());
+    /// assert!(!"233.1.2.0/24".parse::<Ipv4Network>().unwrap().is_multicast_ssm());
+    /// ```
+    pub fn is_multicast_ssm(&self) -> bool {
+        self.is_within_any(&[Self::MULTICAST_SSM])
+    }
+
+    /// Returns true if this network lies within [`Ipv4Network::MULTICAST_GLOP`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("233.252.0.0/24".parse::<Ipv4Network>().unwrap().is_multicast_glop());
+    /// assert!(!"232.0.0.0/7".parse::<Ipv4Network>().unwrap().is_multicast_glop());
+    /// ```
+    pub fn is_multicast_glop(&self) -> bool {
+        self.is_within_any(&[Self::MULTICAST_GLOP])
+    }
+
+    /// Returns true if this network lies within [`Ipv4Network::MULTICAST_ADMIN_SCOPED`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!("239.255.0.0/16".parse::<Ipv4Network>().unwrap().is_multicast_admin_scoped());
+    /// assert!(!"238.0.0.0/8".parse::<Ipv4Network>().unwrap().is_multicast_admin_scoped());
+    /// ```
+    pub fn is_multicast_admin_scoped(&self) -> bool {
+        self.is_within_any(&[Self::MULTICAST_ADMIN_SCOPED])
+    }
+
+    /// Returns the GLOP `/24` of the 16-bit AS number `asn`, `233.X.Y.0/24` where `X.Y` are the
+    /// bytes of `asn` as defined in [RFC 3180].
+    ///
+    /// [RFC 3180]: https://www.rfc-editor.org/rfc/rfc3180
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert_eq!(Ipv4Network::glop(5662), "233.22.30.0/24".parse().unwrap());
+    /// ```
+    pub const fn glop(asn: u16) -> Ipv4Network {
+        let [x, y] = asn.to_be_bytes();
+        v4(233, x, y, 0, 24)
+    }
+}
+
+/// The scope of an IPv6 multicast network, from the scope field of its addresses.
+///
+/// See [IETF RFC 7346] for the defined scopes.
+///
+/// [IETF RFC 7346]: https://tools.ietf.org/html/rfc7346
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Ipv6MulticastScope {
+    /// Interface-Local scope (`1`).
+    InterfaceLocal,
+    /// Link-Local scope (`2`).
+    LinkLocal,
+    /// Realm-Local scope (`3`).
+    RealmLocal,
+    /// Admin-Local scope (`4`).
+    AdminLocal,
+    /// Site-Local scope (`5`).
+    SiteLocal,
+    /// Organization-Local scope (`8`).
+    OrganizationLocal,
+    /// Global scope (`e`).
+    Global,
+}
+
+/// Returns true if every address from `lo` to `hi`, both inclusive, lies in the union of
+/// `IPV6_GLOBAL_EXCEPTIONS`, which is sorted by first address.
+fn is_covered_by_exceptions(lo: u128, hi: u128) -> bool {
+    let mut next = lo;
+    for exception in IPV6_GLOBAL_EXCEPTIONS {
+        let (first, last) = (
+            exception.network().to_bits(),
+            exception.broadcast().to_bits(),
+        );
+        if last < next {
+            continue;
+        }
+        if first > next {
+            return false;
+        }
+        if last >= hi {
+            return true;
+        }
+        next = last + 1;
+    }
+    false
+}
+
+impl Ipv6Network {
+    /// Returns true if this network lies entirely within one of `blocks`.
+    fn is_within_any(&self, blocks: &[Ipv6Network]) -> bool {
+        blocks.iter().any(|block| {
+            matches!(
+                self.relation(*block),
+                NetworkRelation::Equal | NetworkRelation::SubsetOf
+            )
+        })
+    }
+
+    /// Returns true if every address in this network is globally reachable according to the
+    /// IANA IPv6 Special-Purpose Address Registry.
+    ///
+    /// This includes recent allocations that the unstable `Ipv6Addr::is_global` may not know
+    /// about yet, such as the `3fff::/20` documentation prefix and the Dummy IPv6 Prefix
+    /// `100:0:0:1::/64`. A network that merely overlaps a non-global block is not global, unless
+    /// the overlap is covered by globally reachable entries nested in that block, as for
+    /// `2001:20::/27`, which spans ORCHIDv2 and the Drone Remote ID Protocol Entity Tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// assert!("2606:4700::/32".parse::<Ipv6Network>().unwrap().is_global());
+    /// assert!("2001:4:112::/48".parse::<Ipv6Network>().unwrap().is_global());
+    /// assert!("2001:20::/27".parse::<Ipv6Network>().unwrap().is_global());
+    /// assert!(!"2001::/16".parse::<Ipv6Network>().unwrap().is_global());
+    /// assert!(!"3fff:1::/32".parse::<Ipv6Network>().unwrap().is_global());
+    /// assert!(!"fd00::/8".parse::<Ipv6Network>().unwrap().is_global());
+    /// ```
+    pub fn is_global(&self) -> bool {
+        let (start, end) = (self.network().to_bits(), self.broadcast().to_bits());
+        IPV6_NOT_GLOBAL.iter().all(|block| {
+            let lo = start.max(block.network().to_bits());
+            let hi = end.min(block.broadcast().to_bits());
+            lo > hi || is_covered_by_exceptions(lo, hi)
+        })
+    }
+
+    /// Returns true if this network lies within one of the ranges reserved for documentation:
+    /// `2001:db8::/32` ([RFC 3849]) and `3fff::/20` ([RFC 9637]).
+    ///
+    /// [RFC 3849]: https://www.rfc-editor.org/rfc/rfc3849
+    /// [RFC 9637]: https://www.rfc-editor.org/rfc/rfc9637
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// assert!("2001:db8:1::/48".parse::<Ipv6Network>().unwrap().is_documentation());
+    /// assert!("3fff:fff::/32".parse::<Ipv6Network>().unwrap().is_documentation());
+    /// assert!(!"3fff::/16".parse::<Ipv6Network>().unwrap().is_documentation());
+    /// ```
+    pub fn is_documentation(&self) -> bool {
+        self.is_within_any(&IPV6_DOCUMENTATION)
+    }
+
+    /// Returns true if this network lies within `2001:2::/48`, the range reserved for
+    /// benchmarking by [RFC 5180].
+    ///
+    /// [RFC 5180]: https://www.rfc-editor.org/rfc/rfc5180
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// assert!("2001:2::/64".parse::<Ipv6Network>().unwrap().is_benchmarking());
+    /// assert!(!"2001:2::/32".parse::<Ipv6Network>().unwrap().is_benchmarking());
+    /// ```
+    pub fn is_benchmarking(&self) -> bool {
+        self.is_within_any(&[IPV6_BENCHMARKING])
+    }
+
+    /// Returns true if this network lies within one of the blocks that the IANA IPv6 Address
+    /// Space registry marks as reserved by the IETF, such as `6000::/3` or the deprecated
+    /// site-local prefix `fec0::/10`.
+    ///
+    /// Networks overlapping an assignment made out of a reserved block, such as the SRv6 SID
+    /// prefix `5f00::/16`, are not reserved. Neither are networks within `::/8` or `100::/8`,
+    /// which hold special-purpose assignments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// assert!("6000::/3".parse::<Ipv6Network>().unwrap().is_reserved());
+    /// assert!(!"4000::/3".parse::<Ipv6Network>().unwrap().is_reserved());
+    /// assert!("fec0::/10".parse::<Ipv6Network>().unwrap().is_reserved());
+    /// assert!(!"5f00::/16".parse::<Ipv6Network>().unwrap().is_reserved());
+    /// assert!(!"2001:db8::/32".parse::<Ipv6Network>().unwrap().is_reserved());
+    /// ```
+    pub fn is_reserved(&self) -> bool {
+        self.is_within_any(&IPV6_RESERVED)
+            && !IPV6_RESERVED_EXCEPTIONS
+                .iter()
+                .any(|block| self.overlaps(*block))
+    }
+
+    /// Returns true if this network lies within `fc00::/7`, the unique local address range
+    /// defined in [RFC 4193].
+    ///
+    /// [RFC 4193]: https://www.rfc-editor.org/rfc/rfc4193
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// assert!("fd12:3456:789a::/48".parse::<Ipv6Network>().unwrap().is_unique_local());
+    /// assert!(!"fc00::/6".parse::<Ipv6Network>().unwrap().is_unique_local());
+    /// ```
+    pub fn is_unique_local(&self) -> bool {
+        self.is_within_any(&[IPV6_UNIQUE_LOCAL])
+    }
+
+    /// Returns true if this network lies within `fe80::/10`, the link-local unicast range
+    /// defined in [RFC 4291].
+    ///
+    /// [RFC 4291]: https://www.rfc-editor.org/rfc/rfc4291
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// assert!("fe80::/64".parse::<Ipv6Network>().unwrap().is_unicast_link_local());
+    /// assert!(!"fe00::/8".parse::<Ipv6Network>().unwrap().is_unicast_link_local());
+    /// ```
+    pub fn is_unicast_link_local(&self) -> bool {
+        self.is_within_any(&[IPV6_UNICAST_LINK_LOCAL])
+    }
+
+    /// Returns true if this network lies within `2000::/3`, the range IANA currently allocates
+    /// global unicast addresses from.
+    ///
+    /// This only looks at the allocation, not at reachability: `2001:db8::/32` is global
+    /// unicast but not [global](Ipv6Network::is_global).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// assert!("2606:4700::/32".parse::<Ipv6Network>().unwrap().is_global_unicast());
+    /// assert!("2001:db8::/32".parse::<Ipv6Network>().unwrap().is_global_unicast());
+    /// assert!(!"fd00::/8".parse::<Ipv6Network>().unwrap().is_global_unicast());
+    /// ```
+    pub fn is_global_unicast(&self) -> bool {
+        self.is_within_any(&[IPV6_GLOBAL_UNICAST])
+    }
+
+    /// Returns true if this network lies within `2001::/32`, the prefix of Teredo addresses.
+    ///
+    /// Use [`TeredoAddr::from_ipv6`] to extract the fields embedded in a single address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// assert!("2001:0:4136:e378::/64".parse::<Ipv6Network>().unwrap().is_teredo());
+    /// assert!(!"2001::/23".parse::<Ipv6Network>().unwrap().is_teredo());
+    /// ```
+    pub fn is_teredo(&self) -> bool {
+        self.is_within_any(&[TeredoAddr::PREFIX])
+    }
+
+    /// Returns true if this network lies within the IPv6 multicast range `ff00::/8`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// assert!("ff02::/16".parse::<Ipv6Network>().unwrap().is_multicast());
+    /// assert!(!"fe00::/7".parse::<Ipv6Network>().unwrap().is_multicast());
+    /// ```
+    pub fn is_multicast(&self) -> bool {
+        self.is_within_any(&[IPV6_MULTICAST])
+    }
+
+    /// Returns the multicast scope shared by every address in this network.
+    ///
+    /// Returns `None` if the network is not entirely multicast, if its prefix is too short
+    /// to fix the scope field (shorter than `/16`), or if the scope is reserved or
+    /// unassigned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{Ipv6MulticastScope, Ipv6Network};
+    ///
+    /// let all_nodes: Ipv6Network = "ff02::1/128".parse().unwrap();
+    /// assert_eq!(all_nodes.multicast_scope(), Some(Ipv6MulticastScope::LinkLocal));
+    /// let ssm: Ipv6Network = "ff3e::/32".parse().unwrap();
+    /// assert_eq!(ssm.multicast_scope(), Some(Ipv6MulticastScope::Global));
+    /// let any: Ipv6Network = "ff00::/8".parse().unwrap();
+    /// assert_eq!(any.multicast_scope(), None);
+    /// ```
+    pub fn multicast_scope(&self) -> Option<Ipv6MulticastScope> {
+        if !self.is_multicast() || self.prefix() < 16 {
+            return None;
+        }
+        match self.network().segments()[0] & 0xf {
+            0x1 => Some(Ipv6MulticastScope::InterfaceLocal),
+            0x2 => Some(Ipv6MulticastScope::LinkLocal),
+            0x3 => Some(Ipv6MulticastScope::RealmLocal),
+            0x4 => Some(Ipv6MulticastScope::AdminLocal),
+            0x5 => Some(Ipv6MulticastScope::SiteLocal),
+            0x8 => Some(Ipv6MulticastScope::OrganizationLocal),
+            0xe => Some(Ipv6MulticastScope::Global),
+            _ => None,
+        }
+    }
+}
+
+impl IpNetwork {
+    /// Returns true if every address in this network is globally reachable according to the
+    /// IANA special-purpose address registry of its family.
+    ///
+    /// See [`Ipv4Network::is_global`] and [`Ipv6Network::is_global`].
+    pub fn is_global(&self) -> bool {
+        match self {
+            IpNetwork::V4(net) => net.is_global(),
+            IpNetwork::V6(net) => net.is_global(),
+        }
+    }
+
+    /// Returns true if this network lies within one of the ranges reserved for documentation.
+    ///
+    /// See [`Ipv4Network::is_documentation`] and [`Ipv6Network::is_documentation`].
+    pub fn is_documentation(&self) -> bool {
+        match self {
+            IpNetwork::V4(net) => net.is_documentation(),
+            IpNetwork::V6(net) => net.is_documentation(),
+        }
+    }
+
+    /// Returns true if this network lies within the range reserved for benchmarking.
+    ///
+    /// See [`Ipv4Network::is_benchmarking`] and [`Ipv6Network::is_benchmarking`].
+    pub fn is_benchmarking(&self) -> bool {
+        match self {
+            IpNetwork::V4(net) => net.is_benchmarking(),
+            IpNetwork::V6(net) => net.is_benchmarking(),
+        }
+    }
+
+    /// Returns true if this is an IPv4 network within the shared address space.
+    ///
+    /// See [`Ipv4Network::is_shared`]. There is no IPv6 equivalent, so this is always false for
+    /// IPv6 networks.
+    pub fn is_shared(&self) -> bool {
+        match self {
+            IpNetwork::V4(net) => net.is_shared(),
+            IpNetwork::V6(_) => false,
+        }
+    }
+
+    /// Returns true if this network lies within a range reserved for future use.
+    ///
+    /// See [`Ipv4Network::is_reserved`] and [`Ipv6Network::is_reserved`].
+    pub fn is_reserved(&self) -> bool {
+        match self {
+            IpNetwork::V4(net) => net.is_reserved(),
+            IpNetwork::V6(net) => net.is_reserved(),
+        }
+    }
+
+    /// Returns true if this network lies within the multicast range of its family.
+    ///
+    /// See [`Ipv4Network::is_multicast`] and [`Ipv6Network::is_multicast`].
+    pub fn is_multicast(&self) -> bool {
+        match self {
+            IpNetwork::V4(net) => net.is_multicast(),
+            IpNetwork::V6(net) => net.is_multicast(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn v4net(s: &str) -> Ipv4Network {
+        s.parse().unwrap()
+    }
+
+    fn v6net(s: &str) -> Ipv6Network {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn is_global_v4() {
+        assert!(v4net("1.1.1.1").is_global());
+        assert!(v4net("224.0.0.0/4").is_global());
+        assert!(v4net("192.31.196.0/24").is_global());
+        assert!(v4net("192.0.0.10/32").is_global());
+        assert!(v4net("100.128.0.0/9").is_global());
+        assert!(!v4net("100.64.0.0/9").is_global());
+        assert!(!v4net("192.0.0.0/28").is_global());
+        assert!(!v4net("172.31.255.255").is_global());
+        assert!(!v4net("198.19.0.0/16").is_global());
+        assert!(!v4net("255.255.255.255").is_global());
+        assert!(!v4net("248.0.0.0/5").is_global());
+        // Host bits must not affect the answer.
+        assert!(!v4net("10.0.0.1/7").is_global());
+    }
+
+    #[test]
+    fn is_global_v6() {
+        assert!(v6net("2001:1::3/128").is_global());
+        assert!(v6net("2001:1::2/127").is_global());
+        assert!(v6net("2001:20::/27").is_global());
+        assert!(v6net("2001:2f::/32").is_global());
+        assert!(v6net("2620:4f:8000::/48").is_global());
+        assert!(v6net("ff0e::/16").is_global());
+        assert!(!v6net("2001:1::/120").is_global());
+        assert!(!v6net("2001:2::/48").is_global());
+        assert!(!v6net("100:0:0:1::1").is_global());
+        assert!(!v6net("::ffff:8.8.8.8").is_global());
+        assert!(!v6net("5f00:1::/32").is_global());
+        assert!(!v6net("2002::/16").is_global());
+        assert!(!v6net("::/0").is_global());
+    }
+
+    #[test]
+    fn is_private_loopback_link_local() {
+        assert!(v4net("10.255.0.0/16").is_private());
+        assert!(v4net("172.31.255.255").is_private());
+        assert!(v4net("192.168.0.0/16").is_private());
+        assert!(!v4net("172.32.0.0/16").is_private());
+        assert!(!v4net("192.168.0.0/15").is_private());
+        assert!(v4net("127.0.0.0/8").is_loopback());
+        assert!(!v4net("127.0.0.0/7").is_loopback());
+        assert!(v4net("169.254.255.255").is_link_local());
+        assert!(!v4net("169.255.0.0/16").is_link_local());
+    }
+
+    #[test]
+    fn is_documentation() {
+        assert!(v4net("192.0.2.0/24").is_documentation());
+        assert!(v4net("203.0.113.7").is_documentation());
+        assert!(!v4net("192.0.2.0/23").is_documentation());
+        assert!(!v4net("8.8.8.8").is_documentation());
+        assert!(v6net("2001:db8::/32").is_documentation());
+        assert!(v6net("3fff::1").is_documentation());
+        assert!(!v6net("3fff:1000::/20").is_documentation());
+        assert!(!v6net("2001:db9::/32").is_documentation());
+    }
+
+    #[test]
+    fn is_benchmarking() {
+        assert!(v4net("198.18.0.0/15").is_benchmarking());
+        assert!(v4net("198.19.255.255").is_benchmarking());
+        assert!(!v4net("198.20.0.0").is_benchmarking());
+        assert!(v6net("2001:2::1").is_benchmarking());
+        assert!(!v6net("2001:2:1::/48").is_benchmarking());
+        let net: IpNetwork = "198.18.1.0/24".parse().unwrap();
+        assert!(net.is_benchmarking());
+    }
+
+    #[test]
+    fn is_shared() {
+        assert!(v4net("100.64.0.0/10").is_shared());
+        assert!(v4net("100.127.255.255").is_shared());
+        assert!(!v4net("100.128.0.0").is_shared());
+        assert!(!v4net("100.64.0.0/9").is_shared());
+        let net: IpNetwork = "::ffff:100.64.0.1".parse().unwrap();
+        assert!(!net.is_shared());
+    }
+
+    #[test]
+    fn is_reserved() {
+        assert!(v4net("240.0.0.1").is_reserved());
+        assert!(v4net("254.0.0.0/8").is_reserved());
+        assert!(!v4net("255.0.0.0/8").is_reserved());
+        assert!(!v4net("224.0.0.0/4").is_reserved());
+        assert!(v6net("a000::/3").is_reserved());
+        assert!(v6net("fe00::/9").is_reserved());
+        assert!(v6net("5eff::/16").is_reserved());
+        assert!(!v6net("5f00:1::/32").is_reserved());
+        assert!(!v6net("4000::/2").is_reserved());
+        assert!(!v6net("fe80::/10").is_reserved());
+        assert!(!v6net("::1").is_reserved());
+    }
+
+    #[test]
+    fn ipv6_unicast_categories() {
+        assert!(v6net("fc00::/7").is_unique_local());
+        assert!(v6net("fdff::1").is_unique_local());
+        assert!(!v6net("fe00::1").is_unique_local());
+        assert!(v6net("febf:ffff::/32").is_unicast_link_local());
+        assert!(!v6net("fec0::/10").is_unicast_link_local());
+        assert!(!v6net("fe80::/9").is_unicast_link_local());
+        assert!(v6net("2000::/3").is_global_unicast());
+        assert!(v6net("3fff:ffff::1").is_global_unicast());
+        assert!(!v6net("2000::/2").is_global_unicast());
+        assert!(!v6net("::1").is_global_unicast());
+    }
+
+    #[test]
+    fn ipv6_multicast_scope() {
+        use Ipv6MulticastScope::*;
+        assert_eq!(v6net("ff01::/16").multicast_scope(), Some(InterfaceLocal));
+        assert_eq!(v6net("ff12::1:3").multicast_scope(), Some(LinkLocal));
+        assert_eq!(v6net("ff05::/64").multicast_scope(), Some(SiteLocal));
+        assert_eq!(
+            v6net("ff08::/16").multicast_scope(),
+            Some(OrganizationLocal)
+        );
+        assert_eq!(v6net("ff0e::/16").multicast_scope(), Some(Global));
+        assert_eq!(v6net("ff00::/15").multicast_scope(), None);
+        assert_eq!(v6net("ff0f::/16").multicast_scope(), None);
+        assert_eq!(v6net("ff06::1").multicast_scope(), None);
+        assert_eq!(v6net("fe02::/16").multicast_scope(), None);
+    }
+
+    #[test]
+    fn ipv4_multicast_ranges() {
+        assert!(v4net("239.255.255.255").is_multicast());
+        assert!(!v4net("240.0.0.0").is_multicast());
+        assert!(v4net("232.0.0.0/8").is_multicast_ssm());
+        assert!(!v4net("232.0.0.0/7").is_multicast_ssm());
+        assert!(v4net("233.255.255.0/24").is_multicast_glop());
+        assert!(v4net("239.0.0.0/8").is_multicast_admin_scoped());
+        assert!(!v4net("239.0.0.0/8").is_multicast_glop());
+        assert_eq!(Ipv4Network::glop(0), v4net("233.0.0.0/24"));
+        assert_eq!(Ipv4Network::glop(u16::MAX), v4net("233.255.255.0/24"));
+        let net: IpNetwork = "ff05::2".parse().unwrap();
+        assert!(net.is_multicast());
+    }
+
+    #[test]
+    fn is_teredo() {
+        assert!(v6net("2001::/32").is_teredo());
+        assert!(v6net("2001:0:ffff::1").is_teredo());
+        assert!(!v6net("2001:1::/32").is_teredo());
+    }
+
+    #[test]
+    fn is_global_ip() {
+        let net: IpNetwork = "8.8.8.8".parse().unwrap();
+        assert!(net.is_global());
+        let net: IpNetwork = "fe80::1".parse().unwrap();
+        assert!(!net.is_global());
+    }
+}
@@ -0,0 +1,240 @@
+//! Loaders for the IP range feeds published by cloud providers.
+//!
+//! Every loader groups the networks of a feed by the service and region they are published
+//! for, into an [`IpNetworkSet`] per group.
+
+use crate::error::LoadError;
+use crate::{IpNetwork, IpNetworkSet, Ipv4Network, Ipv6Network};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io;
+
+/// The service and region a group of networks in a cloud provider feed is published for.
+///
+/// Networks that are not tied to a region have an empty region, or the marker the provider
+/// uses for them, such as `GLOBAL` for AWS.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CloudTag {
+    /// The service, such as `EC2` for AWS, the tag name for Azure, or `Google Cloud`.
+    pub service: String,
+    /// The region, such as `us-west-2`, or the scope for Google Cloud.
+    pub region: String,
+}
+
+impl CloudTag {
+    fn new(service: String, region: String) -> CloudTag {
+        CloudTag { service, region }
+    }
+}
+
+/// The networks of a cloud provider feed, grouped by service and region.
+pub type CloudRanges = BTreeMap<CloudTag, IpNetworkSet>;
+
+#[derive(Deserialize)]
+struct AwsRanges {
+    prefixes: Vec<AwsIpv4Prefix>,
+    ipv6_prefixes: Vec<AwsIpv6Prefix>,
+}
+
+#[derive(Deserialize)]
+struct AwsIpv4Prefix {
+    ip_prefix: Ipv4Network,
+    region: String,
+    service: String,
+}
+
+#[derive(Deserialize)]
+struct AwsIpv6Prefix {
+    ipv6_prefix: Ipv6Network,
+    region: String,
+    service: String,
+}
+
+#[derive(Deserialize)]
+struct AzureServiceTags {
+    values: Vec<AzureServiceTag>,
+}
+
+#[derive(Deserialize)]
+struct AzureServiceTag {
+    name: String,
+    properties: AzureProperties,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureProperties {
+    region: String,
+    address_prefixes: Vec<IpNetwork>,
+}
+
+#[derive(Deserialize)]
+struct GcpRanges {
+    prefixes: Vec<GcpPrefix>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcpPrefix {
+    ipv4_prefix: Option<Ipv4Network>,
+    ipv6_prefix: Option<Ipv6Network>,
+    service: String,
+    scope: String,
+}
+
+fn from_json<R: io::Read, T: for<'de> Deserialize<'de>>(reader: R) -> Result<T, LoadError> {
+    serde_json::from_reader(reader).map_err(|e| LoadError::Record {
+        line: e.line() as u64,
+        message: e.to_string(),
+    })
+}
+
+fn group<I: IntoIterator<Item = (CloudTag, IpNetwork)>>(entries: I) -> CloudRanges {
+    let mut groups: BTreeMap<CloudTag, Vec<IpNetwork>> = BTreeMap::new();
+    for (tag, net) in entries {
+        groups.entry(tag).or_default().push(net);
+    }
+    groups
+        .into_iter()
+        .map(|(tag, networks)| (tag, IpNetworkSet::from(networks)))
+        .collect()
+}
+
+/// Loads the AWS `ip-ranges.json` feed, grouping networks by their `service` and `region`.
+///
+/// AWS lists the networks of specific services under the `AMAZON` service as well.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{load_aws_ip_ranges, CloudTag, IpNetwork};
+///
+/// let data = r#"{
+///   "syncToken": "1700000000",
+///   "prefixes": [
+///     {"ip_prefix": "3.5.140.0/22", "region": "ap-northeast-2", "service": "AMAZON",
+///      "network_border_group": "ap-northeast-2"}
+///   ],
+///   "ipv6_prefixes": [
+///     {"ipv6_prefix": "2600:1f14::/35", "region": "us-west-2", "service": "EC2",
+///      "network_border_group": "us-west-2"}
+///   ]
+/// }"#;
+/// let ranges = load_aws_ip_ranges(data.as_bytes()).unwrap();
+/// let tag = CloudTag { service: "EC2".to_string(), region: "us-west-2".to_string() };
+/// assert_eq!(ranges[&tag].as_slice(), ["2600:1f14::/35".parse::<IpNetwork>().unwrap()]);
+/// assert!(ranges[&tag].contains("2600:1f14::1".parse().unwrap()));
+/// ```
+pub fn load_aws_ip_ranges<R: io::Read>(reader: R) -> Result<CloudRanges, LoadError> {
+    let feed: AwsRanges = from_json(reader)?;
+    let v4 = feed.prefixes.into_iter().map(|p| {
+        let tag = CloudTag::new(p.service, p.region);
+        (tag, IpNetwork::V4(p.ip_prefix))
+    });
+    let v6 = feed.ipv6_prefixes.into_iter().map(|p| {
+        let tag = CloudTag::new(p.service, p.region);
+        (tag, IpNetwork::V6(p.ipv6_prefix))
+    });
+    Ok(group(v4.chain(v6)))
+}
+
+/// Loads an Azure Service Tags feed, grouping networks by the tag `name` and its `region`.
+///
+/// Regional tags such as `Storage.WestUS` are kept apart from the global `Storage` tag, which
+/// has an empty region.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{load_azure_service_tags, CloudTag};
+///
+/// let data = r#"{
+///   "changeNumber": 1,
+///   "cloud": "Public",
+///   "values": [{
+///     "name": "Storage.WestUS",
+///     "id": "Storage.WestUS",
+///     "properties": {
+///       "region": "westus",
+///       "systemService": "AzureStorage",
+///       "addressPrefixes": ["13.88.144.248/29", "2603:1030:a09::/48"]
+///     }
+///   }]
+/// }"#;
+/// let ranges = load_azure_service_tags(data.as_bytes()).unwrap();
+/// let tag = CloudTag { service: "Storage.WestUS".to_string(), region: "westus".to_string() };
+/// assert_eq!(ranges[&tag].len(), 2);
+/// ```
+pub fn load_azure_service_tags<R: io::Read>(reader: R) -> Result<CloudRanges, LoadError> {
+    let feed: AzureServiceTags = from_json(reader)?;
+    Ok(group(feed.values.into_iter().flat_map(|value| {
+        let tag = CloudTag::new(value.name, value.properties.region);
+        let networks = value.properties.address_prefixes;
+        networks.into_iter().map(move |net| (tag.clone(), net))
+    })))
+}
+
+/// Loads the Google Cloud `cloud.json` feed, grouping networks by their `service` and `scope`.
+///
+/// Entries without an `ipv4Prefix` or `ipv6Prefix` are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{load_gcp_cloud_ranges, CloudTag, IpNetwork};
+///
+/// let data = r#"{
+///   "syncToken": "1700000000000",
+///   "prefixes": [
+///     {"ipv4Prefix": "34.35.0.0/16", "service": "Google Cloud", "scope": "africa-south1"},
+///     {"ipv6Prefix": "2600:1900:8000::/44", "service": "Google Cloud", "scope": "africa-south1"}
+///   ]
+/// }"#;
+/// let ranges = load_gcp_cloud_ranges(data.as_bytes()).unwrap();
+/// let tag = CloudTag { service: "Google Cloud".to_string(), region: "africa-south1".to_string() };
+/// assert_eq!(ranges[&tag].as_slice()[0], "34.35.0.0/16".parse::<IpNetwork>().unwrap());
+/// ```
+pub fn load_gcp_cloud_ranges<R: io::Read>(reader: R) -> Result<CloudRanges, LoadError> {
+    let feed: GcpRanges = from_json(reader)?;
+    Ok(group(feed.prefixes.into_iter().flat_map(|p| {
+        let tag = CloudTag::new(p.service, p.scope);
+        let v4 = p.ipv4_prefix.map(IpNetwork::V4);
+        let v6 = p.ipv6_prefix.map(IpNetwork::V6);
+        v4.into_iter().chain(v6).map(move |net| (tag.clone(), net))
+    })))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn aws_ranges_are_merged_per_tag() {
+        let data = r#"{"prefixes": [
+            {"ip_prefix": "10.0.1.0/24", "region": "r", "service": "S"},
+            {"ip_prefix": "10.0.0.0/24", "region": "r", "service": "S"},
+            {"ip_prefix": "10.0.2.0/24", "region": "GLOBAL", "service": "S"}
+        ], "ipv6_prefixes": []}"#;
+        let ranges = load_aws_ip_ranges(data.as_bytes()).unwrap();
+        assert_eq!(ranges.len(), 2);
+        let tag = CloudTag::new("S".to_string(), "r".to_string());
+        assert_eq!(
+            ranges[&tag].as_slice(),
+            ["10.0.0.0/23".parse::<IpNetwork>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn cloud_feed_errors_report_line() {
+        let data = concat!(
+            "{\"prefixes\": [\n",
+            "{\"ip_prefix\": \"2001:db8::/32\", \"region\": \"r\", \"service\": \"S\"}\n",
+            "], \"ipv6_prefixes\": []}"
+        );
+        let err = load_aws_ip_ranges(data.as_bytes()).unwrap_err();
+        assert_eq!(err.line(), 2);
+
+        let err = load_gcp_cloud_ranges("{\"prefixes\": 1}".as_bytes()).unwrap_err();
+        assert!(matches!(err, LoadError::Record { line: 1, .. }));
+    }
+}
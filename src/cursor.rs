@@ -0,0 +1,164 @@
+use crate::error::IpNetworkError;
+use crate::IpNetwork;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// A resumable iterator over the addresses of an `IpNetwork`.
+///
+/// The cursor only consists of the network being scanned and the next address to yield, so
+/// it can be persisted (with the `serde` feature) to checkpoint a long running scan and
+/// resumed later with the same sequence of addresses.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::IpAddr;
+/// use ipnetwork::{IpNetwork, IpNetworkCursor};
+///
+/// let net: IpNetwork = "10.0.0.0/30".parse().unwrap();
+/// let mut cursor = IpNetworkCursor::new(net);
+/// assert_eq!(cursor.next(), Some("10.0.0.0".parse::<IpAddr>().unwrap()));
+///
+/// // Checkpoint and resume from the saved position.
+/// let position = cursor.position().unwrap();
+/// let resumed = IpNetworkCursor::with_position(net, position).unwrap();
+/// assert_eq!(resumed.count(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IpNetworkCursor {
+    network: IpNetwork,
+    next: Option<IpAddr>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IpNetworkCursor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (network, next) = <(IpNetwork, Option<IpAddr>)>::deserialize(deserializer)?;
+        match next {
+            Some(next) => IpNetworkCursor::with_position(network, next),
+            None => Ok(IpNetworkCursor {
+                network,
+                next: None,
+            }),
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IpNetworkCursor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&(self.network, self.next), serializer)
+    }
+}
+
+impl IpNetworkCursor {
+    /// Constructs a cursor positioned at the first address of `network`.
+    pub const fn new(network: IpNetwork) -> IpNetworkCursor {
+        IpNetworkCursor {
+            network,
+            next: Some(network.network()),
+        }
+    }
+
+    /// Constructs a cursor over `network` whose next address will be `next`.
+    ///
+    /// If `next` is not contained in `network` this will return an
+    /// `IpNetworkError::InvalidAddr`.
+    pub fn with_position(network: IpNetwork, next: IpAddr) -> Result<Self, IpNetworkError> {
+        if !network.contains(next) {
+            return Err(IpNetworkError::InvalidAddr(format!(
+                "{next} is not in {network}"
+            )));
+        }
+        Ok(IpNetworkCursor {
+            network,
+            next: Some(next),
+        })
+    }
+
+    /// Returns the network this cursor iterates over.
+    pub const fn network(&self) -> IpNetwork {
+        self.network
+    }
+
+    /// Returns the next address the cursor will yield, or `None` if the scan is complete.
+    pub const fn position(&self) -> Option<IpAddr> {
+        self.next
+    }
+}
+
+impl Iterator for IpNetworkCursor {
+    type Item = IpAddr;
+
+    fn next(&mut self) -> Option<IpAddr> {
+        let next = self.next?;
+        self.next = if next == self.network.broadcast() {
+            None
+        } else {
+            Some(match next {
+                IpAddr::V4(a) => IpAddr::V4(Ipv4Addr::from(u32::from(a) + 1)),
+                IpAddr::V6(a) => IpAddr::V6(Ipv6Addr::from(u128::from(a) + 1)),
+            })
+        };
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cursor_matches_iter() {
+        let net: IpNetwork = "192.168.1.0/29".parse().unwrap();
+        let cursor = IpNetworkCursor::new(net);
+        assert!(cursor.eq(net.iter()));
+    }
+
+    #[test]
+    fn cursor_resume() {
+        let net: IpNetwork = "2001:db8::/126".parse().unwrap();
+        let mut cursor = IpNetworkCursor::new(net);
+        cursor.next();
+        cursor.next();
+        let resumed = IpNetworkCursor::with_position(net, cursor.position().unwrap()).unwrap();
+        assert_eq!(resumed, cursor);
+        assert!(resumed.eq(net.iter().skip(2)));
+    }
+
+    #[test]
+    fn cursor_exhausted() {
+        let net: IpNetwork = "255.255.255.255/32".parse().unwrap();
+        let mut cursor = IpNetworkCursor::new(net);
+        assert_eq!(cursor.next(), Some(net.ip()));
+        assert_eq!(cursor.position(), None);
+        assert_eq!(cursor.next(), None);
+    }
+
+    #[test]
+    fn cursor_position_outside_network() {
+        let net: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        assert!(IpNetworkCursor::with_position(net, "11.0.0.0".parse().unwrap()).is_err());
+        assert!(IpNetworkCursor::with_position(net, "::1".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn cursor_serde_round_trip() {
+        let net: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        let cursor = IpNetworkCursor::with_position(net, "10.1.2.3".parse().unwrap()).unwrap();
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(json, r#"["10.0.0.0/8","10.1.2.3"]"#);
+        assert_eq!(
+            serde_json::from_str::<IpNetworkCursor>(&json).unwrap(),
+            cursor
+        );
+        assert!(serde_json::from_str::<IpNetworkCursor>(r#"["10.0.0.0/8","11.0.0.0"]"#).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Conversions for the address blocks of RIR delegated-stats records.
+//!
+//! IPv4 records give the first address of a block and its number of addresses, which need not
+//! be a power of two. IPv6 records give the first address and a prefix length.
+
+use crate::error::IpNetworkError;
+use crate::range::{push_cidrs, IPV4_BITS};
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Returns the minimal list of networks covering the `count` addresses starting at `start`.
+///
+/// Returns an error if `count` is zero or the block runs past `255.255.255.255`.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::Ipv4Addr;
+/// use ipnetwork::{ipv4_networks_from_count, Ipv4Network};
+///
+/// let nets = ipv4_networks_from_count(Ipv4Addr::new(192, 0, 2, 0), 384).unwrap();
+/// assert_eq!(nets, vec![
+///     "192.0.2.0/24".parse::<Ipv4Network>().unwrap(),
+///     "192.0.3.0/25".parse().unwrap(),
+/// ]);
+/// ```
+pub fn ipv4_networks_from_count(
+    start: Ipv4Addr,
+    count: u64,
+) -> Result<Vec<Ipv4Network>, IpNetworkError> {
+    let first = u64::from(start.to_bits());
+    if count == 0 {
+        return Err(IpNetworkError::InvalidRange(
+            "the address count is zero".to_string(),
+        ));
+    }
+    let last = first
+        .checked_add(count - 1)
+        .filter(|&last| last <= u64::from(u32::MAX))
+        .ok_or_else(|| {
+            IpNetworkError::InvalidRange(format!(
+                "{count} addresses from {start} run past the end of the address space"
+            ))
+        })?;
+
+    let mut networks = Vec::new();
+    push_cidrs(IPV4_BITS, first.into(), last.into(), &mut networks);
+    Ok(networks
+        .into_iter()
+        .map(|net| match net {
+            IpNetwork::V4(net) => net,
+            IpNetwork::V6(_) => unreachable!("IPv4 bounds only produce IPv4 networks"),
+        })
+        .collect())
+}
+
+/// Returns the networks described by the start and value fields of an RIR delegated-stats
+/// record.
+///
+/// For IPv4, `value` is the number of addresses in the block, see
+/// [`ipv4_networks_from_count`]. For IPv6, `value` is the prefix length, and `start` must be
+/// the first address of the network.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::IpAddr;
+/// use ipnetwork::{delegation_networks, IpNetwork};
+///
+/// // From the line "apnic|JP|ipv4|1.0.16.0|4096|20110412|allocated".
+/// let start: IpAddr = "1.0.16.0".parse().unwrap();
+/// assert_eq!(
+///     delegation_networks(start, 4096).unwrap(),
+///     vec!["1.0.16.0/20".parse::<IpNetwork>().unwrap()]
+/// );
+///
+/// // From the line "apnic|JP|ipv6|2001:200::|35|19990813|allocated".
+/// let start: IpAddr = "2001:200::".parse().unwrap();
+/// assert_eq!(
+///     delegation_networks(start, 35).unwrap(),
+///     vec!["2001:200::/35".parse::<IpNetwork>().unwrap()]
+/// );
+/// ```
+pub fn delegation_networks(start: IpAddr, value: u64) -> Result<Vec<IpNetwork>, IpNetworkError> {
+    match start {
+        IpAddr::V4(start) => Ok(ipv4_networks_from_count(start, value)?
+            .into_iter()
+            .map(IpNetwork::V4)
+            .collect()),
+        IpAddr::V6(start) => Ok(vec![IpNetwork::V6(ipv6_delegation(start, value)?)]),
+    }
+}
+
+fn ipv6_delegation(start: Ipv6Addr, prefix: u64) -> Result<Ipv6Network, IpNetworkError> {
+    let prefix = u8::try_from(prefix).map_err(|_| IpNetworkError::InvalidPrefix)?;
+    let net = Ipv6Network::new(start, prefix)?;
+    if net.network() != start {
+        return Err(IpNetworkError::InvalidRange(format!(
+            "{start} is not the first address of a /{prefix}"
+        )));
+    }
+    Ok(net)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ipv4_networks_from_count_edges() {
+        let all = ipv4_networks_from_count(Ipv4Addr::UNSPECIFIED, 1 << 32).unwrap();
+        assert_eq!(all, vec!["0.0.0.0/0".parse().unwrap()]);
+
+        let last = ipv4_networks_from_count(Ipv4Addr::BROADCAST, 1).unwrap();
+        assert_eq!(last, vec!["255.255.255.255/32".parse().unwrap()]);
+
+        let unaligned = ipv4_networks_from_count(Ipv4Addr::new(10, 0, 0, 1), 3).unwrap();
+        assert_eq!(
+            unaligned,
+            vec![
+                "10.0.0.1/32".parse().unwrap(),
+                "10.0.0.2/31".parse().unwrap()
+            ]
+        );
+
+        assert!(ipv4_networks_from_count(Ipv4Addr::new(10, 0, 0, 0), 0).is_err());
+        assert!(ipv4_networks_from_count(Ipv4Addr::BROADCAST, 2).is_err());
+    }
+
+    #[test]
+    fn delegation_networks_ipv6_errors() {
+        let start: IpAddr = "2001:db8::".parse().unwrap();
+        assert_eq!(
+            delegation_networks(start, 129),
+            Err(IpNetworkError::InvalidPrefix)
+        );
+        assert_eq!(
+            delegation_networks(start, 1 << 40),
+            Err(IpNetworkError::InvalidPrefix)
+        );
+        assert!(matches!(
+            delegation_networks(start, 16),
+            Err(IpNetworkError::InvalidRange(_))
+        ));
+    }
+}
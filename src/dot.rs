@@ -0,0 +1,104 @@
+use crate::merge::Merge;
+use crate::IpNetwork;
+use std::io;
+
+/// Writes the containment hierarchy of `networks` as a Graphviz DOT digraph.
+///
+/// Every network becomes a node, with host bits cleared and duplicates removed. Each network has
+/// an edge from the smallest other network in the list containing it, so the graph is a forest
+/// with one tree per top-level network. Nodes and edges are written in sorted order, IPv4 before
+/// IPv6, so the output is stable for a given set of networks.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{write_containment_dot, IpNetwork};
+///
+/// let networks: Vec<IpNetwork> = ["10.1.0.0/16", "10.0.0.0/8", "10.1.2.0/24", "192.0.2.0/24"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// let mut out = Vec::new();
+/// write_containment_dot(&mut out, &networks).unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     concat!(
+///         "digraph networks {\n",
+///         "    \"10.0.0.0/8\";\n",
+///         "    \"10.1.0.0/16\";\n",
+///         "    \"10.1.2.0/24\";\n",
+///         "    \"192.0.2.0/24\";\n",
+///         "    \"10.0.0.0/8\" -> \"10.1.0.0/16\";\n",
+///         "    \"10.1.0.0/16\" -> \"10.1.2.0/24\";\n",
+///         "}\n",
+///     )
+/// );
+/// ```
+pub fn write_containment_dot<W: io::Write>(
+    mut writer: W,
+    networks: &[IpNetwork],
+) -> io::Result<()> {
+    let mut sorted: Vec<IpNetwork> = networks.iter().map(|net| net.normalize()).collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    // Supernets sort before the networks they contain, so the ancestors of each network are
+    // the stack entries left after popping those that do not contain it.
+    let mut edges = Vec::new();
+    let mut ancestors: Vec<IpNetwork> = Vec::new();
+    for &net in &sorted {
+        while ancestors.last().is_some_and(|&top| !top.covers(net)) {
+            ancestors.pop();
+        }
+        if let Some(&parent) = ancestors.last() {
+            edges.push((parent, net));
+        }
+        ancestors.push(net);
+    }
+
+    writeln!(writer, "digraph networks {{")?;
+    for net in &sorted {
+        writeln!(writer, "    \"{net}\";")?;
+    }
+    for (parent, child) in edges {
+        writeln!(writer, "    \"{parent}\" -> \"{child}\";")?;
+    }
+    writeln!(writer, "}}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dot(networks: &[&str]) -> String {
+        let networks: Vec<IpNetwork> = networks.iter().map(|n| n.parse().unwrap()).collect();
+        let mut out = Vec::new();
+        write_containment_dot(&mut out, &networks).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn containment_dot_edges() {
+        let out = dot(&[
+            "::/0",
+            "10.0.0.0/8",
+            "0.0.0.0/0",
+            "10.128.0.0/9",
+            "10.0.0.1/16",
+            "10.0.0.0/16",
+            "2001:db8::/32",
+        ]);
+        let edges: Vec<&str> = out.lines().filter(|line| line.contains("->")).collect();
+        assert_eq!(
+            edges,
+            vec![
+                "    \"0.0.0.0/0\" -> \"10.0.0.0/8\";",
+                "    \"10.0.0.0/8\" -> \"10.0.0.0/16\";",
+                "    \"10.0.0.0/8\" -> \"10.128.0.0/9\";",
+                "    \"::/0\" -> \"2001:db8::/32\";",
+            ]
+        );
+        assert_eq!(out.lines().filter(|line| !line.contains("->")).count(), 8);
+        assert_eq!(dot(&[]), "digraph networks {\n}\n");
+    }
+}
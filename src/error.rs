@@ -1,4 +1,4 @@
-use std::{error::Error, fmt, net::AddrParseError};
+use std::{error::Error, fmt, io, net::AddrParseError};
 
 use crate::error::IpNetworkError::*;
 
@@ -8,8 +8,57 @@ use crate::error::IpNetworkError::*;
 pub enum IpNetworkError {
     InvalidAddr(String),
     InvalidPrefix,
+    InvalidNetmask,
     InvalidCidrFormat(String),
     NetworkSizeError(NetworkSizeError),
+    InvalidRange(String),
+}
+
+/// The category of an [`IpNetworkError`], for handling errors without matching on every
+/// variant.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The text is not a valid address or network.
+    Syntax,
+    /// The prefix length is not valid for the address family.
+    Prefix,
+    /// The netmask or wildcard mask is not contiguous.
+    Mask,
+    /// A size or range of addresses cannot be represented.
+    Range,
+}
+
+impl IpNetworkError {
+    /// Returns the category of this error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use ipnetwork::{ErrorKind, Ipv4Network};
+    ///
+    /// let err = "10.0.0/8".parse::<Ipv4Network>().unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::Syntax);
+    ///
+    /// let err = "10.0.0.0/33".parse::<Ipv4Network>().unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::Prefix);
+    ///
+    /// let err = Ipv4Network::from_addr_mask_str("10.0.0.0 255.0.255.0").unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::Mask);
+    ///
+    /// let err = io::Error::from(err);
+    /// assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    /// assert_eq!(err.to_string(), "invalid netmask");
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match *self {
+            InvalidAddr(_) | InvalidCidrFormat(_) => ErrorKind::Syntax,
+            InvalidPrefix => ErrorKind::Prefix,
+            InvalidNetmask => ErrorKind::Mask,
+            NetworkSizeError(_) | InvalidRange(_) => ErrorKind::Range,
+        }
+    }
 }
 
 impl fmt::Display for IpNetworkError {
@@ -17,8 +66,10 @@ impl fmt::Display for IpNetworkError {
         match *self {
             InvalidAddr(ref s) => write!(f, "invalid address: {s}"),
             InvalidPrefix => write!(f, "invalid prefix"),
+            InvalidNetmask => write!(f, "invalid netmask"),
             InvalidCidrFormat(ref s) => write!(f, "invalid cidr format: {s}"),
             NetworkSizeError(ref e) => write!(f, "network size error: {e}"),
+            InvalidRange(ref s) => write!(f, "invalid range: {s}"),
         }
     }
 }
@@ -28,12 +79,21 @@ impl Error for IpNetworkError {
         match *self {
             InvalidAddr(_) => "address is invalid",
             InvalidPrefix => "prefix is invalid",
+            InvalidNetmask => "netmask is invalid",
             InvalidCidrFormat(_) => "cidr is invalid",
             NetworkSizeError(_) => "network size error",
+            InvalidRange(_) => "range is invalid",
         }
     }
 }
 
+impl From<IpNetworkError> for io::Error {
+    /// Wraps the error in an `io::Error` of kind [`InvalidInput`](io::ErrorKind::InvalidInput).
+    fn from(e: IpNetworkError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, e)
+    }
+}
+
 impl From<AddrParseError> for IpNetworkError {
     fn from(e: AddrParseError) -> Self {
         InvalidAddr(e.to_string())
@@ -54,3 +114,50 @@ impl fmt::Display for NetworkSizeError {
 }
 
 impl Error for NetworkSizeError {}
+
+/// Represents an error encountered while loading networks from a prefix list, CSV or JSON lines
+/// document.
+///
+/// Every variant carries the 1-based line number of the offending record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LoadError {
+    /// The record could not be read or decoded.
+    Record { line: u64, message: String },
+    /// The record does not have the requested column or field.
+    MissingField { line: u64, field: String },
+    /// The value of the requested column or field is not a valid network.
+    InvalidNetwork { line: u64, error: IpNetworkError },
+}
+
+impl LoadError {
+    /// Returns the line of the record that caused this error.
+    pub fn line(&self) -> u64 {
+        match *self {
+            LoadError::Record { line, .. }
+            | LoadError::MissingField { line, .. }
+            | LoadError::InvalidNetwork { line, .. } => line,
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            LoadError::Record { line, ref message } => write!(f, "line {line}: {message}"),
+            LoadError::MissingField { line, ref field } => {
+                write!(f, "line {line}: missing field {field}")
+            }
+            LoadError::InvalidNetwork { line, ref error } => write!(f, "line {line}: {error}"),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            LoadError::InvalidNetwork { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
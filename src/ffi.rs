@@ -0,0 +1,64 @@
+//! Custom type support for `uniffi`, so foreign-language bindings receive networks as strings.
+//!
+//! Each network type is lowered to its CIDR notation, and strings coming from the foreign side
+//! are parsed and validated when they are lifted. A failed parse is reported to the caller as
+//! an error instead of producing an invalid network.
+
+use crate::{IpNetwork, Ipv4Network, Ipv6Network, UniffiCustomTypeConverter};
+
+uniffi::custom_type!(Ipv4Network, String);
+uniffi::custom_type!(Ipv6Network, String);
+uniffi::custom_type!(IpNetwork, String);
+
+impl UniffiCustomTypeConverter for Ipv4Network {
+    type Builtin = String;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        Ok(val.parse()?)
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.to_string()
+    }
+}
+
+impl UniffiCustomTypeConverter for Ipv6Network {
+    type Builtin = String;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        Ok(val.parse()?)
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.to_string()
+    }
+}
+
+impl UniffiCustomTypeConverter for IpNetwork {
+    type Builtin = String;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        Ok(val.parse()?)
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi::{FfiConverter, Lift, Lower};
+
+    #[test]
+    fn uniffi_round_trip() {
+        let net: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        let lowered = <IpNetwork as Lower<crate::UniFfiTag>>::lower(net);
+        let lifted = <IpNetwork as Lift<crate::UniFfiTag>>::try_lift(lowered).unwrap();
+        assert_eq!(lifted, net);
+
+        let bogus = <String as FfiConverter<crate::UniFfiTag>>::lower("10.0.0.0/33".to_string());
+        assert!(<Ipv4Network as Lift<crate::UniFfiTag>>::try_lift(bogus).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Ipv4Network, Ipv6Network};
+use std::{
+    fmt::{self, Write},
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+/// A `fmt::Write` sink that fills a byte buffer without allocating.
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        let dest = self.buf.get_mut(self.len..end).ok_or(fmt::Error)?;
+        dest.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Formats `value` into the start of `buf` and returns the written part as a `&str`.
+///
+/// The caller guarantees that `buf` is large enough for every value of the type.
+pub(crate) fn format_into<'a>(value: &impl fmt::Display, buf: &'a mut [u8]) -> &'a str {
+    let mut writer = SliceWriter { buf, len: 0 };
+    write!(writer, "{}", value).expect("buffer too small for formatted network");
+    let SliceWriter { buf, len } = writer;
+    std::str::from_utf8(&buf[..len]).expect("formatted network is valid UTF-8")
+}
+
+/// Renders `addr` as four dot-separated groups of eight binary digits, such as
+/// `11000000.10101000.00000001.00000000` for `192.168.1.0`.
+///
+/// Apply it to [`Ipv4Network::network`], [`Ipv4Network::mask`] or
+/// [`Ipv4Network::broadcast`] to show how a prefix splits an address.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{ipv4_to_binary, Ipv4Network};
+///
+/// let net: Ipv4Network = "10.0.0.0/12".parse().unwrap();
+/// assert_eq!(ipv4_to_binary(net.mask()), "11111111.11110000.00000000.00000000");
+/// ```
+pub fn ipv4_to_binary(addr: Ipv4Addr) -> String {
+    let [a, b, c, d] = addr.octets();
+    format!("{a:08b}.{b:08b}.{c:08b}.{d:08b}")
+}
+
+/// Renders `addr` as eight colon-separated groups of sixteen binary digits.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{ipv6_to_binary, Ipv6Network};
+///
+/// let net: Ipv6Network = "ff00::/8".parse().unwrap();
+/// assert!(ipv6_to_binary(net.mask()).starts_with("1111111100000000:0000000000000000:"));
+/// ```
+pub fn ipv6_to_binary(addr: Ipv6Addr) -> String {
+    let groups: Vec<String> = addr
+        .segments()
+        .iter()
+        .map(|group| format!("{group:016b}"))
+        .collect();
+    groups.join(":")
+}
+
+/// Renders `addr` fully expanded, as eight colon-separated groups of four hexadecimal digits,
+/// so that every nibble is in a fixed position.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::Ipv6Addr;
+/// use ipnetwork::ipv6_to_expanded;
+///
+/// assert_eq!(
+///     ipv6_to_expanded(Ipv6Addr::LOCALHOST),
+///     "0000:0000:0000:0000:0000:0000:0000:0001"
+/// );
+/// ```
+pub fn ipv6_to_expanded(addr: Ipv6Addr) -> String {
+    let groups: Vec<String> = addr
+        .segments()
+        .iter()
+        .map(|group| format!("{group:04x}"))
+        .collect();
+    groups.join(":")
+}
+
+impl Ipv4Network {
+    /// Returns the address of this network in binary, as rendered by [`ipv4_to_binary`],
+    /// followed by the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.168.1.0/24".parse().unwrap();
+    /// assert_eq!(net.to_binary_string(), "11000000.10101000.00000001.00000000/24");
+    /// ```
+    pub fn to_binary_string(&self) -> String {
+        format!("{}/{}", ipv4_to_binary(self.ip()), self.prefix())
+    }
+
+    /// Returns the network address and wildcard mask of this network separated by a space, as
+    /// written in Cisco ACLs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.0.0.1/24".parse().unwrap();
+    /// assert_eq!(net.to_wildcard_string(), "10.0.0.0 0.0.0.255");
+    ///
+    /// let parsed = Ipv4Network::from_addr_wildcard_str(&net.to_wildcard_string()).unwrap();
+    /// assert_eq!(parsed, "10.0.0.0/24".parse().unwrap());
+    /// ```
+    pub fn to_wildcard_string(&self) -> String {
+        format!("{} {}", self.network(), self.wildcard_mask())
+    }
+}
+
+impl Ipv6Network {
+    /// Returns the address of this network in binary, as rendered by [`ipv6_to_binary`],
+    /// followed by the prefix.
+    pub fn to_binary_string(&self) -> String {
+        format!("{}/{}", ipv6_to_binary(self.ip()), self.prefix())
+    }
+
+    /// Returns the address of this network fully expanded, as rendered by
+    /// [`ipv6_to_expanded`], followed by the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8::/32".parse().unwrap();
+    /// assert_eq!(
+    ///     net.to_expanded_string(),
+    ///     "2001:0db8:0000:0000:0000:0000:0000:0000/32"
+    /// );
+    /// ```
+    pub fn to_expanded_string(&self) -> String {
+        format!("{}/{}", ipv6_to_expanded(self.ip()), self.prefix())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn binary_strings() {
+        let net: Ipv4Network = "255.0.128.1/32".parse().unwrap();
+        assert_eq!(
+            net.to_binary_string(),
+            "11111111.00000000.10000000.00000001/32"
+        );
+        let net: Ipv6Network = "8000::1/1".parse().unwrap();
+        let binary = net.to_binary_string();
+        assert_eq!(binary.len(), 8 * 16 + 7 + 2);
+        assert!(binary.starts_with("1000000000000000:0000000000000000:"));
+        assert!(binary.ends_with(":0000000000000001/1"));
+    }
+}
@@ -0,0 +1,148 @@
+//! GraphQL scalars for the network types, serialized as strings in CIDR notation.
+
+#[cfg(feature = "async-graphql")]
+mod async_graphql_scalars {
+    use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+    use async_graphql::{InputType, InputValueError, InputValueResult, Scalar, ScalarType, Value};
+    use std::str::FromStr;
+
+    /// Parses a GraphQL string value in CIDR notation.
+    fn parse<T>(value: Value) -> InputValueResult<T>
+    where
+        T: InputType + FromStr,
+        T::Err: std::fmt::Display,
+    {
+        match value {
+            Value::String(s) => s.parse().map_err(InputValueError::custom),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    /// An IPv4 network in CIDR notation, such as `192.168.0.0/16`.
+    #[Scalar(name = "Ipv4Network")]
+    impl ScalarType for Ipv4Network {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            parse(value)
+        }
+
+        fn is_valid(value: &Value) -> bool {
+            matches!(value, Value::String(s) if s.parse::<Self>().is_ok())
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.to_string())
+        }
+    }
+
+    /// An IPv6 network in CIDR notation, such as `2001:db8::/32`.
+    #[Scalar(name = "Ipv6Network")]
+    impl ScalarType for Ipv6Network {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            parse(value)
+        }
+
+        fn is_valid(value: &Value) -> bool {
+            matches!(value, Value::String(s) if s.parse::<Self>().is_ok())
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.to_string())
+        }
+    }
+
+    /// An IPv4 or IPv6 network in CIDR notation.
+    #[Scalar(name = "IpNetwork")]
+    impl ScalarType for IpNetwork {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            parse(value)
+        }
+
+        fn is_valid(value: &Value) -> bool {
+            matches!(value, Value::String(s) if s.parse::<Self>().is_ok())
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.to_string())
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn async_graphql_scalar() {
+            let net: IpNetwork = "10.0.0.0/8".parse().unwrap();
+            let value = ScalarType::to_value(&net);
+            assert_eq!(value, Value::String("10.0.0.0/8".to_string()));
+            assert_eq!(<IpNetwork as ScalarType>::parse(value).unwrap(), net);
+
+            let bogus = Value::String("10.0.0.0/33".to_string());
+            assert!(!<Ipv4Network as ScalarType>::is_valid(&bogus));
+            assert!(<Ipv4Network as ScalarType>::parse(bogus).is_err());
+            assert!(<Ipv6Network as ScalarType>::parse(Value::Number(1.into())).is_err());
+        }
+    }
+}
+
+#[cfg(feature = "juniper")]
+// The type aliases only name the types for `graphql_scalar`, which implements the traits on
+// the aliased types.
+#[allow(dead_code)]
+mod juniper_scalars {
+    use juniper::{graphql_scalar, InputValue, ScalarValue, Value};
+
+    /// An IPv4 network in CIDR notation, such as `192.168.0.0/16`.
+    #[graphql_scalar(with = cidr_scalar, parse_token(String))]
+    type Ipv4Network = crate::Ipv4Network;
+
+    /// An IPv6 network in CIDR notation, such as `2001:db8::/32`.
+    #[graphql_scalar(with = cidr_scalar, parse_token(String))]
+    type Ipv6Network = crate::Ipv6Network;
+
+    /// An IPv4 or IPv6 network in CIDR notation.
+    #[graphql_scalar(with = cidr_scalar, parse_token(String))]
+    type IpNetwork = crate::IpNetwork;
+
+    mod cidr_scalar {
+        use super::*;
+        use std::{fmt, str::FromStr};
+
+        pub(super) fn to_output<T: fmt::Display, S: ScalarValue>(v: &T) -> Value<S> {
+            Value::scalar(v.to_string())
+        }
+
+        pub(super) fn from_input<T, S>(v: &InputValue<S>) -> Result<T, String>
+        where
+            T: FromStr,
+            T::Err: fmt::Display,
+            S: ScalarValue,
+        {
+            v.as_string_value()
+                .ok_or_else(|| format!("Expected `String`, found: {v}"))
+                .and_then(|s| {
+                    s.parse()
+                        .map_err(|e| format!("Failed to parse network: {e}"))
+                })
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use juniper::{DefaultScalarValue, FromInputValue, ToInputValue};
+
+        #[test]
+        fn juniper_scalar() {
+            let net: IpNetwork = "2001:db8::/32".parse().unwrap();
+            let input: InputValue<DefaultScalarValue> = net.to_input_value();
+            assert_eq!(input.as_string_value(), Some("2001:db8::/32"));
+            assert_eq!(IpNetwork::from_input_value(&input), Ok(net));
+
+            let bogus: InputValue<DefaultScalarValue> = InputValue::scalar("10.0.0.0/33");
+            assert!(Ipv4Network::from_input_value(&bogus).is_err());
+            let number: InputValue<DefaultScalarValue> = InputValue::scalar(1);
+            assert!(Ipv6Network::from_input_value(&number).is_err());
+        }
+    }
+}
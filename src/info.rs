@@ -0,0 +1,183 @@
+use crate::range::IPV4_BITS;
+use crate::{IpNetwork, Ipv4Network, Ipv6Network, NetworkSize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// A summary of a network, as shown by subnet calculators.
+///
+/// Returned by [`IpNetwork::info`]. With the `serde` feature it serializes as a struct whose
+/// addresses are strings, `size` and `usable_hosts` are integers, and flags are booleans.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NetworkInfo {
+    /// The network address, with the host bits cleared.
+    pub network: IpAddr,
+    /// The last address of the network.
+    pub broadcast: IpAddr,
+    /// The netmask, such as `255.255.255.0`.
+    pub mask: IpAddr,
+    /// The inverse of the netmask, such as `0.0.0.255`.
+    pub hostmask: IpAddr,
+    /// The prefix length.
+    pub prefix: u8,
+    /// The number of addresses, as returned by [`IpNetwork::size`].
+    pub size: NetworkSize,
+    /// The number of addresses that can be assigned to hosts.
+    ///
+    /// For IPv4 networks shorter than `/31` this excludes the network and broadcast addresses,
+    /// see [`Ipv4Network::contains_usable`]. IPv6 has no broadcast address, so every address is
+    /// counted. Unlike `size`, this is exact for `0.0.0.0/0`, and saturates at `u128::MAX` for
+    /// `::/0`.
+    pub usable_hosts: u128,
+    /// See [`IpNetwork::is_global`].
+    pub is_global: bool,
+    /// See [`IpNetwork::is_documentation`].
+    pub is_documentation: bool,
+    /// See [`IpNetwork::is_benchmarking`].
+    pub is_benchmarking: bool,
+    /// See [`IpNetwork::is_shared`].
+    pub is_shared: bool,
+    /// See [`IpNetwork::is_reserved`].
+    pub is_reserved: bool,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NetworkInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("NetworkInfo", 12)?;
+        state.serialize_field("network", &self.network)?;
+        state.serialize_field("broadcast", &self.broadcast)?;
+        state.serialize_field("mask", &self.mask)?;
+        state.serialize_field("hostmask", &self.hostmask)?;
+        state.serialize_field("prefix", &self.prefix)?;
+        state.serialize_field("size", &u128::from(self.size))?;
+        state.serialize_field("usable_hosts", &self.usable_hosts)?;
+        state.serialize_field("is_global", &self.is_global)?;
+        state.serialize_field("is_documentation", &self.is_documentation)?;
+        state.serialize_field("is_benchmarking", &self.is_benchmarking)?;
+        state.serialize_field("is_shared", &self.is_shared)?;
+        state.serialize_field("is_reserved", &self.is_reserved)?;
+        state.end()
+    }
+}
+
+impl IpNetwork {
+    /// Returns a summary of this network: its addresses, masks, size, usable host count and
+    /// classification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let net: IpNetwork = "192.0.2.77/26".parse().unwrap();
+    /// let info = net.info();
+    /// assert_eq!(info.network, Ipv4Addr::new(192, 0, 2, 64));
+    /// assert_eq!(info.broadcast, Ipv4Addr::new(192, 0, 2, 127));
+    /// assert_eq!(info.mask, Ipv4Addr::new(255, 255, 255, 192));
+    /// assert_eq!(info.hostmask, Ipv4Addr::new(0, 0, 0, 63));
+    /// assert_eq!(info.usable_hosts, 62);
+    /// assert!(info.is_documentation);
+    /// assert!(!info.is_global);
+    /// ```
+    pub fn info(&self) -> NetworkInfo {
+        let (hostmask, usable_hosts) = match self {
+            IpNetwork::V4(net) => {
+                let hostmask = IpAddr::V4(Ipv4Addr::from_bits(!net.mask().to_bits()));
+                let size = 1u128 << (IPV4_BITS - net.prefix());
+                let usable = if net.prefix() < 31 { size - 2 } else { size };
+                (hostmask, usable)
+            }
+            IpNetwork::V6(net) => {
+                let hostmask = IpAddr::V6(Ipv6Addr::from_bits(!net.mask().to_bits()));
+                let last_offset = u128::MAX.checked_shr(net.prefix().into()).unwrap_or(0);
+                (hostmask, last_offset.saturating_add(1))
+            }
+        };
+        NetworkInfo {
+            network: self.network(),
+            broadcast: self.broadcast(),
+            mask: self.mask(),
+            hostmask,
+            prefix: self.prefix(),
+            size: self.size(),
+            usable_hosts,
+            is_global: self.is_global(),
+            is_documentation: self.is_documentation(),
+            is_benchmarking: self.is_benchmarking(),
+            is_shared: self.is_shared(),
+            is_reserved: self.is_reserved(),
+        }
+    }
+}
+
+impl Ipv4Network {
+    /// Returns a summary of this network, see [`IpNetwork::info`].
+    pub fn info(&self) -> NetworkInfo {
+        IpNetwork::V4(*self).info()
+    }
+}
+
+impl Ipv6Network {
+    /// Returns a summary of this network, see [`IpNetwork::info`].
+    pub fn info(&self) -> NetworkInfo {
+        IpNetwork::V6(*self).info()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn info_usable_hosts() {
+        let usable = |s: &str| s.parse::<IpNetwork>().unwrap().info().usable_hosts;
+        assert_eq!(usable("0.0.0.0/0"), (1 << 32) - 2);
+        assert_eq!(usable("10.0.0.0/30"), 2);
+        assert_eq!(usable("10.0.0.0/31"), 2);
+        assert_eq!(usable("10.0.0.1/32"), 1);
+        assert_eq!(usable("::/0"), u128::MAX);
+        assert_eq!(usable("::/1"), 1 << 127);
+        assert_eq!(usable("2001:db8::/64"), 1 << 64);
+        assert_eq!(usable("::1/128"), 1);
+    }
+
+    #[test]
+    fn info_ipv6() {
+        let net: Ipv6Network = "2001:db8::1/32".parse().unwrap();
+        let info = net.info();
+        assert_eq!(info.network, "2001:db8::".parse::<IpAddr>().unwrap());
+        assert_eq!(
+            info.broadcast,
+            "2001:db8:ffff:ffff:ffff:ffff:ffff:ffff"
+                .parse::<IpAddr>()
+                .unwrap()
+        );
+        assert_eq!(info.mask, "ffff:ffff::".parse::<IpAddr>().unwrap());
+        assert_eq!(
+            info.hostmask,
+            "::ffff:ffff:ffff:ffff:ffff:ffff".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(info.prefix, 32);
+        assert_eq!(info.size, NetworkSize::V6(1 << 96));
+        assert!(info.is_documentation);
+        assert!(!info.is_shared);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn info_serialize() {
+        let net: IpNetwork = "100.64.0.0/10".parse().unwrap();
+        let json = serde_json::to_value(net.info()).unwrap();
+        assert_eq!(json["network"], "100.64.0.0");
+        assert_eq!(json["hostmask"], "0.63.255.255");
+        assert_eq!(json["size"], 4194304);
+        assert_eq!(json["usable_hosts"], 4194302);
+        assert_eq!(json["is_shared"], true);
+    }
+}
@@ -0,0 +1,340 @@
+use crate::error::IpNetworkError;
+use crate::merge::Merge;
+use crate::{CanonicalIpNetwork, IpNetwork, Ipv4Network, Ipv6Network};
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+/// An IPv4 interface address: a host address together with the prefix of the network it is
+/// on, such as `10.0.0.5/24`.
+///
+/// The host bits are part of the value, so `10.0.0.5/24` and `10.0.0.6/24` are different
+/// interfaces on the same network. Use [`network`](Ipv4Interface::network) to get the network
+/// with the host bits cleared.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::Ipv4Addr;
+/// use ipnetwork::{Ipv4Interface, Ipv4Network};
+///
+/// let iface: Ipv4Interface = "10.0.0.5/24".parse().unwrap();
+/// assert_eq!(iface.ip(), Ipv4Addr::new(10, 0, 0, 5));
+/// assert_eq!(iface.network(), "10.0.0.0/24".parse::<Ipv4Network>().unwrap());
+/// assert_ne!(iface, "10.0.0.6/24".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Ipv4Interface(Ipv4Network);
+
+/// An IPv6 interface address: a host address together with the prefix of the network it is
+/// on, such as `2001:db8::5/64`.
+///
+/// See [`Ipv4Interface`].
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{Ipv6Interface, Ipv6Network};
+///
+/// let iface: Ipv6Interface = "2001:db8::5/64".parse().unwrap();
+/// assert_eq!(iface.network(), "2001:db8::/64".parse::<Ipv6Network>().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Ipv6Interface(Ipv6Network);
+
+/// An IPv4 or IPv6 interface address, see [`Ipv4Interface`].
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{CanonicalIpNetwork, IpInterface};
+///
+/// let a: IpInterface = "10.0.0.5/24".parse().unwrap();
+/// let b: IpInterface = "10.0.0.6/24".parse().unwrap();
+/// assert_ne!(a, b);
+/// assert_eq!(a.network(), b.network());
+/// assert_eq!(a.network(), "10.0.0.0/24".parse::<CanonicalIpNetwork>().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IpInterface {
+    V4(Ipv4Interface),
+    V6(Ipv6Interface),
+}
+
+impl Ipv4Interface {
+    /// Constructs a new `Ipv4Interface` from an address and the prefix of its network.
+    ///
+    /// If the prefix is larger than 32 this will return an `IpNetworkError::InvalidPrefix`.
+    pub const fn new(addr: Ipv4Addr, prefix: u8) -> Result<Ipv4Interface, IpNetworkError> {
+        match Ipv4Network::new_checked(addr, prefix) {
+            Some(net) => Ok(Ipv4Interface(net)),
+            None => Err(IpNetworkError::InvalidPrefix),
+        }
+    }
+
+    /// Returns the address of the interface.
+    pub const fn ip(self) -> Ipv4Addr {
+        self.0.ip()
+    }
+
+    /// Returns the prefix of the network the interface is on.
+    pub const fn prefix(self) -> u8 {
+        self.0.prefix()
+    }
+
+    /// Returns the network the interface is on, with the host bits cleared.
+    pub fn network(self) -> Ipv4Network {
+        self.0.normalize()
+    }
+}
+
+impl Ipv6Interface {
+    /// Constructs a new `Ipv6Interface` from an address and the prefix of its network.
+    ///
+    /// If the prefix is larger than 128 this will return an `IpNetworkError::InvalidPrefix`.
+    pub const fn new(addr: Ipv6Addr, prefix: u8) -> Result<Ipv6Interface, IpNetworkError> {
+        match Ipv6Network::new_checked(addr, prefix) {
+            Some(net) => Ok(Ipv6Interface(net)),
+            None => Err(IpNetworkError::InvalidPrefix),
+        }
+    }
+
+    /// Returns the address of the interface.
+    pub const fn ip(self) -> Ipv6Addr {
+        self.0.ip()
+    }
+
+    /// Returns the prefix of the network the interface is on.
+    pub const fn prefix(self) -> u8 {
+        self.0.prefix()
+    }
+
+    /// Returns the network the interface is on, with the host bits cleared.
+    pub fn network(self) -> Ipv6Network {
+        self.0.normalize()
+    }
+}
+
+impl IpInterface {
+    /// Constructs a new `IpInterface` from an address and the prefix of its network.
+    ///
+    /// If the prefix is larger than the address is wide this will return an
+    /// `IpNetworkError::InvalidPrefix`.
+    pub fn new(addr: IpAddr, prefix: u8) -> Result<IpInterface, IpNetworkError> {
+        match addr {
+            IpAddr::V4(addr) => Ipv4Interface::new(addr, prefix).map(IpInterface::V4),
+            IpAddr::V6(addr) => Ipv6Interface::new(addr, prefix).map(IpInterface::V6),
+        }
+    }
+
+    /// Returns the address of the interface.
+    pub const fn ip(self) -> IpAddr {
+        match self {
+            IpInterface::V4(iface) => IpAddr::V4(iface.ip()),
+            IpInterface::V6(iface) => IpAddr::V6(iface.ip()),
+        }
+    }
+
+    /// Returns the prefix of the network the interface is on.
+    pub const fn prefix(self) -> u8 {
+        match self {
+            IpInterface::V4(iface) => iface.prefix(),
+            IpInterface::V6(iface) => iface.prefix(),
+        }
+    }
+
+    /// Returns the network the interface is on, with the host bits cleared.
+    pub fn network(self) -> CanonicalIpNetwork {
+        CanonicalIpNetwork::new(IpNetwork::from(self))
+    }
+}
+
+impl fmt::Display for Ipv4Interface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for Ipv6Interface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for IpInterface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpInterface::V4(iface) => iface.fmt(f),
+            IpInterface::V6(iface) => iface.fmt(f),
+        }
+    }
+}
+
+/// Parses an interface in the notation accepted by [`Ipv4Network`].
+impl FromStr for Ipv4Interface {
+    type Err = IpNetworkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Ipv4Interface)
+    }
+}
+
+/// Parses an interface in the notation accepted by [`Ipv6Network`].
+impl FromStr for Ipv6Interface {
+    type Err = IpNetworkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Ipv6Interface)
+    }
+}
+
+/// Parses an interface in the notation accepted by [`IpNetwork`].
+impl FromStr for IpInterface {
+    type Err = IpNetworkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<IpNetwork>().map(IpInterface::from)
+    }
+}
+
+impl From<Ipv4Network> for Ipv4Interface {
+    fn from(net: Ipv4Network) -> Self {
+        Ipv4Interface(net)
+    }
+}
+
+impl From<Ipv4Interface> for Ipv4Network {
+    /// Returns the interface as a network, keeping the host bits of its address.
+    fn from(iface: Ipv4Interface) -> Self {
+        iface.0
+    }
+}
+
+impl From<Ipv6Network> for Ipv6Interface {
+    fn from(net: Ipv6Network) -> Self {
+        Ipv6Interface(net)
+    }
+}
+
+impl From<Ipv6Interface> for Ipv6Network {
+    /// Returns the interface as a network, keeping the host bits of its address.
+    fn from(iface: Ipv6Interface) -> Self {
+        iface.0
+    }
+}
+
+impl From<Ipv4Interface> for IpInterface {
+    fn from(iface: Ipv4Interface) -> Self {
+        IpInterface::V4(iface)
+    }
+}
+
+impl From<Ipv6Interface> for IpInterface {
+    fn from(iface: Ipv6Interface) -> Self {
+        IpInterface::V6(iface)
+    }
+}
+
+impl From<IpNetwork> for IpInterface {
+    fn from(net: IpNetwork) -> Self {
+        match net {
+            IpNetwork::V4(net) => IpInterface::V4(Ipv4Interface(net)),
+            IpNetwork::V6(net) => IpInterface::V6(Ipv6Interface(net)),
+        }
+    }
+}
+
+impl From<IpInterface> for IpNetwork {
+    /// Returns the interface as a network, keeping the host bits of its address.
+    fn from(iface: IpInterface) -> Self {
+        match iface {
+            IpInterface::V4(iface) => IpNetwork::V4(iface.0),
+            IpInterface::V6(iface) => IpNetwork::V6(iface.0),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Ipv4Interface {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Ipv4Interface {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ipv4Network::deserialize(deserializer).map(Ipv4Interface)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Ipv6Interface {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Ipv6Interface {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ipv6Network::deserialize(deserializer).map(Ipv6Interface)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IpInterface {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        IpNetwork::from(*self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IpInterface {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        IpNetwork::deserialize(deserializer).map(IpInterface::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn interface_keeps_host_bits() {
+        let iface: IpInterface = "2001:db8::5/64".parse().unwrap();
+        assert_eq!(iface.to_string(), "2001:db8::5/64");
+        assert_eq!(iface.ip(), "2001:db8::5".parse::<IpAddr>().unwrap());
+        assert_eq!(iface.prefix(), 64);
+        assert_eq!(iface.network().to_string(), "2001:db8::/64");
+        assert_eq!(IpNetwork::from(iface).to_string(), "2001:db8::5/64");
+
+        let v4 = Ipv4Interface::new(Ipv4Addr::new(192, 0, 2, 77), 26).unwrap();
+        assert_eq!(v4.network().to_string(), "192.0.2.64/26");
+        assert_eq!(
+            IpInterface::new(IpAddr::V4(v4.ip()), 26),
+            Ok(IpInterface::V4(v4))
+        );
+        assert_eq!(
+            Ipv6Interface::new(Ipv6Addr::LOCALHOST, 129),
+            Err(IpNetworkError::InvalidPrefix)
+        );
+    }
+}
@@ -1,11 +1,25 @@
 use crate::error::IpNetworkError;
+use crate::format::format_into;
+use crate::ipv6::Ipv6Network;
+use crate::merge::{merge_in_place, merge_with_waste_in_place, Merge, WasteTolerance};
+use crate::netmask::Netmask4;
+use crate::network_range::Ipv4NetworkRange;
 use crate::parse::{cidr_parts, parse_prefix};
-use std::{convert::TryFrom, fmt, net::Ipv4Addr, str::FromStr};
+use crate::prefix::Ipv4Prefix;
+use crate::relation::NetworkRelation;
+use std::{
+    convert::TryFrom, fmt, iter::FusedIterator, net::Ipv4Addr, ops::RangeInclusive, str::FromStr,
+};
 
 const IPV4_BITS: u8 = 32;
 
 /// Represents a network range where the IP addresses are of v4
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug, PartialEq, Eq, Hash))
+)]
 pub struct Ipv4Network {
     addr: Ipv4Addr,
     prefix: u8,
@@ -17,8 +31,13 @@ impl<'de> serde::Deserialize<'de> for Ipv4Network {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = <String>::deserialize(deserializer)?;
-        Ipv4Network::from_str(&s).map_err(serde::de::Error::custom)
+        if deserializer.is_human_readable() {
+            let s = <String>::deserialize(deserializer)?;
+            Ipv4Network::from_str(&s).map_err(serde::de::Error::custom)
+        } else {
+            let (ip, prefix) = <(Ipv4Addr, u8)>::deserialize(deserializer)?;
+            Ipv4Network::new(ip, prefix).map_err(serde::de::Error::custom)
+        }
     }
 }
 
@@ -28,7 +47,52 @@ impl serde::Serialize for Ipv4Network {
     where
         S: serde::Serializer,
     {
-        serializer.collect_str(self)
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serde::Serialize::serialize(&(self.ip(), self.prefix()), serializer)
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedIpv4Network {
+    /// Returns the address of the archived network.
+    pub const fn ip(&self) -> Ipv4Addr {
+        self.addr.as_ipv4()
+    }
+
+    /// Returns the prefix length of the archived network.
+    ///
+    /// Validating an archive only checks its layout, so the prefix of an archive from an
+    /// untrusted source may be greater than 32.
+    pub const fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Copies the network out of the archive, checking its prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{ArchivedIpv4Network, Ipv4Network};
+    ///
+    /// let nets: Vec<Ipv4Network> = vec!["10.0.0.0/8".parse().unwrap()];
+    /// let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&nets).unwrap();
+    /// let archived =
+    ///     rkyv::access::<rkyv::Archived<Vec<Ipv4Network>>, rkyv::rancor::Error>(&bytes).unwrap();
+    /// assert!(archived[0].contains("10.1.2.3".parse().unwrap()));
+    /// assert_eq!(archived[0].to_network(), Ok(nets[0]));
+    /// ```
+    pub fn to_network(&self) -> Result<Ipv4Network, IpNetworkError> {
+        Ipv4Network::new(self.ip(), self.prefix)
+    }
+
+    /// Checks if the archived network contains `ip`, without copying it out of the archive.
+    ///
+    /// A network with an invalid prefix contains no addresses.
+    pub fn contains(&self, ip: Ipv4Addr) -> bool {
+        self.to_network().is_ok_and(|net| net.contains(ip))
     }
 }
 
@@ -62,6 +126,23 @@ impl schemars::JsonSchema for Ipv4Network {
     }
 }
 
+#[cfg(feature = "smoltcp")]
+impl From<Ipv4Network> for smoltcp::wire::Ipv4Cidr {
+    fn from(net: Ipv4Network) -> Self {
+        smoltcp::wire::Ipv4Cidr::new(net.ip(), net.prefix())
+    }
+}
+
+#[cfg(feature = "smoltcp")]
+impl From<smoltcp::wire::Ipv4Cidr> for Ipv4Network {
+    fn from(cidr: smoltcp::wire::Ipv4Cidr) -> Self {
+        Ipv4Network {
+            addr: cidr.address(),
+            prefix: cidr.prefix_len(),
+        }
+    }
+}
+
 impl Ipv4Network {
     /// Constructs a new `Ipv4Network` from any `Ipv4Addr` and a prefix denoting the network size.
     ///
@@ -115,7 +196,7 @@ impl Ipv4Network {
 
     /// Constructs a new `Ipv4Network` from a network address and a network mask.
     ///
-    /// If the netmask is not valid this will return an `IpNetworkError::InvalidPrefix`.
+    /// If the netmask is not valid this will return an `IpNetworkError::InvalidNetmask`.
     pub fn with_netmask(
         netaddr: Ipv4Addr,
         netmask: Ipv4Addr,
@@ -128,18 +209,165 @@ impl Ipv4Network {
         Ok(net)
     }
 
+    /// Constructs a new `Ipv4Network` from a network address and a wildcard mask, the inverse
+    /// of a netmask used by Cisco ACLs, such as `0.0.0.255` for a `/24`.
+    ///
+    /// Only wildcard masks matching a contiguous block of host bits describe a network. Any
+    /// other wildcard mask returns an `IpNetworkError::InvalidNetmask`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let addr = Ipv4Addr::new(10, 0, 0, 0);
+    /// let net = Ipv4Network::with_wildcard_mask(addr, Ipv4Addr::new(0, 0, 0, 255)).unwrap();
+    /// assert_eq!(net.prefix(), 24);
+    /// assert!(Ipv4Network::with_wildcard_mask(addr, Ipv4Addr::new(0, 255, 0, 255)).is_err());
+    /// ```
+    pub fn with_wildcard_mask(
+        netaddr: Ipv4Addr,
+        wildcard: Ipv4Addr,
+    ) -> Result<Ipv4Network, IpNetworkError> {
+        Ipv4Network::with_netmask(netaddr, Ipv4Addr::from_bits(!wildcard.to_bits()))
+    }
+
+    /// Constructs a new `Ipv4Network` from any `Ipv4Addr` and an already validated prefix.
+    ///
+    /// Since an `Ipv4Prefix` is always between 0 and 32, this cannot fail.
+    pub const fn with_prefix(addr: Ipv4Addr, prefix: Ipv4Prefix) -> Ipv4Network {
+        Ipv4Network {
+            addr,
+            prefix: prefix.get(),
+        }
+    }
+
+    /// Constructs a new `Ipv4Network` from any `Ipv4Addr` and an already validated netmask.
+    ///
+    /// Since a `Netmask4` is always contiguous, this cannot fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::{Ipv4Network, Netmask4};
+    ///
+    /// let mask: Netmask4 = "255.255.255.0".parse().unwrap();
+    /// let net = Ipv4Network::with_validated_netmask(Ipv4Addr::new(10, 1, 2, 0), mask);
+    /// assert_eq!(net, "10.1.2.0/24".parse().unwrap());
+    /// ```
+    pub const fn with_validated_netmask(addr: Ipv4Addr, netmask: Netmask4) -> Ipv4Network {
+        Ipv4Network::with_prefix(addr, netmask.prefix())
+    }
+
     /// Returns an iterator over `Ipv4Network`. Each call to `next` will return the next
     /// `Ipv4Addr` in the given network. `None` will be returned when there are no more
     /// addresses.
     pub fn iter(self) -> Ipv4NetworkIterator {
-        let start = u32::from(self.network());
-        let end = start + (self.size() - 1);
         Ipv4NetworkIterator {
-            next: Some(start),
-            end,
+            next: Some(u32::from(self.network())),
+            end: u32::from(self.broadcast()),
         }
     }
 
+    /// Returns an iterator over the addresses in this network, starting at `start` rather than
+    /// at the network address.
+    ///
+    /// Returns `None` if `start` is not contained in the network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.168.0.0/30".parse().unwrap();
+    /// let mut iter = net.iter_from(Ipv4Addr::new(192, 168, 0, 2)).unwrap();
+    /// assert_eq!(iter.next(), Some(Ipv4Addr::new(192, 168, 0, 2)));
+    /// assert_eq!(iter.next(), Some(Ipv4Addr::new(192, 168, 0, 3)));
+    /// assert_eq!(iter.next(), None);
+    ///
+    /// assert!(net.iter_from(Ipv4Addr::new(192, 168, 0, 4)).is_none());
+    /// ```
+    pub fn iter_from(self, start: Ipv4Addr) -> Option<Ipv4NetworkIterator> {
+        if !self.contains(start) {
+            return None;
+        }
+        Some(Ipv4NetworkIterator {
+            next: Some(u32::from(start)),
+            end: u32::from(self.broadcast()),
+        })
+    }
+
+    /// Returns an iterator over every `step`-th address in this network, starting at the
+    /// network address.
+    ///
+    /// Unlike `iter().step_by(step)`, this computes each address directly instead of advancing
+    /// one address at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.0.0.0/30".parse().unwrap();
+    /// let addrs: Vec<Ipv4Addr> = net.iter_step(2).collect();
+    /// assert_eq!(addrs, [Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 0, 2)]);
+    /// ```
+    pub fn iter_step(self, step: u32) -> Ipv4StepIterator {
+        assert!(step != 0, "step must be non-zero");
+        Ipv4StepIterator {
+            next: Some(self.network().to_bits()),
+            end: self.broadcast().to_bits(),
+            step,
+        }
+    }
+
+    /// Returns an iterator over the address at `offset` within each subnet of length `prefix`
+    /// of this network, such as one address per `/24` of a `/8`.
+    ///
+    /// Returns an `IpNetworkError::InvalidPrefix` if `prefix` is shorter than the prefix of this
+    /// network or longer than 32, and an `IpNetworkError::InvalidRange` if
+    /// `offset` lies outside a subnet of length `prefix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.0.0.0/8".parse().unwrap();
+    /// // The address ending in .1 of every /24, such as a gateway.
+    /// let gateways = net.iter_strata(24, 1).unwrap();
+    /// assert_eq!(gateways.clone().next(), Some(Ipv4Addr::new(10, 0, 0, 1)));
+    /// assert_eq!(gateways.last(), Some(Ipv4Addr::new(10, 255, 255, 1)));
+    /// assert!(net.iter_strata(24, 256).is_err());
+    /// ```
+    pub fn iter_strata(self, prefix: u8, offset: u32) -> Result<Ipv4StepIterator, IpNetworkError> {
+        if prefix < self.prefix || prefix > IPV4_BITS {
+            return Err(IpNetworkError::InvalidPrefix);
+        }
+        let host_bits = u32::from(IPV4_BITS - prefix);
+        if offset.checked_shr(host_bits).unwrap_or(0) != 0 {
+            return Err(IpNetworkError::InvalidRange(format!(
+                "offset {offset} is outside a /{prefix}"
+            )));
+        }
+        let step = 1u32.checked_shl(host_bits).unwrap_or(0);
+        Ok(Ipv4StepIterator {
+            next: Some(self.network().to_bits() + offset),
+            end: self.broadcast().to_bits(),
+            // A step of zero stands for the whole address space, which is only taken once.
+            step,
+        })
+    }
+
     pub const fn ip(self) -> Ipv4Addr {
         self.addr
     }
@@ -148,6 +376,44 @@ impl Ipv4Network {
         self.prefix
     }
 
+    /// Returns the prefix of this `Ipv4Network` as an `Ipv4Prefix`.
+    pub const fn prefix_len(self) -> Ipv4Prefix {
+        match Ipv4Prefix::new_checked(self.prefix) {
+            Some(prefix) => prefix,
+            None => unreachable!(),
+        }
+    }
+
+    /// Returns the number of host bits in this `Ipv4Network`, that is `32 - prefix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.1.0.0/16".parse().unwrap();
+    /// assert_eq!(net.host_bits(), 16);
+    /// ```
+    pub const fn host_bits(self) -> u8 {
+        IPV4_BITS - self.prefix
+    }
+
+    /// Returns the base 2 logarithm of the number of addresses in this `Ipv4Network`.
+    ///
+    /// Unlike [`Ipv4Network::size`] this is exact for every prefix, including `/0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+    /// assert_eq!(net.size_log2(), 32);
+    /// ```
+    pub const fn size_log2(self) -> u32 {
+        self.host_bits() as u32
+    }
+
     /// Checks if the given `Ipv4Network` is a subnet of the other.
     pub fn is_subnet_of(self, other: Ipv4Network) -> bool {
         other.ip() <= self.ip() && other.broadcast() >= self.broadcast()
@@ -158,6 +424,76 @@ impl Ipv4Network {
         other.is_subnet_of(self)
     }
 
+    /// Returns an iterator over the networks enclosing this one, from the one with a prefix one
+    /// shorter up to the whole address space with prefix 0.
+    ///
+    /// The yielded networks have their host bits cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.1.3.0/24".parse().unwrap();
+    /// let mut supernets = net.supernets();
+    /// assert_eq!(supernets.len(), 24);
+    /// assert_eq!(supernets.next(), Some("10.1.2.0/23".parse().unwrap()));
+    /// assert_eq!(supernets.last(), Some("0.0.0.0/0".parse().unwrap()));
+    /// ```
+    pub fn supernets(self) -> Ipv4SupernetIterator {
+        self.supernets_to(0)
+    }
+
+    /// Returns an iterator over the networks enclosing this one, from the one with a prefix one
+    /// shorter up to the one with prefix `stop_prefix`, inclusive.
+    ///
+    /// The iterator is empty if `stop_prefix` is not shorter than the prefix of this network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.1.2.0/24".parse().unwrap();
+    /// let supernets: Vec<Ipv4Network> = net.supernets_to(22).collect();
+    /// assert_eq!(supernets, ["10.1.2.0/23".parse().unwrap(), "10.1.0.0/22".parse().unwrap()]);
+    /// ```
+    pub fn supernets_to(self, stop_prefix: u8) -> Ipv4SupernetIterator {
+        Ipv4SupernetIterator {
+            addr: self.addr,
+            prefix: self.prefix,
+            stop_prefix,
+        }
+    }
+
+    /// Returns an iterator over the subnets of length `new_prefix` that make up this network,
+    /// in order, such as the 256 `/24`s of a `/16`.
+    ///
+    /// Returns an `IpNetworkError::InvalidPrefix` if `new_prefix` is shorter than the prefix of
+    /// this network or longer than 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.1.0.0/16".parse().unwrap();
+    /// let mut subnets = net.subnets(24).unwrap();
+    /// assert_eq!(subnets.clone().count(), 256);
+    /// assert_eq!(subnets.next(), Some("10.1.0.0/24".parse().unwrap()));
+    /// assert_eq!(subnets.next_back(), Some("10.1.255.0/24".parse().unwrap()));
+    /// assert!(net.subnets(8).is_err());
+    /// ```
+    pub fn subnets(self, new_prefix: u8) -> Result<Ipv4NetworkRange, IpNetworkError> {
+        if new_prefix < self.prefix {
+            return Err(IpNetworkError::InvalidPrefix);
+        }
+        Ipv4NetworkRange::new(
+            Ipv4Network::new(self.network(), new_prefix)?,
+            Ipv4Network::new(self.broadcast(), new_prefix)?,
+        )
+    }
+
     /// Checks if the given `Ipv4Network` is partly contained in other.
     pub fn overlaps(self, other: Ipv4Network) -> bool {
         other.contains(self.ip())
@@ -166,6 +502,84 @@ impl Ipv4Network {
             || self.contains(other.broadcast())
     }
 
+    /// Classifies how this network relates to `other` in a single comparison.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{Ipv4Network, NetworkRelation};
+    ///
+    /// let small: Ipv4Network = "10.0.0.0/24".parse().unwrap();
+    /// let big: Ipv4Network = "10.0.0.0/16".parse().unwrap();
+    /// let other: Ipv4Network = "10.1.0.0/24".parse().unwrap();
+    /// assert_eq!(small.relation(big), NetworkRelation::SubsetOf);
+    /// assert_eq!(big.relation(small), NetworkRelation::SupersetOf);
+    /// assert_eq!(small.relation(small), NetworkRelation::Equal);
+    /// assert_eq!(small.relation(other), NetworkRelation::Disjoint);
+    /// ```
+    pub fn relation(self, other: Ipv4Network) -> NetworkRelation {
+        NetworkRelation::between(
+            (self.network(), self.broadcast()),
+            (other.network(), other.broadcast()),
+        )
+    }
+
+    /// Splits the addresses of this network and `other` into three parts in a single
+    /// comparison: the networks covering the addresses only in `self`, the network shared by
+    /// both, and the networks covering the addresses only in `other`.
+    ///
+    /// Two CIDR networks are either nested or disjoint, so the shared part is at most a single
+    /// network. All returned networks have their host bits cleared and each list is sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let big: Ipv4Network = "10.0.0.0/22".parse().unwrap();
+    /// let small: Ipv4Network = "10.0.1.0/24".parse().unwrap();
+    /// let (only_big, shared, only_small) = big.overlap_partition(small);
+    /// assert_eq!(
+    ///     only_big,
+    ///     vec!["10.0.0.0/24".parse::<Ipv4Network>().unwrap(), "10.0.2.0/23".parse().unwrap()]
+    /// );
+    /// assert_eq!(shared, Some(small));
+    /// assert!(only_small.is_empty());
+    /// ```
+    pub fn overlap_partition(
+        self,
+        other: Ipv4Network,
+    ) -> (Vec<Ipv4Network>, Option<Ipv4Network>, Vec<Ipv4Network>) {
+        let this = self.normalize();
+        let other = other.normalize();
+        match this.relation(other) {
+            NetworkRelation::Equal => (Vec::new(), Some(this), Vec::new()),
+            NetworkRelation::SubsetOf => (Vec::new(), Some(this), other.exclude(this)),
+            NetworkRelation::SupersetOf => (this.exclude(other), Some(other), Vec::new()),
+            _ => (vec![this], None, vec![other]),
+        }
+    }
+
+    /// Returns the networks covering the addresses of this normalized network outside of
+    /// `inner`, a normalized subnet of it.
+    ///
+    /// These are the siblings of `inner` and of each of its supernets up to `self`.
+    fn exclude(self, inner: Ipv4Network) -> Vec<Ipv4Network> {
+        let target = inner.addr.to_bits();
+        let mut parts: Vec<Ipv4Network> = (self.prefix + 1..=inner.prefix)
+            .map(|prefix| {
+                let mask = u32::MAX << (IPV4_BITS - prefix);
+                let bit = 1 << (IPV4_BITS - prefix);
+                Ipv4Network {
+                    addr: Ipv4Addr::from_bits((target & mask) ^ bit),
+                    prefix,
+                }
+            })
+            .collect();
+        parts.sort_unstable();
+        parts
+    }
+
     /// Returns the mask for this `Ipv4Network`.
     /// That means the `prefix` most significant bits will be 1 and the rest 0
     ///
@@ -189,6 +603,23 @@ impl Ipv4Network {
         Ipv4Addr::from_bits(mask)
     }
 
+    /// Returns the wildcard mask for this `Ipv4Network`, the inverse of its netmask as used by
+    /// Cisco ACLs.
+    /// That means the `prefix` most significant bits will be 0 and the rest 1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.0.0.0/22".parse().unwrap();
+    /// assert_eq!(net.wildcard_mask(), Ipv4Addr::new(0, 0, 3, 255));
+    /// ```
+    pub const fn wildcard_mask(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(!self.mask().to_bits())
+    }
+
     /// Returns the address of the network denoted by this `Ipv4Network`.
     /// This means the lowest possible IPv4 address inside of the network.
     ///
@@ -225,6 +656,24 @@ impl Ipv4Network {
         Ipv4Addr::from_bits(broadcast)
     }
 
+    /// Returns the addresses of the network, from the network address to the broadcast
+    /// address, as a range of integers.
+    ///
+    /// Use [`RangeInclusive::into_inner`] to get the bounds as a tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.0.0.7/24".parse().unwrap();
+    /// assert_eq!(net.as_u32_range(), 0x0a00_0000..=0x0a00_00ff);
+    /// assert_eq!(net.as_u32_range().into_inner(), (0x0a00_0000, 0x0a00_00ff));
+    /// ```
+    pub const fn as_u32_range(&self) -> RangeInclusive<u32> {
+        self.network().to_bits()..=self.broadcast().to_bits()
+    }
+
     /// Checks if a given `Ipv4Addr` is in this `Ipv4Network`
     ///
     /// # Examples
@@ -246,6 +695,80 @@ impl Ipv4Network {
         (ip.to_bits() & mask) == net
     }
 
+    /// Checks if a given `Ipv4Addr` is a usable host address in this `Ipv4Network`.
+    ///
+    /// For prefixes shorter than `/31` this excludes the network and broadcast addresses.
+    /// Point-to-point `/31` networks ([RFC 3021]) and `/32` host routes have no such reserved
+    /// addresses, so every contained address is usable.
+    ///
+    /// [RFC 3021]: https://www.rfc-editor.org/rfc/rfc3021
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.168.1.0/24".parse().unwrap();
+    /// assert!(net.contains_usable(Ipv4Addr::new(192, 168, 1, 1)));
+    /// assert!(!net.contains_usable(Ipv4Addr::new(192, 168, 1, 0)));
+    /// assert!(!net.contains_usable(Ipv4Addr::new(192, 168, 1, 255)));
+    ///
+    /// let p2p: Ipv4Network = "192.168.1.0/31".parse().unwrap();
+    /// assert!(p2p.contains_usable(Ipv4Addr::new(192, 168, 1, 0)));
+    /// ```
+    pub const fn contains_usable(&self, ip: Ipv4Addr) -> bool {
+        if !self.contains(ip) {
+            return false;
+        }
+        if self.prefix >= IPV4_BITS - 1 {
+            return true;
+        }
+        let ip = ip.to_bits();
+        ip != self.network().to_bits() && ip != self.broadcast().to_bits()
+    }
+
+    /// Checks if `ip` is the limited broadcast address `255.255.255.255`, which is never
+    /// forwarded by routers regardless of the network it is sent on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// assert!(Ipv4Network::is_limited_broadcast(Ipv4Addr::BROADCAST));
+    /// assert!(!Ipv4Network::is_limited_broadcast(Ipv4Addr::new(10, 255, 255, 255)));
+    /// ```
+    pub const fn is_limited_broadcast(ip: Ipv4Addr) -> bool {
+        ip.to_bits() == Ipv4Addr::BROADCAST.to_bits()
+    }
+
+    /// Checks if `ip` is the directed broadcast address of this `Ipv4Network`, that is its
+    /// broadcast address while the prefix is shorter than `/31`.
+    ///
+    /// Point-to-point `/31` networks ([RFC 3021]) and `/32` host routes have no broadcast
+    /// address, so this is always false for them.
+    ///
+    /// [RFC 3021]: https://www.rfc-editor.org/rfc/rfc3021
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.168.1.0/24".parse().unwrap();
+    /// assert!(net.is_directed_broadcast(Ipv4Addr::new(192, 168, 1, 255)));
+    /// assert!(!net.is_directed_broadcast(Ipv4Addr::new(192, 168, 2, 255)));
+    ///
+    /// let p2p: Ipv4Network = "192.168.1.0/31".parse().unwrap();
+    /// assert!(!p2p.is_directed_broadcast(Ipv4Addr::new(192, 168, 1, 1)));
+    /// ```
+    pub const fn is_directed_broadcast(&self, ip: Ipv4Addr) -> bool {
+        self.prefix < IPV4_BITS - 1 && ip.to_bits() == self.broadcast().to_bits()
+    }
+
     /// Returns number of possible host addresses in this `Ipv4Network`.
     ///
     /// # Examples
@@ -293,6 +816,123 @@ impl Ipv4Network {
             None
         }
     }
+
+    /// Returns the network of the same prefix `n` blocks after this one, or before it if `n` is
+    /// negative.
+    ///
+    /// The address is moved by `n` times the size of the network, so host bits are kept.
+    /// Returns `None` if the result would leave the IPv4 address space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "10.0.4.0/24".parse().unwrap();
+    /// assert_eq!(net.nth_sibling(3), Some("10.0.7.0/24".parse().unwrap()));
+    /// assert_eq!(net.nth_sibling(-4), Some("10.0.0.0/24".parse().unwrap()));
+    /// assert_eq!(net.nth_sibling(1 << 24), None);
+    /// ```
+    pub fn nth_sibling(self, n: i64) -> Option<Ipv4Network> {
+        let addr = self.addr.to_bits();
+        let addr = if n == 0 {
+            addr
+        } else {
+            let block = 1u32.checked_shl(u32::from(IPV4_BITS - self.prefix))?;
+            let offset = u32::try_from(n.unsigned_abs()).ok()?.checked_mul(block)?;
+            if n > 0 {
+                addr.checked_add(offset)?
+            } else {
+                addr.checked_sub(offset)?
+            }
+        };
+        Some(Ipv4Network {
+            addr: Ipv4Addr::from_bits(addr),
+            prefix: self.prefix,
+        })
+    }
+
+    /// Converts this network to the equivalent IPv4-mapped `Ipv6Network` inside
+    /// `::ffff:0:0/96`, extending the prefix by 96 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.0.2.0/24".parse().unwrap();
+    /// assert_eq!(net.to_ipv6_mapped().to_string(), "::ffff:192.0.2.0/120");
+    /// ```
+    pub const fn to_ipv6_mapped(&self) -> Ipv6Network {
+        match Ipv6Network::new_checked(self.addr.to_ipv6_mapped(), self.prefix + 96) {
+            Some(net) => net,
+            None => unreachable!(),
+        }
+    }
+
+    /// Converts this network to the equivalent IPv4-compatible `Ipv6Network` inside `::/96`,
+    /// extending the prefix by 96 bits.
+    ///
+    /// IPv4-compatible addresses are deprecated by [RFC 4291], but still show up in older
+    /// configurations.
+    ///
+    /// [RFC 4291]: https://www.rfc-editor.org/rfc/rfc4291#section-2.5.5.1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.0.2.0/24".parse().unwrap();
+    /// assert_eq!(net.to_ipv6_compatible().to_string(), "::c000:200/120");
+    /// ```
+    pub const fn to_ipv6_compatible(&self) -> Ipv6Network {
+        match Ipv6Network::new_checked(self.addr.to_ipv6_compatible(), self.prefix + 96) {
+            Some(net) => net,
+            None => unreachable!(),
+        }
+    }
+
+    /// The length of the longest string `Display` can produce for an `Ipv4Network`, as in
+    /// `255.255.255.255/32`.
+    pub const MAX_DISPLAY_LEN: usize = 18;
+
+    /// Writes the CIDR notation of this `Ipv4Network` to `w`, without allocating.
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write!(w, "{}", self)
+    }
+
+    /// Formats this `Ipv4Network` into `buf` and returns the written part, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.168.0.0/16".parse().unwrap();
+    /// let mut buf = [0; Ipv4Network::MAX_DISPLAY_LEN];
+    /// assert_eq!(net.to_str_buf(&mut buf), "192.168.0.0/16");
+    /// ```
+    pub fn to_str_buf<'a>(&self, buf: &'a mut [u8; Ipv4Network::MAX_DISPLAY_LEN]) -> &'a str {
+        format_into(self, buf)
+    }
+}
+
+/// The alternate form (`{:#?}`) additionally prints the network, broadcast address, mask and
+/// size derived from the address and prefix.
+impl fmt::Debug for Ipv4Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alternate = f.alternate();
+        let mut s = f.debug_struct("Ipv4Network");
+        s.field("addr", &self.addr).field("prefix", &self.prefix);
+        if alternate {
+            s.field("network", &self.network())
+                .field("broadcast", &self.broadcast())
+                .field("mask", &self.mask())
+                .field("size", &self.size());
+        }
+        s.finish()
+    }
 }
 
 impl fmt::Display for Ipv4Network {
@@ -368,8 +1008,125 @@ impl Iterator for Ipv4NetworkIterator {
         };
         Some(next.into())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Ipv4Addr> {
+        let next = self.next?;
+        self.next = u32::try_from(n)
+            .ok()
+            .and_then(|n| next.checked_add(n))
+            .filter(|&addr| addr <= self.end);
+        self.next()
+    }
+
+    fn count(self) -> usize {
+        usize::try_from(self.remaining()).expect("address count overflows usize")
+    }
+
+    fn last(self) -> Option<Ipv4Addr> {
+        self.next.map(|_| self.end.into())
+    }
 }
 
+impl Ipv4NetworkIterator {
+    fn remaining(&self) -> u64 {
+        match self.next {
+            None => 0,
+            Some(next) => u64::from(self.end - next) + 1,
+        }
+    }
+}
+
+impl DoubleEndedIterator for Ipv4NetworkIterator {
+    fn next_back(&mut self) -> Option<Ipv4Addr> {
+        let next = self.next?;
+        let end = self.end;
+        if end == next {
+            self.next = None;
+        } else {
+            self.end -= 1;
+        }
+        Some(end.into())
+    }
+}
+
+/// The iterator over `0.0.0.0/0` yields 2<sup>32</sup> addresses, which only fits in a `usize`
+/// on 64-bit targets.
+#[cfg(target_pointer_width = "64")]
+impl ExactSizeIterator for Ipv4NetworkIterator {}
+
+impl FusedIterator for Ipv4NetworkIterator {}
+
+/// An iterator over evenly spaced addresses of an `Ipv4Network`, created by
+/// [`Ipv4Network::iter_step`] and [`Ipv4Network::iter_strata`].
+#[derive(Clone, Debug)]
+pub struct Ipv4StepIterator {
+    next: Option<u32>,
+    end: u32,
+    step: u32,
+}
+
+impl Iterator for Ipv4StepIterator {
+    type Item = Ipv4Addr;
+
+    fn next(&mut self) -> Option<Ipv4Addr> {
+        let next = self.next?;
+        self.next = next
+            .checked_add(self.step)
+            .filter(|&after| self.step != 0 && after <= self.end);
+        Some(Ipv4Addr::from_bits(next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.next {
+            None => 0,
+            Some(_) if self.step == 0 => 1,
+            Some(next) => u64::from((self.end - next) / self.step) + 1,
+        };
+        match usize::try_from(remaining) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for Ipv4StepIterator {}
+
+/// An iterator over the networks enclosing an `Ipv4Network`, created by [`Ipv4Network::supernets`] and
+/// [`Ipv4Network::supernets_to`].
+#[derive(Clone, Debug)]
+pub struct Ipv4SupernetIterator {
+    addr: Ipv4Addr,
+    prefix: u8,
+    stop_prefix: u8,
+}
+
+impl Iterator for Ipv4SupernetIterator {
+    type Item = Ipv4Network;
+
+    fn next(&mut self) -> Option<Ipv4Network> {
+        if self.prefix <= self.stop_prefix {
+            return None;
+        }
+        self.prefix -= 1;
+        let net = Ipv4Network::new_checked(self.addr, self.prefix)?;
+        Ipv4Network::new_checked(net.network(), self.prefix)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.prefix.saturating_sub(self.stop_prefix));
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Ipv4SupernetIterator {}
+
 impl IntoIterator for &'_ Ipv4Network {
     type IntoIter = Ipv4NetworkIterator;
     type Item = Ipv4Addr;
@@ -380,11 +1137,11 @@ impl IntoIterator for &'_ Ipv4Network {
 
 /// Converts a `Ipv4Addr` network mask into a prefix.
 ///
-/// If the mask is invalid this will return an `IpNetworkError::InvalidPrefix`.
+/// If the mask is invalid this will return an `IpNetworkError::InvalidNetmask`.
 pub fn ipv4_mask_to_prefix(mask: Ipv4Addr) -> Result<u8, IpNetworkError> {
     match ipv4_mask_to_prefix_checked(mask) {
         Some(prefix) => Ok(prefix),
-        None => Err(IpNetworkError::InvalidPrefix),
+        None => Err(IpNetworkError::InvalidNetmask),
     }
 }
 
@@ -403,9 +1160,124 @@ pub const fn ipv4_mask_to_prefix_checked(mask: Ipv4Addr) -> Option<u8> {
     }
 }
 
+/// Sorts `networks` and merges overlapping and adjacent networks into the minimal list of
+/// `Ipv4Network`s covering the same addresses.
+///
+/// The vector is modified in place and never reallocated. Host bits are cleared from the
+/// resulting networks.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{ipv4_merge_in_place, Ipv4Network};
+///
+/// let mut nets: Vec<Ipv4Network> = ["10.0.0.0/25", "10.0.0.128/25", "10.0.0.64/26"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// ipv4_merge_in_place(&mut nets);
+/// assert_eq!(nets, vec!["10.0.0.0/24".parse::<Ipv4Network>().unwrap()]);
+/// ```
+pub fn ipv4_merge_in_place(networks: &mut Vec<Ipv4Network>) {
+    merge_in_place(networks)
+}
+
+/// Merges `networks` like [`ipv4_merge_in_place`], and additionally replaces neighboring
+/// networks by their smallest common supernet when the addresses this adds to it are within
+/// `tolerance`.
+///
+/// This trades accuracy for fewer networks, such as when the rules they turn into are scarce.
+/// Merging is greedy, and every resulting network covers at most `tolerance` addresses that
+/// were not in the input.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{ipv4_merge_with_waste, Ipv4Network, WasteTolerance};
+///
+/// let mut nets: Vec<Ipv4Network> = ["10.0.0.0/24", "10.0.2.0/24", "10.0.3.0/24"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// ipv4_merge_with_waste(&mut nets, WasteTolerance::Addresses(256));
+/// assert_eq!(nets, vec!["10.0.0.0/22".parse::<Ipv4Network>().unwrap()]);
+/// ```
+pub fn ipv4_merge_with_waste(networks: &mut Vec<Ipv4Network>, tolerance: WasteTolerance) {
+    merge_with_waste_in_place(networks, tolerance)
+}
+
+/// Returns the network in `sorted` that contains `ip`, in `O(log n)` time.
+///
+/// `sorted` must be sorted and its networks must not overlap, as left by
+/// [`ipv4_merge_in_place`]. Otherwise the result is unspecified, but it is always either `None`
+/// or a network containing `ip`.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::Ipv4Addr;
+/// use ipnetwork::{ipv4_find_containing, Ipv4Network};
+///
+/// let sorted: Vec<Ipv4Network> = ["10.0.0.0/8", "172.16.0.0/12", "192.168.0.0/16"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// assert_eq!(ipv4_find_containing(&sorted, Ipv4Addr::new(172, 20, 1, 1)), Some(&sorted[1]));
+/// assert_eq!(ipv4_find_containing(&sorted, Ipv4Addr::new(8, 8, 8, 8)), None);
+/// ```
+pub fn ipv4_find_containing(sorted: &[Ipv4Network], ip: Ipv4Addr) -> Option<&Ipv4Network> {
+    let after = sorted.partition_point(|net| net.network() <= ip);
+    sorted[..after].last().filter(|net| net.contains(ip))
+}
+
+impl Merge for Ipv4Network {
+    fn normalize(self) -> Self {
+        Ipv4Network {
+            addr: self.network(),
+            prefix: self.prefix,
+        }
+    }
+
+    fn covers(self, other: Self) -> bool {
+        self.prefix <= other.prefix && self.contains(other.addr)
+    }
+
+    fn join(self, other: Self) -> Option<Self> {
+        if self.prefix != other.prefix || self.prefix == 0 || self.addr == other.addr {
+            return None;
+        }
+        let parent = Ipv4Network {
+            addr: self.addr,
+            prefix: self.prefix - 1,
+        };
+        if parent.network() == self.addr && parent.contains(other.addr) {
+            Some(parent)
+        } else {
+            None
+        }
+    }
+
+    fn common_supernet(self, other: Self) -> Option<Self> {
+        let shared = (self.addr.to_bits() ^ other.addr.to_bits()).leading_zeros() as u8;
+        let prefix = self.prefix.min(other.prefix).min(shared);
+        Some(
+            Ipv4Network {
+                addr: self.addr,
+                prefix,
+            }
+            .normalize(),
+        )
+    }
+
+    fn last_offset(self) -> u128 {
+        u128::from(self.broadcast().to_bits() - self.network().to_bits())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::error::ErrorKind;
     use std::collections::HashMap;
     use std::mem;
     use std::net::Ipv4Addr;
@@ -569,6 +1441,57 @@ mod test {
         assert_eq!(None, iter.next());
     }
 
+    #[test]
+    fn iterator_v4_rev() {
+        let cidr: Ipv4Network = "192.168.122.0/30".parse().unwrap();
+        let mut iter = cidr.iter();
+        assert_eq!(Ipv4Addr::new(192, 168, 122, 3), iter.next_back().unwrap());
+        assert_eq!(Ipv4Addr::new(192, 168, 122, 0), iter.next().unwrap());
+        assert_eq!(Ipv4Addr::new(192, 168, 122, 2), iter.next_back().unwrap());
+        assert_eq!(Ipv4Addr::new(192, 168, 122, 1), iter.next_back().unwrap());
+        assert_eq!(None, iter.next_back());
+        assert_eq!(None, iter.next());
+
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(all.iter().next_back(), Some(Ipv4Addr::BROADCAST));
+        let host: Ipv4Network = "10.0.0.1/32".parse().unwrap();
+        assert_eq!(host.iter().rev().collect::<Vec<_>>(), [host.ip()]);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn iterator_v4_len() {
+        let cidr: Ipv4Network = "10.0.0.0/24".parse().unwrap();
+        let mut iter = cidr.iter();
+        assert_eq!(iter.len(), 256);
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 254);
+        assert_eq!(iter.by_ref().count(), 254);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(all.iter().len(), 1 << 32);
+    }
+
+    #[test]
+    fn iterator_v4_nth() {
+        let cidr: Ipv4Network = "10.0.0.0/8".parse().unwrap();
+        let mut iter = cidr.iter();
+        assert_eq!(iter.nth(1_000_000), Some(Ipv4Addr::new(10, 15, 66, 64)));
+        assert_eq!(iter.next(), Some(Ipv4Addr::new(10, 15, 66, 65)));
+        assert_eq!(iter.clone().count(), (1 << 24) - 1_000_002);
+        assert_eq!(iter.clone().last(), Some(Ipv4Addr::new(10, 255, 255, 255)));
+        assert_eq!(iter.nth(1 << 24), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.last(), None);
+
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(all.iter().nth(u32::MAX as usize), Some(Ipv4Addr::BROADCAST));
+        assert_eq!(all.iter().last(), Some(Ipv4Addr::BROADCAST));
+    }
+
     // Tests the entire IPv4 space to see if the iterator will stop at the correct place
     // and not overflow or wrap around. Ignored since it takes a long time to run.
     #[test]
@@ -601,7 +1524,14 @@ mod test {
     fn parse_netmask_broken_v4() {
         assert_eq!(
             "192.168.1.0/255.0.255.0".parse::<Ipv4Network>(),
-            Err(IpNetworkError::InvalidPrefix)
+            Err(IpNetworkError::InvalidNetmask)
+        );
+        assert_eq!(
+            "10.0.0.0/255.0.255.0"
+                .parse::<Ipv4Network>()
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Mask
         );
     }
 
@@ -609,7 +1539,7 @@ mod test {
     fn invalid_v4_mask_to_prefix() {
         let mask = Ipv4Addr::new(255, 0, 255, 0);
         let prefix = ipv4_mask_to_prefix(mask);
-        assert!(prefix.is_err());
+        assert_eq!(prefix, Err(IpNetworkError::InvalidNetmask));
     }
 
     #[test]
@@ -626,7 +1556,10 @@ mod test {
             // Negative test-case.
             let addr = Ipv4Addr::new(127, 0, 0, 1);
             let mask = Ipv4Addr::new(255, 0, 255, 0);
-            Ipv4Network::with_netmask(addr, mask).unwrap_err();
+            assert_eq!(
+                Ipv4Network::with_netmask(addr, mask),
+                Err(IpNetworkError::InvalidNetmask)
+            );
         }
     }
 
@@ -765,4 +1698,256 @@ mod test {
             high_addrs[255]
         );
     }
+
+    #[test]
+    fn debug_v4() {
+        let net: Ipv4Network = "10.1.2.3/24".parse().unwrap();
+        assert_eq!(
+            format!("{net:?}"),
+            "Ipv4Network { addr: 10.1.2.3, prefix: 24 }"
+        );
+        let alternate = format!("{net:#?}");
+        assert!(alternate.contains("network: 10.1.2.0,"));
+        assert!(alternate.contains("broadcast: 10.1.2.255,"));
+        assert!(alternate.contains("mask: 255.255.255.0,"));
+        assert!(alternate.contains("size: 256,"));
+    }
+
+    #[test]
+    fn host_bits_v4() {
+        for prefix in 0..=32 {
+            let net = Ipv4Network::new(Ipv4Addr::new(10, 0, 0, 0), prefix).unwrap();
+            assert_eq!(net.host_bits(), 32 - prefix);
+            assert_eq!(net.size_log2(), u32::from(32 - prefix));
+        }
+    }
+
+    #[test]
+    fn relation_ignores_host_bits_v4() {
+        let a: Ipv4Network = "10.0.0.5/24".parse().unwrap();
+        let b: Ipv4Network = "10.0.0.0/24".parse().unwrap();
+        assert_eq!(a.relation(b), NetworkRelation::Equal);
+        assert_eq!(b.relation(a), NetworkRelation::Equal);
+
+        let sub: Ipv4Network = "10.0.0.128/25".parse().unwrap();
+        assert_eq!(a.relation(sub), NetworkRelation::SupersetOf);
+        assert_eq!(sub.relation(a), NetworkRelation::SubsetOf);
+    }
+
+    #[test]
+    fn contains_usable_v4() {
+        let net: Ipv4Network = "10.0.0.5/30".parse().unwrap();
+        let usable: Vec<_> = net.iter().filter(|ip| net.contains_usable(*ip)).collect();
+        assert_eq!(
+            usable,
+            vec![Ipv4Addr::new(10, 0, 0, 5), Ipv4Addr::new(10, 0, 0, 6)]
+        );
+        assert!(!net.contains_usable(Ipv4Addr::new(10, 0, 0, 8)));
+
+        let host: Ipv4Network = "10.0.0.5/32".parse().unwrap();
+        assert!(host.contains_usable(Ipv4Addr::new(10, 0, 0, 5)));
+    }
+
+    #[test]
+    fn broadcast_detection_v4() {
+        assert!(Ipv4Network::is_limited_broadcast(Ipv4Addr::new(
+            255, 255, 255, 255
+        )));
+        assert!(!Ipv4Network::is_limited_broadcast(Ipv4Addr::new(
+            255, 255, 255, 254
+        )));
+
+        let net: Ipv4Network = "10.1.2.3/8".parse().unwrap();
+        assert!(net.is_directed_broadcast(Ipv4Addr::new(10, 255, 255, 255)));
+        assert!(!net.is_directed_broadcast(Ipv4Addr::new(10, 0, 0, 0)));
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        assert!(all.is_directed_broadcast(Ipv4Addr::new(255, 255, 255, 255)));
+        let host: Ipv4Network = "10.0.0.1/32".parse().unwrap();
+        assert!(!host.is_directed_broadcast(Ipv4Addr::new(10, 0, 0, 1)));
+    }
+
+    #[test]
+    fn to_ipv6_v4() {
+        let net: Ipv4Network = "10.1.2.3/8".parse().unwrap();
+        let mapped = net.to_ipv6_mapped();
+        assert_eq!(mapped.prefix(), 104);
+        assert_eq!(mapped.ip(), Ipv4Addr::new(10, 1, 2, 3).to_ipv6_mapped());
+        assert_eq!(
+            mapped.network(),
+            Ipv4Addr::new(10, 0, 0, 0).to_ipv6_mapped()
+        );
+        assert_eq!(
+            mapped.broadcast(),
+            Ipv4Addr::new(10, 255, 255, 255).to_ipv6_mapped()
+        );
+
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(all.to_ipv6_mapped().to_string(), "::ffff:0.0.0.0/96");
+        assert_eq!(all.to_ipv6_compatible().to_string(), "::/96");
+        let host: Ipv4Network = "10.0.0.1/32".parse().unwrap();
+        assert_eq!(host.to_ipv6_compatible().prefix(), 128);
+    }
+
+    #[test]
+    fn overlap_partition_v4() {
+        let a: Ipv4Network = "10.0.0.5/24".parse().unwrap();
+        let b: Ipv4Network = "10.0.0.200/30".parse().unwrap();
+        let (only_a, shared, only_b) = a.overlap_partition(b);
+        let expected: Vec<Ipv4Network> = [
+            "10.0.0.0/25",
+            "10.0.0.128/26",
+            "10.0.0.192/29",
+            "10.0.0.204/30",
+            "10.0.0.208/28",
+            "10.0.0.224/27",
+        ]
+        .iter()
+        .map(|n| n.parse().unwrap())
+        .collect();
+        assert_eq!(only_a, expected);
+        assert_eq!(shared, Some("10.0.0.200/30".parse().unwrap()));
+        assert!(only_b.is_empty());
+
+        let (only_b, shared, only_a) = b.overlap_partition(a);
+        assert_eq!((only_b, shared), (Vec::new(), Some(b.normalize())));
+        assert_eq!(only_a.len(), 6);
+
+        let c: Ipv4Network = "10.0.1.0/24".parse().unwrap();
+        assert_eq!(a.overlap_partition(c), (vec![a.normalize()], None, vec![c]));
+        assert_eq!(c.overlap_partition(c), (Vec::new(), Some(c), Vec::new()));
+    }
+
+    #[test]
+    fn overlap_partition_whole_space_v4() {
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        let host: Ipv4Network = "255.255.255.255/32".parse().unwrap();
+        let (rest, shared, _) = all.overlap_partition(host);
+        assert_eq!(rest.len(), 32);
+        assert_eq!(rest[0], "0.0.0.0/1".parse().unwrap());
+        assert_eq!(shared, Some(host));
+    }
+
+    #[test]
+    fn to_str_buf_longest_v4() {
+        let net: Ipv4Network = "255.255.255.255/32".parse().unwrap();
+        let mut buf = [0; Ipv4Network::MAX_DISPLAY_LEN];
+        assert_eq!(net.to_str_buf(&mut buf), "255.255.255.255/32");
+
+        let mut s = String::new();
+        net.write_to(&mut s).unwrap();
+        assert_eq!(s, net.to_string());
+    }
+
+    #[test]
+    fn supernets_v4() {
+        let net: Ipv4Network = "192.168.1.77/26".parse().unwrap();
+        let supernets: Vec<Ipv4Network> = net.supernets().collect();
+        assert_eq!(supernets.len(), 26);
+        assert_eq!(supernets[0], "192.168.1.0/25".parse().unwrap());
+        assert_eq!(supernets[25], "0.0.0.0/0".parse().unwrap());
+        assert!(supernets.iter().all(|s| s.ip() == s.network()));
+
+        assert_eq!(net.supernets_to(26).next(), None);
+        assert_eq!(net.supernets_to(30).len(), 0);
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(all.supernets().next(), None);
+    }
+
+    #[test]
+    fn as_u32_range_bounds() {
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(all.as_u32_range(), 0..=u32::MAX);
+        let host: Ipv4Network = "255.255.255.255/32".parse().unwrap();
+        assert_eq!(host.as_u32_range(), u32::MAX..=u32::MAX);
+    }
+
+    #[test]
+    fn nth_sibling_v4_bounds() {
+        let net: Ipv4Network = "10.0.0.7/30".parse().unwrap();
+        assert_eq!(net.nth_sibling(0), Some(net));
+        assert_eq!(net.nth_sibling(1), Some("10.0.0.11/30".parse().unwrap()));
+        assert_eq!(
+            net.nth_sibling(-2),
+            Some("9.255.255.255/30".parse().unwrap())
+        );
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(all.nth_sibling(0), Some(all));
+        assert_eq!(all.nth_sibling(1), None);
+        let last: Ipv4Network = "255.255.255.0/24".parse().unwrap();
+        assert_eq!(last.nth_sibling(1), None);
+        assert_eq!(
+            last.nth_sibling(-0xff_ffff),
+            Some("0.0.0.0/24".parse().unwrap())
+        );
+        assert_eq!(last.nth_sibling(-0x100_0000), None);
+        assert_eq!(last.nth_sibling(i64::MIN), None);
+    }
+
+    #[test]
+    fn find_containing_edges() {
+        let sorted: Vec<Ipv4Network> = ["0.0.0.0/32", "10.0.0.0/24", "10.0.1.0/25", "255.0.0.0/8"]
+            .iter()
+            .map(|n| n.parse().unwrap())
+            .collect();
+        let find = |ip| ipv4_find_containing(&sorted, ip);
+        assert_eq!(find(Ipv4Addr::UNSPECIFIED), Some(&sorted[0]));
+        assert_eq!(find(Ipv4Addr::new(0, 0, 0, 1)), None);
+        assert_eq!(find(Ipv4Addr::new(10, 0, 0, 255)), Some(&sorted[1]));
+        assert_eq!(find(Ipv4Addr::new(10, 0, 1, 127)), Some(&sorted[2]));
+        assert_eq!(find(Ipv4Addr::new(10, 0, 1, 128)), None);
+        assert_eq!(find(Ipv4Addr::BROADCAST), Some(&sorted[3]));
+        assert_eq!(ipv4_find_containing(&[], Ipv4Addr::BROADCAST), None);
+    }
+
+    #[test]
+    fn iter_step_edges() {
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        let mut iter = all.iter_step(u32::MAX);
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.next(), Some(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(iter.next(), Some(Ipv4Addr::BROADCAST));
+        assert_eq!(iter.next(), None);
+
+        if usize::BITS > 32 {
+            assert_eq!(all.iter_step(1).size_hint(), (1 << 32, Some(1 << 32)));
+        }
+        let strata = all.iter_strata(0, 7).unwrap();
+        assert_eq!(strata.collect::<Vec<_>>(), [Ipv4Addr::new(0, 0, 0, 7)]);
+        let strata = all.iter_strata(8, 0).unwrap();
+        assert_eq!(strata.size_hint(), (256, Some(256)));
+
+        let net: Ipv4Network = "10.0.0.0/24".parse().unwrap();
+        assert_eq!(
+            net.iter_strata(23, 0).unwrap_err(),
+            IpNetworkError::InvalidPrefix
+        );
+        assert_eq!(
+            net.iter_strata(33, 0).unwrap_err(),
+            IpNetworkError::InvalidPrefix
+        );
+        assert_eq!(net.iter_strata(32, 0).unwrap().count(), 256);
+        assert!(net.iter_strata(32, 1).is_err());
+    }
+
+    #[test]
+    #[allow(clippy::iter_nth_zero)]
+    fn subnets_v4() {
+        let net: Ipv4Network = "10.1.2.3/30".parse().unwrap();
+        let subnets: Vec<String> = net.subnets(31).unwrap().map(|n| n.to_string()).collect();
+        assert_eq!(subnets, ["10.1.2.0/31", "10.1.2.2/31"]);
+        assert_eq!(
+            net.subnets(30).unwrap().collect::<Vec<_>>(),
+            ["10.1.2.0/30".parse().unwrap()]
+        );
+        assert_eq!(
+            net.subnets(32).unwrap().rev().nth(1),
+            Some("10.1.2.2/32".parse().unwrap())
+        );
+        assert_eq!(net.subnets(33).unwrap_err(), IpNetworkError::InvalidPrefix);
+        assert_eq!(net.subnets(29).unwrap_err(), IpNetworkError::InvalidPrefix);
+
+        let all: Ipv4Network = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(all.subnets(1).unwrap().count(), 2);
+        assert_eq!(all.subnets(0).unwrap().nth(0), Some(all));
+    }
 }
@@ -1,12 +1,24 @@
 use crate::error::IpNetworkError;
+use crate::format::format_into;
+use crate::merge::{merge_in_place, merge_with_waste_in_place, Merge, WasteTolerance};
+use crate::netmask::Netmask6;
 use crate::parse::{cidr_parts, parse_prefix};
-use std::{convert::TryFrom, fmt, net::Ipv6Addr, str::FromStr};
+use crate::prefix::Ipv6Prefix;
+use crate::relation::NetworkRelation;
+use std::{
+    convert::TryFrom, fmt, iter::FusedIterator, net::Ipv6Addr, ops::RangeInclusive, str::FromStr,
+};
 
 const IPV6_BITS: u8 = 128;
 const IPV6_SEGMENT_BITS: u8 = 16;
 
 /// Represents a network range where the IP addresses are of v6
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug, PartialEq, Eq, Hash))
+)]
 pub struct Ipv6Network {
     addr: Ipv6Addr,
     prefix: u8,
@@ -18,8 +30,13 @@ impl<'de> serde::Deserialize<'de> for Ipv6Network {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = <String>::deserialize(deserializer)?;
-        Ipv6Network::from_str(&s).map_err(serde::de::Error::custom)
+        if deserializer.is_human_readable() {
+            let s = <String>::deserialize(deserializer)?;
+            Ipv6Network::from_str(&s).map_err(serde::de::Error::custom)
+        } else {
+            let (ip, prefix) = <(Ipv6Addr, u8)>::deserialize(deserializer)?;
+            Ipv6Network::new(ip, prefix).map_err(serde::de::Error::custom)
+        }
     }
 }
 
@@ -29,7 +46,39 @@ impl serde::Serialize for Ipv6Network {
     where
         S: serde::Serializer,
     {
-        serializer.collect_str(self)
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serde::Serialize::serialize(&(self.ip(), self.prefix()), serializer)
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedIpv6Network {
+    /// Returns the address of the archived network.
+    pub const fn ip(&self) -> Ipv6Addr {
+        self.addr.as_ipv6()
+    }
+
+    /// Returns the prefix length of the archived network.
+    ///
+    /// Validating an archive only checks its layout, so the prefix of an archive from an
+    /// untrusted source may be greater than 128.
+    pub const fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Copies the network out of the archive, checking its prefix.
+    pub fn to_network(&self) -> Result<Ipv6Network, IpNetworkError> {
+        Ipv6Network::new(self.ip(), self.prefix)
+    }
+
+    /// Checks if the archived network contains `ip`, without copying it out of the archive.
+    ///
+    /// A network with an invalid prefix contains no addresses.
+    pub fn contains(&self, ip: Ipv6Addr) -> bool {
+        self.to_network().is_ok_and(|net| net.contains(ip))
     }
 }
 
@@ -73,6 +122,23 @@ impl schemars::JsonSchema for Ipv6Network {
     }
 }
 
+#[cfg(feature = "smoltcp")]
+impl From<Ipv6Network> for smoltcp::wire::Ipv6Cidr {
+    fn from(net: Ipv6Network) -> Self {
+        smoltcp::wire::Ipv6Cidr::new(net.ip(), net.prefix())
+    }
+}
+
+#[cfg(feature = "smoltcp")]
+impl From<smoltcp::wire::Ipv6Cidr> for Ipv6Network {
+    fn from(cidr: smoltcp::wire::Ipv6Cidr) -> Self {
+        Ipv6Network {
+            addr: cidr.address(),
+            prefix: cidr.prefix_len(),
+        }
+    }
+}
+
 impl Ipv6Network {
     /// Constructs a new `Ipv6Network` from any `Ipv6Addr` and a prefix denoting the network size.
     ///
@@ -126,7 +192,7 @@ impl Ipv6Network {
 
     /// Constructs a new `Ipv6Network` from a network address and a network mask.
     ///
-    /// If the netmask is not valid this will return an `IpNetworkError::InvalidPrefix`.
+    /// If the netmask is not valid this will return an `IpNetworkError::InvalidNetmask`.
     pub fn with_netmask(netaddr: Ipv6Addr, netmask: Ipv6Addr) -> Result<Self, IpNetworkError> {
         let prefix = ipv6_mask_to_prefix(netmask)?;
         let net = Self {
@@ -136,6 +202,95 @@ impl Ipv6Network {
         Ok(net)
     }
 
+    /// Constructs a new `Ipv6Network` from any `Ipv6Addr` and an already validated prefix.
+    ///
+    /// Since an `Ipv6Prefix` is always between 0 and 128, this cannot fail.
+    pub const fn with_prefix(addr: Ipv6Addr, prefix: Ipv6Prefix) -> Ipv6Network {
+        Ipv6Network {
+            addr,
+            prefix: prefix.get(),
+        }
+    }
+
+    /// Constructs a new `Ipv6Network` from any `Ipv6Addr` and an already validated netmask.
+    ///
+    /// Since a `Netmask6` is always contiguous, this cannot fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv6Addr;
+    /// use ipnetwork::{Ipv6Network, Netmask6};
+    ///
+    /// let mask: Netmask6 = "ffff:ffff::".parse().unwrap();
+    /// let addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    /// let net = Ipv6Network::with_validated_netmask(addr, mask);
+    /// assert_eq!(net, "2001:db8::1/32".parse().unwrap());
+    /// ```
+    pub const fn with_validated_netmask(addr: Ipv6Addr, netmask: Netmask6) -> Ipv6Network {
+        Ipv6Network::with_prefix(addr, netmask.prefix())
+    }
+
+    /// The link-local prefix `fe80::/64` that every IPv6 interface derives its link-local
+    /// address from.
+    pub const LINK_LOCAL: Ipv6Network = Ipv6Network {
+        addr: Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0),
+        prefix: 64,
+    };
+
+    /// Constructs the link-local address with the given 64-bit interface identifier, paired with
+    /// its `fe80::/64` prefix.
+    ///
+    /// The interface identifier may be a modified EUI-64 (see [`Ipv6Network::link_local_from_mac`])
+    /// or a random value, as recommended by [RFC 8064] for stable and [RFC 8981] for temporary
+    /// addresses.
+    ///
+    /// [RFC 8064]: https://www.rfc-editor.org/rfc/rfc8064
+    /// [RFC 8981]: https://www.rfc-editor.org/rfc/rfc8981
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv6Addr;
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net = Ipv6Network::link_local(0x1234_5678_9abc_def0);
+    /// assert_eq!(net.ip(), "fe80::1234:5678:9abc:def0".parse::<Ipv6Addr>().unwrap());
+    /// assert_eq!(net.prefix(), 64);
+    /// assert_eq!(net.network(), Ipv6Network::LINK_LOCAL.ip());
+    /// ```
+    pub const fn link_local(interface_id: u64) -> Ipv6Network {
+        let prefix = Self::LINK_LOCAL.addr.to_bits();
+        Ipv6Network {
+            addr: Ipv6Addr::from_bits(prefix | interface_id as u128),
+            prefix: Self::LINK_LOCAL.prefix,
+        }
+    }
+
+    /// Constructs the link-local address whose interface identifier is the modified EUI-64
+    /// derived from the 48-bit MAC address `mac`, paired with its `fe80::/64` prefix.
+    ///
+    /// As described in [RFC 4291, appendix A], `ff:fe` is inserted in the middle of the MAC
+    /// address and the universal/local bit is inverted.
+    ///
+    /// [RFC 4291, appendix A]: https://www.rfc-editor.org/rfc/rfc4291#appendix-A
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv6Addr;
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net = Ipv6Network::link_local_from_mac([0x00, 0x1b, 0x21, 0x3a, 0x4b, 0x5c]);
+    /// assert_eq!(net.ip(), "fe80::21b:21ff:fe3a:4b5c".parse::<Ipv6Addr>().unwrap());
+    /// assert_eq!(net.to_string(), "fe80::21b:21ff:fe3a:4b5c/64");
+    /// ```
+    pub const fn link_local_from_mac(mac: [u8; 6]) -> Ipv6Network {
+        let [a, b, c, d, e, f] = mac;
+        let interface_id = u64::from_be_bytes([a ^ 0x02, b, c, 0xff, 0xfe, d, e, f]);
+        Self::link_local(interface_id)
+    }
+
     /// Returns an iterator over `Ipv6Network`. Each call to `next` will return the next
     /// `Ipv6Addr` in the given network. `None` will be returned when there are no more
     /// addresses.
@@ -160,6 +315,102 @@ impl Ipv6Network {
         }
     }
 
+    /// Returns an iterator over the addresses in this network, starting at `start` rather than
+    /// at the network address.
+    ///
+    /// Returns `None` if `start` is not contained in the network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv6Addr;
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8::/126".parse().unwrap();
+    /// let mut iter = net.iter_from("2001:db8::2".parse().unwrap()).unwrap();
+    /// assert_eq!(iter.next(), Some("2001:db8::2".parse::<Ipv6Addr>().unwrap()));
+    /// assert_eq!(iter.next(), Some("2001:db8::3".parse::<Ipv6Addr>().unwrap()));
+    /// assert_eq!(iter.next(), None);
+    ///
+    /// assert!(net.iter_from("2001:db8::4".parse().unwrap()).is_none());
+    /// ```
+    pub fn iter_from(&self, start: Ipv6Addr) -> Option<Ipv6NetworkIterator> {
+        if !self.contains(start) {
+            return None;
+        }
+        Some(Ipv6NetworkIterator {
+            next: Some(u128::from(start)),
+            end: u128::from(self.broadcast()),
+        })
+    }
+
+    /// Returns an iterator over every `step`-th address in this network, starting at the
+    /// network address.
+    ///
+    /// Unlike `iter().step_by(step)`, this computes each address directly instead of advancing
+    /// one address at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv6Addr;
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8::/126".parse().unwrap();
+    /// let addrs: Vec<Ipv6Addr> = net.iter_step(3).collect();
+    /// assert_eq!(addrs, ["2001:db8::".parse::<Ipv6Addr>().unwrap(), "2001:db8::3".parse().unwrap()]);
+    /// ```
+    pub fn iter_step(self, step: u128) -> Ipv6StepIterator {
+        assert!(step != 0, "step must be non-zero");
+        Ipv6StepIterator {
+            next: Some(self.network().to_bits()),
+            end: self.broadcast().to_bits(),
+            step,
+        }
+    }
+
+    /// Returns an iterator over the address at `offset` within each subnet of length `prefix`
+    /// of this network, such as one address per `/64` of a `/48`.
+    ///
+    /// Returns an `IpNetworkError::InvalidPrefix` if `prefix` is shorter than the prefix of this
+    /// network or longer than 128, and an `IpNetworkError::InvalidRange` if
+    /// `offset` lies outside a subnet of length `prefix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv6Addr;
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8::/48".parse().unwrap();
+    /// // The first address of every /64.
+    /// let mut subnets = net.iter_strata(64, 0).unwrap();
+    /// assert_eq!(subnets.next(), Some("2001:db8::".parse().unwrap()));
+    /// assert_eq!(subnets.next(), Some("2001:db8:0:1::".parse().unwrap()));
+    /// ```
+    pub fn iter_strata(self, prefix: u8, offset: u128) -> Result<Ipv6StepIterator, IpNetworkError> {
+        if prefix < self.prefix || prefix > IPV6_BITS {
+            return Err(IpNetworkError::InvalidPrefix);
+        }
+        let host_bits = u32::from(IPV6_BITS - prefix);
+        if offset.checked_shr(host_bits).unwrap_or(0) != 0 {
+            return Err(IpNetworkError::InvalidRange(format!(
+                "offset {offset} is outside a /{prefix}"
+            )));
+        }
+        let step = 1u128.checked_shl(host_bits).unwrap_or(0);
+        Ok(Ipv6StepIterator {
+            next: Some(self.network().to_bits() + offset),
+            end: self.broadcast().to_bits(),
+            // A step of zero stands for the whole address space, which is only taken once.
+            step,
+        })
+    }
+
     pub const fn ip(&self) -> Ipv6Addr {
         self.addr
     }
@@ -168,6 +419,44 @@ impl Ipv6Network {
         self.prefix
     }
 
+    /// Returns the prefix of this `Ipv6Network` as an `Ipv6Prefix`.
+    pub const fn prefix_len(&self) -> Ipv6Prefix {
+        match Ipv6Prefix::new_checked(self.prefix) {
+            Some(prefix) => prefix,
+            None => unreachable!(),
+        }
+    }
+
+    /// Returns the number of host bits in this `Ipv6Network`, that is `128 - prefix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8::/32".parse().unwrap();
+    /// assert_eq!(net.host_bits(), 96);
+    /// ```
+    pub const fn host_bits(&self) -> u8 {
+        IPV6_BITS - self.prefix
+    }
+
+    /// Returns the base 2 logarithm of the number of addresses in this `Ipv6Network`.
+    ///
+    /// Unlike [`Ipv6Network::size`] this is exact for every prefix, including `/0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "::/0".parse().unwrap();
+    /// assert_eq!(net.size_log2(), 128);
+    /// ```
+    pub const fn size_log2(&self) -> u32 {
+        self.host_bits() as u32
+    }
+
     /// Checks if the given `Ipv6Network` is a subnet of the other.
     pub fn is_subnet_of(self, other: Ipv6Network) -> bool {
         other.ip() <= self.ip() && other.broadcast() >= self.broadcast()
@@ -178,6 +467,73 @@ impl Ipv6Network {
         other.is_subnet_of(self)
     }
 
+    /// Returns the `/64` with the given Subnet ID inside this locally assigned Unique Local
+    /// Address `/48`, as laid out in [RFC 4193, section 3.1].
+    ///
+    /// Returns `None` unless this network is a `/48` within `fd00::/8`.
+    ///
+    /// [RFC 4193, section 3.1]: https://www.rfc-editor.org/rfc/rfc4193#section-3.1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let site: Ipv6Network = "fd12:3456:789a::/48".parse().unwrap();
+    /// assert_eq!(site.ula_subnet(1), Some("fd12:3456:789a:1::/64".parse().unwrap()));
+    /// assert_eq!("fc00::/48".parse::<Ipv6Network>().unwrap().ula_subnet(1), None);
+    /// ```
+    pub fn ula_subnet(&self, subnet_id: u16) -> Option<Ipv6Network> {
+        let network = self.network().to_bits();
+        if self.prefix != 48 || network >> 120 != 0xfd {
+            return None;
+        }
+        let addr = Ipv6Addr::from_bits(network | u128::from(subnet_id) << 64);
+        Ipv6Network::new_checked(addr, 64)
+    }
+
+    /// Returns an iterator over the networks enclosing this one, from the one with a prefix one
+    /// shorter up to the whole address space with prefix 0.
+    ///
+    /// The yielded networks have their host bits cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8:1::/48".parse().unwrap();
+    /// let mut supernets = net.supernets();
+    /// assert_eq!(supernets.len(), 48);
+    /// assert_eq!(supernets.next(), Some("2001:db8::/47".parse().unwrap()));
+    /// assert_eq!(supernets.last(), Some("::/0".parse().unwrap()));
+    /// ```
+    pub fn supernets(self) -> Ipv6SupernetIterator {
+        self.supernets_to(0)
+    }
+
+    /// Returns an iterator over the networks enclosing this one, from the one with a prefix one
+    /// shorter up to the one with prefix `stop_prefix`, inclusive.
+    ///
+    /// The iterator is empty if `stop_prefix` is not shorter than the prefix of this network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8:1::/48".parse().unwrap();
+    /// let supernets: Vec<Ipv6Network> = net.supernets_to(46).collect();
+    /// assert_eq!(supernets, ["2001:db8::/47".parse().unwrap(), "2001:db8::/46".parse().unwrap()]);
+    /// ```
+    pub fn supernets_to(self, stop_prefix: u8) -> Ipv6SupernetIterator {
+        Ipv6SupernetIterator {
+            addr: self.addr,
+            prefix: self.prefix,
+            stop_prefix,
+        }
+    }
+
     /// Checks if the given `Ipv6Network` is partly contained in other.
     pub fn overlaps(self, other: Ipv6Network) -> bool {
         other.contains(self.ip())
@@ -186,6 +542,84 @@ impl Ipv6Network {
             || self.contains(other.broadcast())
     }
 
+    /// Classifies how this network relates to `other` in a single comparison.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{Ipv6Network, NetworkRelation};
+    ///
+    /// let small: Ipv6Network = "2001:db8::/48".parse().unwrap();
+    /// let big: Ipv6Network = "2001:db8::/32".parse().unwrap();
+    /// let other: Ipv6Network = "2001:db9::/48".parse().unwrap();
+    /// assert_eq!(small.relation(big), NetworkRelation::SubsetOf);
+    /// assert_eq!(big.relation(small), NetworkRelation::SupersetOf);
+    /// assert_eq!(small.relation(small), NetworkRelation::Equal);
+    /// assert_eq!(small.relation(other), NetworkRelation::Disjoint);
+    /// ```
+    pub fn relation(self, other: Ipv6Network) -> NetworkRelation {
+        NetworkRelation::between(
+            (self.network(), self.broadcast()),
+            (other.network(), other.broadcast()),
+        )
+    }
+
+    /// Splits the addresses of this network and `other` into three parts in a single
+    /// comparison: the networks covering the addresses only in `self`, the network shared by
+    /// both, and the networks covering the addresses only in `other`.
+    ///
+    /// Two CIDR networks are either nested or disjoint, so the shared part is at most a single
+    /// network. All returned networks have their host bits cleared and each list is sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let big: Ipv6Network = "2001:db8::/30".parse().unwrap();
+    /// let small: Ipv6Network = "2001:db9::/32".parse().unwrap();
+    /// let (only_big, shared, only_small) = big.overlap_partition(small);
+    /// assert_eq!(
+    ///     only_big,
+    ///     vec!["2001:db8::/32".parse::<Ipv6Network>().unwrap(), "2001:dba::/31".parse().unwrap()]
+    /// );
+    /// assert_eq!(shared, Some(small));
+    /// assert!(only_small.is_empty());
+    /// ```
+    pub fn overlap_partition(
+        self,
+        other: Ipv6Network,
+    ) -> (Vec<Ipv6Network>, Option<Ipv6Network>, Vec<Ipv6Network>) {
+        let this = self.normalize();
+        let other = other.normalize();
+        match this.relation(other) {
+            NetworkRelation::Equal => (Vec::new(), Some(this), Vec::new()),
+            NetworkRelation::SubsetOf => (Vec::new(), Some(this), other.exclude(this)),
+            NetworkRelation::SupersetOf => (this.exclude(other), Some(other), Vec::new()),
+            _ => (vec![this], None, vec![other]),
+        }
+    }
+
+    /// Returns the networks covering the addresses of this normalized network outside of
+    /// `inner`, a normalized subnet of it.
+    ///
+    /// These are the siblings of `inner` and of each of its supernets up to `self`.
+    fn exclude(self, inner: Ipv6Network) -> Vec<Ipv6Network> {
+        let target = inner.addr.to_bits();
+        let mut parts: Vec<Ipv6Network> = (self.prefix + 1..=inner.prefix)
+            .map(|prefix| {
+                let mask = u128::MAX << (IPV6_BITS - prefix);
+                let bit = 1 << (IPV6_BITS - prefix);
+                Ipv6Network {
+                    addr: Ipv6Addr::from_bits((target & mask) ^ bit),
+                    prefix,
+                }
+            })
+            .collect();
+        parts.sort_unstable();
+        parts
+    }
+
     /// Returns the mask for this `Ipv6Network`.
     /// That means the `prefix` most significant bits will be 1 and the rest 0
     ///
@@ -246,6 +680,23 @@ impl Ipv6Network {
         Ipv6Addr::from_bits(broadcast)
     }
 
+    /// Returns the addresses of the network, from the network address to the last address, as
+    /// a range of integers.
+    ///
+    /// Use [`RangeInclusive::into_inner`] to get the bounds as a tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "::ff00/120".parse().unwrap();
+    /// assert_eq!(net.as_u128_range(), 0xff00..=0xffff);
+    /// ```
+    pub const fn as_u128_range(&self) -> RangeInclusive<u128> {
+        self.network().to_bits()..=self.broadcast().to_bits()
+    }
+
     /// Checks if a given `Ipv6Addr` is in this `Ipv6Network`
     ///
     /// # Examples
@@ -266,6 +717,14 @@ impl Ipv6Network {
         (ip & mask) == net
     }
 
+    /// Checks if a given `Ipv6Addr` is a usable host address in this `Ipv6Network`.
+    ///
+    /// IPv6 has no broadcast address, so this is the same as [`Ipv6Network::contains`]. It
+    /// exists so that code generic over the address family can call it on either type.
+    pub const fn contains_usable(&self, ip: Ipv6Addr) -> bool {
+        self.contains(ip)
+    }
+
     /// Returns number of possible host addresses in this `Ipv6Network`.
     ///
     /// # Examples
@@ -312,6 +771,64 @@ impl Ipv6Network {
             None
         }
     }
+
+    /// Returns the network of the same prefix `n` blocks after this one, or before it if `n` is
+    /// negative.
+    ///
+    /// The address is moved by `n` times the size of the network, so host bits are kept.
+    /// Returns `None` if the result would leave the IPv6 address space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8:0:4::/64".parse().unwrap();
+    /// assert_eq!(net.nth_sibling(3), Some("2001:db8:0:7::/64".parse().unwrap()));
+    /// assert_eq!(net.nth_sibling(-5), Some("2001:db7:ffff:ffff::/64".parse().unwrap()));
+    /// ```
+    pub fn nth_sibling(self, n: i64) -> Option<Ipv6Network> {
+        let addr = self.addr.to_bits();
+        let addr = if n == 0 {
+            addr
+        } else {
+            let block = 1u128.checked_shl(u32::from(IPV6_BITS - self.prefix))?;
+            let offset = u128::from(n.unsigned_abs()).checked_mul(block)?;
+            if n > 0 {
+                addr.checked_add(offset)?
+            } else {
+                addr.checked_sub(offset)?
+            }
+        };
+        Some(Ipv6Network {
+            addr: Ipv6Addr::from_bits(addr),
+            prefix: self.prefix,
+        })
+    }
+
+    /// The length of the longest string `Display` can produce for an `Ipv6Network`: eight
+    /// groups of four hexadecimal digits followed by `/128`.
+    pub const MAX_DISPLAY_LEN: usize = 43;
+
+    /// Writes the CIDR notation of this `Ipv6Network` to `w`, without allocating.
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write!(w, "{}", self)
+    }
+
+    /// Formats this `Ipv6Network` into `buf` and returns the written part, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8::/32".parse().unwrap();
+    /// let mut buf = [0; Ipv6Network::MAX_DISPLAY_LEN];
+    /// assert_eq!(net.to_str_buf(&mut buf), "2001:db8::/32");
+    /// ```
+    pub fn to_str_buf<'a>(&self, buf: &'a mut [u8; Ipv6Network::MAX_DISPLAY_LEN]) -> &'a str {
+        format_into(self, buf)
+    }
 }
 
 /// Creates an `Ipv6Network` from parsing a string in CIDR notation.
@@ -372,8 +889,115 @@ impl Iterator for Ipv6NetworkIterator {
         };
         Some(next.into())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let Some(next) = self.next else {
+            return (0, Some(0));
+        };
+        // The number of addresses left minus one, which fits even for `::/0`.
+        match usize::try_from(self.end - next) {
+            Ok(last) if last < usize::MAX => (last + 1, Some(last + 1)),
+            _ => (usize::MAX, None),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Ipv6Addr> {
+        let next = self.next?;
+        self.next = next.checked_add(n as u128).filter(|&addr| addr <= self.end);
+        self.next()
+    }
+
+    fn count(self) -> usize {
+        match self.size_hint() {
+            (len, Some(_)) => len,
+            _ => panic!("address count overflows usize"),
+        }
+    }
+
+    fn last(self) -> Option<Ipv6Addr> {
+        self.next.map(|_| self.end.into())
+    }
+}
+
+impl DoubleEndedIterator for Ipv6NetworkIterator {
+    fn next_back(&mut self) -> Option<Ipv6Addr> {
+        let next = self.next?;
+        let end = self.end;
+        if end == next {
+            self.next = None;
+        } else {
+            self.end -= 1;
+        }
+        Some(end.into())
+    }
+}
+
+impl FusedIterator for Ipv6NetworkIterator {}
+
+/// An iterator over evenly spaced addresses of an `Ipv6Network`, created by
+/// [`Ipv6Network::iter_step`] and [`Ipv6Network::iter_strata`].
+#[derive(Clone, Debug)]
+pub struct Ipv6StepIterator {
+    next: Option<u128>,
+    end: u128,
+    step: u128,
 }
 
+impl Iterator for Ipv6StepIterator {
+    type Item = Ipv6Addr;
+
+    fn next(&mut self) -> Option<Ipv6Addr> {
+        let next = self.next?;
+        self.next = next
+            .checked_add(self.step)
+            .filter(|&after| self.step != 0 && after <= self.end);
+        Some(Ipv6Addr::from_bits(next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.next {
+            None => Some(0),
+            Some(_) if self.step == 0 => Some(1),
+            Some(next) => ((self.end - next) / self.step).checked_add(1),
+        };
+        match remaining.and_then(|len| usize::try_from(len).ok()) {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for Ipv6StepIterator {}
+
+/// An iterator over the networks enclosing an `Ipv6Network`, created by [`Ipv6Network::supernets`] and
+/// [`Ipv6Network::supernets_to`].
+#[derive(Clone, Debug)]
+pub struct Ipv6SupernetIterator {
+    addr: Ipv6Addr,
+    prefix: u8,
+    stop_prefix: u8,
+}
+
+impl Iterator for Ipv6SupernetIterator {
+    type Item = Ipv6Network;
+
+    fn next(&mut self) -> Option<Ipv6Network> {
+        if self.prefix <= self.stop_prefix {
+            return None;
+        }
+        self.prefix -= 1;
+        let net = Ipv6Network::new_checked(self.addr, self.prefix)?;
+        Ipv6Network::new_checked(net.network(), self.prefix)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.prefix.saturating_sub(self.stop_prefix));
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Ipv6SupernetIterator {}
+
 impl IntoIterator for &'_ Ipv6Network {
     type IntoIter = Ipv6NetworkIterator;
     type Item = Ipv6Addr;
@@ -382,6 +1006,23 @@ impl IntoIterator for &'_ Ipv6Network {
     }
 }
 
+/// The alternate form (`{:#?}`) additionally prints the network, broadcast address, mask and
+/// size derived from the address and prefix.
+impl fmt::Debug for Ipv6Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alternate = f.alternate();
+        let mut s = f.debug_struct("Ipv6Network");
+        s.field("addr", &self.addr).field("prefix", &self.prefix);
+        if alternate {
+            s.field("network", &self.network())
+                .field("broadcast", &self.broadcast())
+                .field("mask", &self.mask())
+                .field("size", &self.size());
+        }
+        s.finish()
+    }
+}
+
 impl fmt::Display for Ipv6Network {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(fmt, "{}/{}", self.ip(), self.prefix())
@@ -389,11 +1030,11 @@ impl fmt::Display for Ipv6Network {
 }
 
 /// Converts a `Ipv6Addr` network mask into a prefix.
-/// If the mask is invalid this will return an `IpNetworkError::InvalidPrefix`.
+/// If the mask is invalid this will return an `IpNetworkError::InvalidNetmask`.
 pub fn ipv6_mask_to_prefix(mask: Ipv6Addr) -> Result<u8, IpNetworkError> {
     match ipv6_mask_to_prefix_checked(mask) {
         Some(prefix) => Ok(prefix),
-        None => Err(IpNetworkError::InvalidPrefix),
+        None => Err(IpNetworkError::InvalidNetmask),
     }
 }
 
@@ -438,6 +1079,121 @@ pub const fn ipv6_mask_to_prefix_checked(mask: Ipv6Addr) -> Option<u8> {
     Some(prefix)
 }
 
+/// Sorts `networks` and merges overlapping and adjacent networks into the minimal list of
+/// `Ipv6Network`s covering the same addresses.
+///
+/// The vector is modified in place and never reallocated. Host bits are cleared from the
+/// resulting networks.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{ipv6_merge_in_place, Ipv6Network};
+///
+/// let mut nets: Vec<Ipv6Network> = ["2001:db8::/33", "2001:db8:8000::/33", "2001:db8::1"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// ipv6_merge_in_place(&mut nets);
+/// assert_eq!(nets, vec!["2001:db8::/32".parse::<Ipv6Network>().unwrap()]);
+/// ```
+pub fn ipv6_merge_in_place(networks: &mut Vec<Ipv6Network>) {
+    merge_in_place(networks)
+}
+
+/// Merges `networks` like [`ipv6_merge_in_place`], and additionally replaces neighboring
+/// networks by their smallest common supernet when the addresses this adds to it are within
+/// `tolerance`.
+///
+/// This trades accuracy for fewer networks, such as when the rules they turn into are scarce.
+/// Merging is greedy, and every resulting network covers at most `tolerance` addresses that
+/// were not in the input.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{ipv6_merge_with_waste, Ipv6Network, WasteTolerance};
+///
+/// let mut nets: Vec<Ipv6Network> = ["2001:db8::/48", "2001:db8:2::/47"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// ipv6_merge_with_waste(&mut nets, WasteTolerance::Addresses(1 << 80));
+/// assert_eq!(nets, vec!["2001:db8::/46".parse::<Ipv6Network>().unwrap()]);
+/// ```
+pub fn ipv6_merge_with_waste(networks: &mut Vec<Ipv6Network>, tolerance: WasteTolerance) {
+    merge_with_waste_in_place(networks, tolerance)
+}
+
+/// Returns the network in `sorted` that contains `ip`, in `O(log n)` time.
+///
+/// `sorted` must be sorted and its networks must not overlap, as left by
+/// [`ipv6_merge_in_place`]. Otherwise the result is unspecified, but it is always either `None`
+/// or a network containing `ip`.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::Ipv6Addr;
+/// use ipnetwork::{ipv6_find_containing, Ipv6Network};
+///
+/// let sorted: Vec<Ipv6Network> = ["2001:db8::/32", "fc00::/7", "fe80::/10"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// let ip: Ipv6Addr = "fd00::1".parse().unwrap();
+/// assert_eq!(ipv6_find_containing(&sorted, ip), Some(&sorted[1]));
+/// assert_eq!(ipv6_find_containing(&sorted, Ipv6Addr::LOCALHOST), None);
+/// ```
+pub fn ipv6_find_containing(sorted: &[Ipv6Network], ip: Ipv6Addr) -> Option<&Ipv6Network> {
+    let after = sorted.partition_point(|net| net.network() <= ip);
+    sorted[..after].last().filter(|net| net.contains(ip))
+}
+
+impl Merge for Ipv6Network {
+    fn normalize(self) -> Self {
+        Ipv6Network {
+            addr: self.network(),
+            prefix: self.prefix,
+        }
+    }
+
+    fn covers(self, other: Self) -> bool {
+        self.prefix <= other.prefix && self.contains(other.addr)
+    }
+
+    fn join(self, other: Self) -> Option<Self> {
+        if self.prefix != other.prefix || self.prefix == 0 || self.addr == other.addr {
+            return None;
+        }
+        let parent = Ipv6Network {
+            addr: self.addr,
+            prefix: self.prefix - 1,
+        };
+        if parent.network() == self.addr && parent.contains(other.addr) {
+            Some(parent)
+        } else {
+            None
+        }
+    }
+
+    fn common_supernet(self, other: Self) -> Option<Self> {
+        let shared = (self.addr.to_bits() ^ other.addr.to_bits()).leading_zeros() as u8;
+        let prefix = self.prefix.min(other.prefix).min(shared);
+        Some(
+            Ipv6Network {
+                addr: self.addr,
+                prefix,
+            }
+            .normalize(),
+        )
+    }
+
+    fn last_offset(self) -> u128 {
+        self.broadcast().to_bits() - self.network().to_bits()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -547,7 +1303,7 @@ mod test {
     fn invalid_v6_mask_to_prefix() {
         let mask = Ipv6Addr::new(0, 0, 0xffff, 0xffff, 0, 0, 0, 0);
         let prefix = ipv6_mask_to_prefix(mask);
-        assert!(prefix.is_err());
+        assert_eq!(prefix, Err(IpNetworkError::InvalidNetmask));
     }
 
     #[test]
@@ -565,7 +1321,10 @@ mod test {
             // Negative test-case.
             let addr = Ipv6Addr::new(0xff01, 0, 0, 0x17, 0, 0, 0, 0x2);
             let mask = Ipv6Addr::new(0, 0, 0xffff, 0xffff, 0, 0, 0, 0);
-            Ipv6Network::with_netmask(addr, mask).unwrap_err();
+            assert_eq!(
+                Ipv6Network::with_netmask(addr, mask),
+                Err(IpNetworkError::InvalidNetmask)
+            );
         }
     }
 
@@ -592,6 +1351,42 @@ mod test {
         assert_eq!(None, iter.next());
     }
 
+    #[test]
+    fn iterator_v6_rev() {
+        let cidr: Ipv6Network = "2001:db8::/126".parse().unwrap();
+        let addrs: Vec<Ipv6Addr> = cidr.iter().rev().collect();
+        let mut expected: Vec<Ipv6Addr> = cidr.iter().collect();
+        expected.reverse();
+        assert_eq!(addrs, expected);
+
+        let all: Ipv6Network = "::/0".parse().unwrap();
+        let mut iter = all.iter();
+        assert_eq!(iter.next_back(), Some(Ipv6Addr::from(u128::MAX)));
+        assert_eq!(iter.next_back(), Some(Ipv6Addr::from(u128::MAX - 1)));
+        assert_eq!(iter.next(), Some(Ipv6Addr::UNSPECIFIED));
+    }
+
+    #[test]
+    fn iterator_v6_nth() {
+        let cidr: Ipv6Network = "2001:db8::/32".parse().unwrap();
+        let mut iter = cidr.iter();
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+        assert_eq!(iter.nth(0x1_0000), "2001:db8::1:0".parse().ok());
+        assert_eq!(
+            iter.clone().last(),
+            "2001:db8:ffff:ffff:ffff:ffff:ffff:ffff".parse().ok()
+        );
+
+        let small: Ipv6Network = "2001:db8::/120".parse().unwrap();
+        let mut iter = small.iter();
+        assert_eq!(iter.nth(254), "2001:db8::fe".parse().ok());
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        assert_eq!(iter.clone().count(), 1);
+        assert_eq!(iter.nth(1), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.count(), 0);
+    }
+
     #[test]
     fn iterator_v6_tiny() {
         let cidr: Ipv6Network = "2001:db8::/128".parse().unwrap();
@@ -792,4 +1587,111 @@ mod test {
         let network: Ipv6Network = "0::/0".parse().unwrap();
         assert_eq!(network.size(), u128::MAX);
     }
+
+    #[test]
+    fn debug_v6() {
+        let net: Ipv6Network = "2001:db8::1/64".parse().unwrap();
+        assert_eq!(
+            format!("{net:?}"),
+            "Ipv6Network { addr: 2001:db8::1, prefix: 64 }"
+        );
+        let alternate = format!("{net:#?}");
+        assert!(alternate.contains("network: 2001:db8::,"));
+        assert!(alternate.contains("broadcast: 2001:db8::ffff:ffff:ffff:ffff,"));
+        assert!(alternate.contains("mask: ffff:ffff:ffff:ffff::,"));
+        assert!(alternate.contains("size: 18446744073709551616,"));
+    }
+
+    #[test]
+    fn host_bits_v6() {
+        for prefix in 0..=128 {
+            let net = Ipv6Network::new(Ipv6Addr::LOCALHOST, prefix).unwrap();
+            assert_eq!(net.host_bits(), 128 - prefix);
+            assert_eq!(net.size_log2(), u32::from(128 - prefix));
+        }
+    }
+
+    #[test]
+    fn link_local_v6() {
+        let net = Ipv6Network::link_local(1);
+        assert_eq!(net.ip(), Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1));
+        assert!(Ipv6Network::LINK_LOCAL.contains(net.ip()));
+
+        // A locally administered MAC address clears the universal/local bit.
+        let net = Ipv6Network::link_local_from_mac([0x02, 0, 0, 0, 0, 0x01]);
+        assert_eq!(
+            net.ip(),
+            Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0xff, 0xfe00, 0x01)
+        );
+        assert_eq!(net.prefix(), 64);
+    }
+
+    #[test]
+    fn overlap_partition_v6() {
+        let all: Ipv6Network = "::/0".parse().unwrap();
+        let host: Ipv6Network = "::1/128".parse().unwrap();
+        let (rest, shared, only_host) = all.overlap_partition(host);
+        assert_eq!(rest.len(), 128);
+        assert_eq!(rest[0], "::/128".parse().unwrap());
+        assert_eq!(rest[127], "8000::/1".parse().unwrap());
+        assert_eq!(shared, Some(host));
+        assert!(only_host.is_empty());
+    }
+
+    #[test]
+    fn to_str_buf_longest_v6() {
+        let longest = "1111:2222:3333:4444:5555:6666:7777:8888/128";
+        let net: Ipv6Network = longest.parse().unwrap();
+        let mut buf = [0; Ipv6Network::MAX_DISPLAY_LEN];
+        assert_eq!(net.to_str_buf(&mut buf), longest);
+
+        let mapped: Ipv6Network = "::ffff:255.255.255.255/128".parse().unwrap();
+        assert_eq!(mapped.to_str_buf(&mut buf), "::ffff:255.255.255.255/128");
+    }
+
+    #[test]
+    fn supernets_v6() {
+        let net: Ipv6Network = "2001:db8::1/128".parse().unwrap();
+        let mut supernets = net.supernets_to(126);
+        assert_eq!(supernets.len(), 2);
+        assert_eq!(supernets.next(), Some("2001:db8::/127".parse().unwrap()));
+        assert_eq!(supernets.next(), Some("2001:db8::/126".parse().unwrap()));
+        assert_eq!(supernets.next(), None);
+        assert_eq!(net.supernets().count(), 128);
+    }
+
+    #[test]
+    fn as_u128_range_bounds() {
+        let all: Ipv6Network = "::/0".parse().unwrap();
+        assert_eq!(all.as_u128_range(), 0..=u128::MAX);
+        let net: Ipv6Network = "2001:db8::1/127".parse().unwrap();
+        let start = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0).to_bits();
+        assert_eq!(net.as_u128_range().into_inner(), (start, start + 1));
+    }
+
+    #[test]
+    fn nth_sibling_v6_bounds() {
+        let all: Ipv6Network = "::/0".parse().unwrap();
+        assert_eq!(all.nth_sibling(-1), None);
+        let half: Ipv6Network = "::/1".parse().unwrap();
+        assert_eq!(half.nth_sibling(1), Some("8000::/1".parse().unwrap()));
+        assert_eq!(half.nth_sibling(2), None);
+        let host: Ipv6Network = "::1/128".parse().unwrap();
+        assert_eq!(
+            host.nth_sibling(i64::MAX).unwrap().ip(),
+            Ipv6Addr::from(1u128 << 63)
+        );
+        assert_eq!(host.nth_sibling(-2), None);
+    }
+
+    #[test]
+    fn iter_step_v6_edges() {
+        let all: Ipv6Network = "::/0".parse().unwrap();
+        let mut iter = all.iter_step(1 << 127);
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.nth(1), Some(Ipv6Addr::from(1u128 << 127)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(all.iter_step(1).size_hint(), (usize::MAX, None));
+        assert_eq!(all.iter_strata(0, u128::MAX).unwrap().count(), 1);
+    }
 }
@@ -8,24 +8,146 @@
     unused_import_braces
 )]
 
-use std::{convert::TryFrom, fmt, net::IpAddr, str::FromStr};
+use crate::format::format_into;
+use crate::merge::{merge_in_place, merge_with_waste_in_place, Merge};
+use std::{
+    convert::TryFrom, fmt, iter::FusedIterator, net::IpAddr, ops::RangeInclusive, str::FromStr,
+};
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_value;
+#[cfg(feature = "bson")]
+mod bson_value;
+mod canonical;
+mod classify;
+#[cfg(feature = "cloud")]
+mod cloud;
+mod cursor;
+mod delegation;
+mod dot;
 mod error;
+#[cfg(feature = "uniffi")]
+mod ffi;
+mod format;
+#[cfg(any(feature = "async-graphql", feature = "juniper"))]
+mod graphql;
+mod info;
+mod interface;
 mod ipv4;
 mod ipv6;
+#[cfg(any(feature = "csv", feature = "jsonl"))]
+mod load;
+mod merge;
+mod netmask;
+mod network_range;
+mod nlri;
+mod ops;
+mod packed;
 mod parse;
+#[cfg(feature = "postgres")]
+mod postgres_value;
+mod prefix;
+mod prefix_list;
+#[cfg(feature = "pyo3")]
+mod py;
+#[cfg(feature = "quickcheck")]
+mod quickcheck_value;
+#[cfg(feature = "rand")]
+mod random;
+mod range;
+#[cfg(feature = "redis")]
+mod redis_value;
+mod relation;
+mod reverse;
+#[cfg(feature = "sea-orm")]
+mod sea_orm_value;
+#[cfg(feature = "serde")]
+pub mod serde_tagged;
+mod set;
 mod size;
+#[cfg(any(feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+mod sqlx_value;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "async")]
+mod stream;
+mod table;
+mod teredo;
+#[cfg(any(feature = "validator", feature = "garde"))]
+mod validate;
+#[cfg(feature = "clap")]
+mod value_parser;
+#[cfg(feature = "wasm")]
+mod wasm;
 
-pub use crate::error::{IpNetworkError, NetworkSizeError};
-pub use crate::ipv4::Ipv4NetworkIterator;
-pub use crate::ipv4::{ipv4_mask_to_prefix, ipv4_mask_to_prefix_checked, Ipv4Network};
-pub use crate::ipv6::Ipv6NetworkIterator;
-pub use crate::ipv6::{ipv6_mask_to_prefix, ipv6_mask_to_prefix_checked, Ipv6Network};
+#[cfg(feature = "bson")]
+pub use crate::bson_value::bson_type_filter;
+pub use crate::canonical::CanonicalIpNetwork;
+pub use crate::classify::Ipv6MulticastScope;
+#[cfg(feature = "cloud")]
+pub use crate::cloud::{
+    load_aws_ip_ranges, load_azure_service_tags, load_gcp_cloud_ranges, CloudRanges, CloudTag,
+};
+pub use crate::cursor::IpNetworkCursor;
+pub use crate::delegation::{delegation_networks, ipv4_networks_from_count};
+pub use crate::dot::write_containment_dot;
+pub use crate::error::{ErrorKind, IpNetworkError, LoadError, NetworkSizeError};
+pub use crate::format::{ipv4_to_binary, ipv6_to_binary, ipv6_to_expanded};
+pub use crate::info::NetworkInfo;
+pub use crate::interface::{IpInterface, Ipv4Interface, Ipv6Interface};
+#[cfg(feature = "rkyv")]
+pub use crate::ipv4::ArchivedIpv4Network;
+pub use crate::ipv4::{
+    ipv4_find_containing, ipv4_mask_to_prefix, ipv4_mask_to_prefix_checked, ipv4_merge_in_place,
+    ipv4_merge_with_waste, Ipv4Network,
+};
+pub use crate::ipv4::{Ipv4NetworkIterator, Ipv4StepIterator, Ipv4SupernetIterator};
+#[cfg(feature = "rkyv")]
+pub use crate::ipv6::ArchivedIpv6Network;
+pub use crate::ipv6::{
+    ipv6_find_containing, ipv6_mask_to_prefix, ipv6_mask_to_prefix_checked, ipv6_merge_in_place,
+    ipv6_merge_with_waste, Ipv6Network,
+};
+pub use crate::ipv6::{Ipv6NetworkIterator, Ipv6StepIterator, Ipv6SupernetIterator};
+#[cfg(feature = "csv")]
+pub use crate::load::load_csv;
+#[cfg(feature = "jsonl")]
+pub use crate::load::load_json_lines;
+pub use crate::merge::WasteTolerance;
+pub use crate::netmask::{Netmask4, Netmask6};
+pub use crate::network_range::{Ipv4NetworkRange, Ipv6NetworkRange};
+pub use crate::packed::{PackedIpv4Network, PackedIpv6Network};
+pub use crate::parse::ParseOptions;
+pub use crate::prefix::{Ipv4Prefix, Ipv6Prefix, PrefixLen};
+pub use crate::prefix_list::{load_prefix_list, write_prefix_list, write_prefix_list_sorted};
+pub use crate::relation::NetworkRelation;
+pub use crate::reverse::ptr_record_name;
+pub use crate::set::{IpNetworkSet, IpNetworkSetAddresses, IpNetworkSetSize};
 pub use crate::size::NetworkSize;
+#[cfg(feature = "async")]
+pub use crate::stream::IpNetworkStream;
+pub use crate::table::{
+    IpNetworkTable, IpNetworkTableEntry, IpNetworkTableIter, IpNetworkTableValuesMut,
+};
+pub use crate::teredo::TeredoAddr;
+#[cfg(feature = "garde")]
+pub use crate::validate::{garde_cidr, garde_cidr_strict};
+#[cfg(feature = "validator")]
+pub use crate::validate::{validate_cidr, validate_cidr_strict};
+#[cfg(feature = "clap")]
+pub use crate::value_parser::{IpFamily, IpNetworkValueParser};
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
 
 /// Represents a generic network range. This type can have two variants:
 /// the v4 and the v6 case.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug, PartialEq, Eq, Hash))
+)]
 pub enum IpNetwork {
     V4(Ipv4Network),
     V6(Ipv6Network),
@@ -37,8 +159,13 @@ impl<'de> serde::Deserialize<'de> for IpNetwork {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = <String>::deserialize(deserializer)?;
-        IpNetwork::from_str(&s).map_err(serde::de::Error::custom)
+        if deserializer.is_human_readable() {
+            let s = <String>::deserialize(deserializer)?;
+            IpNetwork::from_str(&s).map_err(serde::de::Error::custom)
+        } else {
+            let (ip, prefix) = <(IpAddr, u8)>::deserialize(deserializer)?;
+            IpNetwork::new(ip, prefix).map_err(serde::de::Error::custom)
+        }
     }
 }
 
@@ -48,7 +175,35 @@ impl serde::Serialize for IpNetwork {
     where
         S: serde::Serializer,
     {
-        serializer.collect_str(self)
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serde::Serialize::serialize(&(self.ip(), self.prefix()), serializer)
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedIpNetwork {
+    /// Copies the network out of the archive, checking its prefix.
+    ///
+    /// See [`ArchivedIpv4Network::to_network`] and [`ArchivedIpv6Network::to_network`].
+    pub fn to_network(&self) -> Result<IpNetwork, IpNetworkError> {
+        match self {
+            ArchivedIpNetwork::V4(net) => net.to_network().map(IpNetwork::V4),
+            ArchivedIpNetwork::V6(net) => net.to_network().map(IpNetwork::V6),
+        }
+    }
+
+    /// Checks if the archived network contains `ip`, without copying it out of the archive.
+    ///
+    /// Returns `false` if `ip` is of the other address family.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self, ip) {
+            (ArchivedIpNetwork::V4(net), IpAddr::V4(ip)) => net.contains(ip),
+            (ArchivedIpNetwork::V6(net), IpAddr::V6(ip)) => net.contains(ip),
+            _ => false,
+        }
     }
 }
 
@@ -120,6 +275,26 @@ impl schemars::JsonSchema for IpNetwork {
     }
 }
 
+#[cfg(feature = "smoltcp")]
+impl From<IpNetwork> for smoltcp::wire::IpCidr {
+    fn from(net: IpNetwork) -> Self {
+        match net {
+            IpNetwork::V4(net) => smoltcp::wire::IpCidr::Ipv4(net.into()),
+            IpNetwork::V6(net) => smoltcp::wire::IpCidr::Ipv6(net.into()),
+        }
+    }
+}
+
+#[cfg(feature = "smoltcp")]
+impl From<smoltcp::wire::IpCidr> for IpNetwork {
+    fn from(cidr: smoltcp::wire::IpCidr) -> Self {
+        match cidr {
+            smoltcp::wire::IpCidr::Ipv4(cidr) => IpNetwork::V4(cidr.into()),
+            smoltcp::wire::IpCidr::Ipv6(cidr) => IpNetwork::V6(cidr.into()),
+        }
+    }
+}
+
 impl IpNetwork {
     /// Constructs a new `IpNetwork` from a given `IpAddr` and a prefix denoting the
     /// network size. If the prefix is larger than 32 (for IPv4) or 128 (for IPv6), this
@@ -134,7 +309,7 @@ impl IpNetwork {
 
     /// Constructs a new `IpNetwork` from a network address and a network mask.
     ///
-    /// If the netmask is not valid this will return an `IpNetworkError::InvalidPrefix`.
+    /// If the netmask is not valid this will return an `IpNetworkError::InvalidNetmask`.
     pub fn with_netmask(netaddr: IpAddr, netmask: IpAddr) -> Result<Self, IpNetworkError> {
         let prefix = ip_mask_to_prefix(netmask)?;
         Self::new(netaddr, prefix)
@@ -167,6 +342,42 @@ impl IpNetwork {
         }
     }
 
+    /// Returns the number of host bits of the given `IpNetwork`, that is the address width
+    /// (32 or 128) minus the prefix.
+    ///
+    /// # Example
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// assert_eq!("10.9.0.32/16".parse::<IpNetwork>().unwrap().host_bits(), 16);
+    /// assert_eq!("ff01::0/32".parse::<IpNetwork>().unwrap().host_bits(), 96);
+    /// ```
+    pub const fn host_bits(&self) -> u8 {
+        match *self {
+            IpNetwork::V4(ref a) => a.host_bits(),
+            IpNetwork::V6(ref a) => a.host_bits(),
+        }
+    }
+
+    /// Returns the base 2 logarithm of the number of addresses in this `IpNetwork`.
+    ///
+    /// This allows comparing the magnitude of networks without any 128-bit arithmetic.
+    ///
+    /// # Example
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let v4: IpNetwork = "10.0.0.0/8".parse().unwrap();
+    /// let v6: IpNetwork = "2001:db8::/104".parse().unwrap();
+    /// assert_eq!(v4.size_log2(), v6.size_log2());
+    /// ```
+    pub const fn size_log2(&self) -> u32 {
+        match *self {
+            IpNetwork::V4(ref a) => a.size_log2(),
+            IpNetwork::V6(ref a) => a.size_log2(),
+        }
+    }
+
     /// Returns the address of the network denoted by this `IpNetwork`.
     /// This means the lowest possible IP address inside of the network.
     ///
@@ -207,6 +418,26 @@ impl IpNetwork {
         }
     }
 
+    /// Returns the addresses of the network as a range of integers, with IPv4 addresses
+    /// widened to `u128`.
+    ///
+    /// See [`Ipv4Network::as_u32_range`] and [`Ipv6Network::as_u128_range`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let net: IpNetwork = "0.0.1.0/24".parse().unwrap();
+    /// assert_eq!(net.as_u128_range(), 0x100..=0x1ff);
+    /// ```
+    pub const fn as_u128_range(&self) -> RangeInclusive<u128> {
+        match *self {
+            IpNetwork::V4(ref a) => a.network().to_bits() as u128..=a.broadcast().to_bits() as u128,
+            IpNetwork::V6(ref a) => a.as_u128_range(),
+        }
+    }
+
     /// Returns the mask for this `IpNetwork`.
     /// That means the `prefix` most significant bits will be 1 and the rest 0
     ///
@@ -275,6 +506,122 @@ impl IpNetwork {
     // variant conversions. Then use that to implement a generic is_subnet_of
     // is_supernet_of, overlaps
 
+    /// Classifies how this network relates to `other` in a single comparison.
+    ///
+    /// Networks of different address families are always `NetworkRelation::Disjoint`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, NetworkRelation};
+    ///
+    /// let net: IpNetwork = "10.1.2.0/24".parse().unwrap();
+    /// let supernet: IpNetwork = "10.0.0.0/8".parse().unwrap();
+    /// let v6: IpNetwork = "::/0".parse().unwrap();
+    /// assert_eq!(net.relation(supernet), NetworkRelation::SubsetOf);
+    /// assert_eq!(net.relation(v6), NetworkRelation::Disjoint);
+    /// ```
+    pub fn relation(&self, other: IpNetwork) -> NetworkRelation {
+        match (*self, other) {
+            (IpNetwork::V4(a), IpNetwork::V4(b)) => a.relation(b),
+            (IpNetwork::V6(a), IpNetwork::V6(b)) => a.relation(b),
+            _ => NetworkRelation::Disjoint,
+        }
+    }
+
+    /// Returns an iterator over the networks enclosing this one, from the one with a prefix one
+    /// shorter up to the whole address space of its family.
+    ///
+    /// See [`Ipv4Network::supernets`] and [`Ipv6Network::supernets`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let net: IpNetwork = "10.1.2.3/32".parse().unwrap();
+    /// let mut supernets = net.supernets();
+    /// assert_eq!(supernets.next(), Some("10.1.2.2/31".parse().unwrap()));
+    /// assert_eq!(supernets.last(), Some("0.0.0.0/0".parse().unwrap()));
+    /// ```
+    pub fn supernets(&self) -> IpSupernetIterator {
+        self.supernets_to(0)
+    }
+
+    /// Returns an iterator over the networks enclosing this one, from the one with a prefix one
+    /// shorter up to the one with prefix `stop_prefix`, inclusive.
+    ///
+    /// See [`Ipv4Network::supernets_to`] and [`Ipv6Network::supernets_to`].
+    pub fn supernets_to(&self, stop_prefix: u8) -> IpSupernetIterator {
+        let inner = match self {
+            IpNetwork::V4(net) => IpSupernetIteratorInner::V4(net.supernets_to(stop_prefix)),
+            IpNetwork::V6(net) => IpSupernetIteratorInner::V6(net.supernets_to(stop_prefix)),
+        };
+        IpSupernetIterator { inner }
+    }
+
+    /// The length of the longest string `Display` can produce for an `IpNetwork`.
+    pub const MAX_DISPLAY_LEN: usize = Ipv6Network::MAX_DISPLAY_LEN;
+
+    /// Writes the CIDR notation of this `IpNetwork` to `w`, without allocating.
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write!(w, "{}", self)
+    }
+
+    /// Formats this `IpNetwork` into `buf` and returns the written part, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let net: IpNetwork = "10.0.0.0/8".parse().unwrap();
+    /// let mut buf = [0; IpNetwork::MAX_DISPLAY_LEN];
+    /// assert_eq!(net.to_str_buf(&mut buf), "10.0.0.0/8");
+    /// ```
+    pub fn to_str_buf<'a>(&self, buf: &'a mut [u8; IpNetwork::MAX_DISPLAY_LEN]) -> &'a str {
+        format_into(self, buf)
+    }
+
+    /// Splits the addresses of this network and `other` into the networks only in `self`, the
+    /// network shared by both, and the networks only in `other`.
+    ///
+    /// Networks of different families never share addresses. See
+    /// [`Ipv4Network::overlap_partition`] and [`Ipv6Network::overlap_partition`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let a: IpNetwork = "10.0.0.0/24".parse().unwrap();
+    /// let b: IpNetwork = "10.0.0.0/25".parse().unwrap();
+    /// let (only_a, shared, only_b) = a.overlap_partition(b);
+    /// assert_eq!(only_a, vec!["10.0.0.128/25".parse::<IpNetwork>().unwrap()]);
+    /// assert_eq!(shared, Some(b));
+    /// assert!(only_b.is_empty());
+    /// ```
+    pub fn overlap_partition(
+        &self,
+        other: IpNetwork,
+    ) -> (Vec<IpNetwork>, Option<IpNetwork>, Vec<IpNetwork>) {
+        fn widen<T: Into<IpNetwork>>(
+            (left, shared, right): (Vec<T>, Option<T>, Vec<T>),
+        ) -> (Vec<IpNetwork>, Option<IpNetwork>, Vec<IpNetwork>) {
+            (
+                left.into_iter().map(Into::into).collect(),
+                shared.map(Into::into),
+                right.into_iter().map(Into::into).collect(),
+            )
+        }
+
+        match (*self, other) {
+            (IpNetwork::V4(a), IpNetwork::V4(b)) => widen(a.overlap_partition(b)),
+            (IpNetwork::V6(a), IpNetwork::V6(b)) => widen(a.overlap_partition(b)),
+            (a, b) => (vec![a], None, vec![b]),
+        }
+    }
+
     /// Checks if a given `IpAddr` is in this `IpNetwork`
     ///
     /// # Examples
@@ -300,6 +647,29 @@ impl IpNetwork {
         }
     }
 
+    /// Checks if a given `IpAddr` is a usable host address in this `IpNetwork`.
+    ///
+    /// See [`Ipv4Network::contains_usable`] for which IPv4 addresses are excluded. Every
+    /// address contained in an IPv6 network is usable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let net: IpNetwork = "10.0.0.0/30".parse().unwrap();
+    /// assert!(net.contains_usable("10.0.0.2".parse().unwrap()));
+    /// assert!(!net.contains_usable("10.0.0.3".parse().unwrap()));
+    /// assert!(!net.contains_usable("::1".parse().unwrap()));
+    /// ```
+    pub const fn contains_usable(&self, ip: IpAddr) -> bool {
+        match (*self, ip) {
+            (IpNetwork::V4(net), IpAddr::V4(ip)) => net.contains_usable(ip),
+            (IpNetwork::V6(net), IpAddr::V6(ip)) => net.contains_usable(ip),
+            _ => false,
+        }
+    }
+
     /// Returns the number of possible host addresses in this `IpAddr`
     ///
     /// # Examples
@@ -318,6 +688,27 @@ impl IpNetwork {
         }
     }
 
+    /// Returns the network of the same prefix `n` blocks after this one, or before it if `n` is
+    /// negative.
+    ///
+    /// See [`Ipv4Network::nth_sibling`] and [`Ipv6Network::nth_sibling`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// // Tenant 42 gets the 42nd /24 after the first one in 10.128.0.0/9.
+    /// let first: IpNetwork = "10.128.0.0/24".parse().unwrap();
+    /// assert_eq!(first.nth_sibling(42), Some("10.128.42.0/24".parse().unwrap()));
+    /// ```
+    pub fn nth_sibling(&self, n: i64) -> Option<IpNetwork> {
+        match *self {
+            IpNetwork::V4(net) => net.nth_sibling(n).map(IpNetwork::V4),
+            IpNetwork::V6(net) => net.nth_sibling(n).map(IpNetwork::V6),
+        }
+    }
+
     /// Returns an iterator over the addresses contained in the network.
     ///
     /// This lists all the addresses in the network range, in ascending order.
@@ -328,6 +719,35 @@ impl IpNetwork {
         };
         IpNetworkIterator { inner }
     }
+
+    /// Returns an iterator over the addresses in the network, starting at `start` rather than at
+    /// the network address.
+    ///
+    /// Returns `None` if `start` is not contained in the network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::IpAddr;
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let net: IpNetwork = "10.0.0.0/24".parse().unwrap();
+    /// let start: IpAddr = "10.0.0.254".parse().unwrap();
+    /// assert_eq!(net.iter_from(start).unwrap().count(), 2);
+    /// assert!(net.iter_from("::1".parse().unwrap()).is_none());
+    /// ```
+    pub fn iter_from(&self, start: IpAddr) -> Option<IpNetworkIterator> {
+        let inner = match (self, start) {
+            (IpNetwork::V4(net), IpAddr::V4(start)) => {
+                IpNetworkIteratorInner::V4(net.iter_from(start)?)
+            }
+            (IpNetwork::V6(net), IpAddr::V6(start)) => {
+                IpNetworkIteratorInner::V6(net.iter_from(start)?)
+            }
+            _ => return None,
+        };
+        Some(IpNetworkIterator { inner })
+    }
 }
 
 /// Tries to parse the given string into a `IpNetwork`. Will first try to parse
@@ -414,8 +834,47 @@ impl Iterator for IpNetworkIterator {
             IpNetworkIteratorInner::V6(iter) => iter.next().map(IpAddr::V6),
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            IpNetworkIteratorInner::V4(iter) => iter.size_hint(),
+            IpNetworkIteratorInner::V6(iter) => iter.size_hint(),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<IpAddr> {
+        match &mut self.inner {
+            IpNetworkIteratorInner::V4(iter) => iter.nth(n).map(IpAddr::V4),
+            IpNetworkIteratorInner::V6(iter) => iter.nth(n).map(IpAddr::V6),
+        }
+    }
+
+    fn count(self) -> usize {
+        match self.inner {
+            IpNetworkIteratorInner::V4(iter) => iter.count(),
+            IpNetworkIteratorInner::V6(iter) => iter.count(),
+        }
+    }
+
+    fn last(self) -> Option<IpAddr> {
+        match self.inner {
+            IpNetworkIteratorInner::V4(iter) => iter.last().map(IpAddr::V4),
+            IpNetworkIteratorInner::V6(iter) => iter.last().map(IpAddr::V6),
+        }
+    }
+}
+
+impl DoubleEndedIterator for IpNetworkIterator {
+    fn next_back(&mut self) -> Option<IpAddr> {
+        match &mut self.inner {
+            IpNetworkIteratorInner::V4(iter) => iter.next_back().map(IpAddr::V4),
+            IpNetworkIteratorInner::V6(iter) => iter.next_back().map(IpAddr::V6),
+        }
+    }
 }
 
+impl FusedIterator for IpNetworkIterator {}
+
 impl IntoIterator for &'_ IpNetwork {
     type IntoIter = IpNetworkIterator;
     type Item = IpAddr;
@@ -424,8 +883,40 @@ impl IntoIterator for &'_ IpNetwork {
     }
 }
 
+#[derive(Clone, Debug)]
+enum IpSupernetIteratorInner {
+    V4(Ipv4SupernetIterator),
+    V6(Ipv6SupernetIterator),
+}
+
+/// An iterator over the networks enclosing an `IpNetwork`, created by
+/// [`IpNetwork::supernets`] and [`IpNetwork::supernets_to`].
+#[derive(Clone, Debug)]
+pub struct IpSupernetIterator {
+    inner: IpSupernetIteratorInner,
+}
+
+impl Iterator for IpSupernetIterator {
+    type Item = IpNetwork;
+    fn next(&mut self) -> Option<IpNetwork> {
+        match &mut self.inner {
+            IpSupernetIteratorInner::V4(iter) => iter.next().map(IpNetwork::V4),
+            IpSupernetIteratorInner::V6(iter) => iter.next().map(IpNetwork::V6),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            IpSupernetIteratorInner::V4(iter) => iter.size_hint(),
+            IpSupernetIteratorInner::V6(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for IpSupernetIterator {}
+
 /// Converts a `IpAddr` network mask into a prefix.
-/// If the mask is invalid this will return an `IpNetworkError::InvalidPrefix`.
+/// If the mask is invalid this will return an `IpNetworkError::InvalidNetmask`.
 pub fn ip_mask_to_prefix(mask: IpAddr) -> Result<u8, IpNetworkError> {
     match mask {
         IpAddr::V4(mask) => ipv4_mask_to_prefix(mask),
@@ -444,6 +935,235 @@ pub const fn ip_mask_to_prefix_checked(mask: IpAddr) -> Option<u8> {
     }
 }
 
+/// Sorts `networks` and merges overlapping and adjacent networks into the minimal list of
+/// `IpNetwork`s covering the same addresses.
+///
+/// The vector is modified in place and never reallocated. IPv4 networks sort before IPv6
+/// networks and the two families are never merged with each other.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{ip_merge_in_place, IpNetwork};
+///
+/// let mut nets: Vec<IpNetwork> = ["2001:db8::/32", "10.0.1.0/24", "10.0.0.0/24"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// ip_merge_in_place(&mut nets);
+/// assert_eq!(nets[0], "10.0.0.0/23".parse::<IpNetwork>().unwrap());
+/// assert_eq!(nets[1], "2001:db8::/32".parse::<IpNetwork>().unwrap());
+/// ```
+pub fn ip_merge_in_place(networks: &mut Vec<IpNetwork>) {
+    merge_in_place(networks)
+}
+
+/// Returns the minimal sorted list of networks covering the same addresses as `networks`,
+/// merging overlapping and adjacent networks like Python's `collapse_addresses`.
+///
+/// This is [`ip_merge_in_place`] on a copy of `networks`.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{aggregate, IpNetwork};
+///
+/// let blocklist: Vec<IpNetwork> = ["192.0.2.128/25", "192.0.2.0/25", "192.0.2.7/32", "::1/128"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// let rules: Vec<String> = aggregate(&blocklist).iter().map(|n| n.to_string()).collect();
+/// assert_eq!(rules, ["192.0.2.0/24", "::1/128"]);
+/// ```
+pub fn aggregate(networks: &[IpNetwork]) -> Vec<IpNetwork> {
+    let mut networks = networks.to_vec();
+    merge_in_place(&mut networks);
+    networks
+}
+
+/// Merges `networks` like [`ip_merge_in_place`], and additionally replaces neighboring networks
+/// by their smallest common supernet when the addresses this adds to it are within `tolerance`.
+///
+/// IPv4 and IPv6 networks are never merged with each other. See [`ipv4_merge_with_waste`].
+pub fn ip_merge_with_waste(networks: &mut Vec<IpNetwork>, tolerance: WasteTolerance) {
+    merge_with_waste_in_place(networks, tolerance)
+}
+
+/// Returns the network in `sorted` that contains `ip`, in `O(log n)` time.
+///
+/// `sorted` must be sorted and its networks must not overlap, as left by
+/// [`ip_merge_in_place`]. IPv4 and IPv6 networks may be mixed, since IPv4 networks sort first.
+/// See [`ipv4_find_containing`] and [`ipv6_find_containing`].
+///
+/// # Examples
+///
+/// ```
+/// use std::net::IpAddr;
+/// use ipnetwork::{ip_find_containing, ip_merge_in_place, IpNetwork};
+///
+/// let mut nets: Vec<IpNetwork> = ["fe80::/10", "10.0.0.0/8", "10.1.0.0/16"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// ip_merge_in_place(&mut nets);
+/// let ip: IpAddr = "10.1.2.3".parse().unwrap();
+/// assert_eq!(ip_find_containing(&nets, ip), Some(&"10.0.0.0/8".parse().unwrap()));
+/// let ip: IpAddr = "fe80::1".parse().unwrap();
+/// assert_eq!(ip_find_containing(&nets, ip), Some(&"fe80::/10".parse().unwrap()));
+/// ```
+pub fn ip_find_containing(sorted: &[IpNetwork], ip: IpAddr) -> Option<&IpNetwork> {
+    let after = sorted.partition_point(|net| net.network() <= ip);
+    sorted[..after].last().filter(|net| net.contains(ip))
+}
+
+/// Returns the minimal list of networks covering the addresses that are in exactly one of `a`
+/// and `b`.
+///
+/// The inputs do not need to be sorted or merged. The result is sorted, with IPv4 networks
+/// before IPv6 networks.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{ip_symmetric_difference, IpNetwork};
+///
+/// let parse = |nets: &[&str]| -> Vec<IpNetwork> {
+///     nets.iter().map(|n| n.parse().unwrap()).collect()
+/// };
+/// let running = parse(&["10.0.0.0/24", "10.0.2.0/24"]);
+/// let expected = parse(&["10.0.0.0/25", "10.0.2.0/24"]);
+/// assert_eq!(
+///     ip_symmetric_difference(&running, &expected),
+///     parse(&["10.0.0.128/25"])
+/// );
+/// ```
+pub fn ip_symmetric_difference(a: &[IpNetwork], b: &[IpNetwork]) -> Vec<IpNetwork> {
+    range::symmetric_difference(a, b)
+}
+
+/// Returns the minimal list of networks covering the addresses from `first` to `last`, both
+/// inclusive, like Python's `summarize_address_range`.
+///
+/// Returns an `IpNetworkError::InvalidRange` if `first` and `last` are of different families
+/// or `first` comes after `last`.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{summarize_address_range, IpNetwork};
+///
+/// let nets = summarize_address_range(
+///     "192.0.2.5".parse().unwrap(),
+///     "192.0.2.16".parse().unwrap(),
+/// )
+/// .unwrap();
+/// let nets: Vec<String> = nets.iter().map(|n| n.to_string()).collect();
+/// assert_eq!(nets, ["192.0.2.5/32", "192.0.2.6/31", "192.0.2.8/29", "192.0.2.16/32"]);
+///
+/// assert!(summarize_address_range("10.0.0.2".parse().unwrap(), "10.0.0.1".parse().unwrap()).is_err());
+/// ```
+pub fn summarize_address_range(
+    first: IpAddr,
+    last: IpAddr,
+) -> Result<Vec<IpNetwork>, IpNetworkError> {
+    let (bits, start, end) = match (first, last) {
+        (IpAddr::V4(first), IpAddr::V4(last)) => (
+            range::IPV4_BITS,
+            u128::from(first.to_bits()),
+            u128::from(last.to_bits()),
+        ),
+        (IpAddr::V6(first), IpAddr::V6(last)) => {
+            (range::IPV6_BITS, first.to_bits(), last.to_bits())
+        }
+        _ => {
+            return Err(IpNetworkError::InvalidRange(format!(
+                "{first} and {last} are of different address families"
+            )))
+        }
+    };
+    if start > end {
+        return Err(IpNetworkError::InvalidRange(format!(
+            "{first} comes after {last}"
+        )));
+    }
+    let mut networks = Vec::new();
+    range::push_cidrs(bits, start, end, &mut networks);
+    Ok(networks)
+}
+
+/// Returns the number of distinct IPv4 and IPv6 addresses covered by `networks`.
+///
+/// Overlapping networks are only counted once. The inputs do not need to be sorted or merged.
+/// Like [`Ipv4Network::size`] and [`Ipv6Network::size`], the counts saturate at `u32::MAX` and
+/// `u128::MAX` when the whole address space of a family is covered.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{count_unique_addresses, IpNetwork, NetworkSize};
+///
+/// let nets: Vec<IpNetwork> = ["10.0.0.0/24", "10.0.0.128/25", "10.0.1.0/24", "2001:db8::/64"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// let (v4, v6) = count_unique_addresses(&nets);
+/// assert_eq!(v4, NetworkSize::V4(512));
+/// assert_eq!(v6, NetworkSize::V6(1 << 64));
+/// ```
+pub fn count_unique_addresses(networks: &[IpNetwork]) -> (NetworkSize, NetworkSize) {
+    let mut merged = networks.to_vec();
+    merge_in_place(&mut merged);
+    let (mut v4, mut v6) = (0u32, 0u128);
+    for net in merged {
+        let count = net.last_offset().saturating_add(1);
+        match net {
+            IpNetwork::V4(_) => v4 = v4.saturating_add(count.try_into().unwrap_or(u32::MAX)),
+            IpNetwork::V6(_) => v6 = v6.saturating_add(count),
+        }
+    }
+    (NetworkSize::V4(v4), NetworkSize::V6(v6))
+}
+
+impl Merge for IpNetwork {
+    fn normalize(self) -> Self {
+        match self {
+            IpNetwork::V4(a) => IpNetwork::V4(a.normalize()),
+            IpNetwork::V6(a) => IpNetwork::V6(a.normalize()),
+        }
+    }
+
+    fn covers(self, other: Self) -> bool {
+        match (self, other) {
+            (IpNetwork::V4(a), IpNetwork::V4(b)) => a.covers(b),
+            (IpNetwork::V6(a), IpNetwork::V6(b)) => a.covers(b),
+            _ => false,
+        }
+    }
+
+    fn join(self, other: Self) -> Option<Self> {
+        match (self, other) {
+            (IpNetwork::V4(a), IpNetwork::V4(b)) => a.join(b).map(IpNetwork::V4),
+            (IpNetwork::V6(a), IpNetwork::V6(b)) => a.join(b).map(IpNetwork::V6),
+            _ => None,
+        }
+    }
+
+    fn common_supernet(self, other: Self) -> Option<Self> {
+        match (self, other) {
+            (IpNetwork::V4(a), IpNetwork::V4(b)) => a.common_supernet(b).map(IpNetwork::V4),
+            (IpNetwork::V6(a), IpNetwork::V6(b)) => a.common_supernet(b).map(IpNetwork::V6),
+            _ => None,
+        }
+    }
+
+    fn last_offset(self) -> u128 {
+        match self {
+            IpNetwork::V4(a) => a.last_offset(),
+            IpNetwork::V6(a) => a.last_offset(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -456,4 +1176,84 @@ mod test {
         let _deser: IpNetwork = serde_json::from_value(val)
             .expect("Fails to deserialize from json_value::value::Value");
     }
+
+    #[test]
+    #[cfg(feature = "rkyv")]
+    fn rkyv_archived_networks() {
+        use super::*;
+        use rkyv::rancor::Error;
+
+        let nets: Vec<IpNetwork> = vec![
+            "192.0.2.77/24".parse().unwrap(),
+            "2001:db8::/32".parse().unwrap(),
+        ];
+        let bytes = rkyv::to_bytes::<Error>(&nets).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Vec<IpNetwork>>, Error>(&bytes).unwrap();
+        assert!(archived[0].contains("192.0.2.1".parse().unwrap()));
+        assert!(!archived[0].contains("2001:db8::1".parse().unwrap()));
+        assert!(archived[1].contains("2001:db8::1".parse().unwrap()));
+        assert_eq!(archived[0].to_network(), Ok(nets[0]));
+        assert_eq!(
+            rkyv::deserialize::<Vec<IpNetwork>, Error>(archived).unwrap(),
+            nets
+        );
+    }
+
+    #[test]
+    fn overlap_partition_mixed_families() {
+        use super::*;
+        let a: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        let b: IpNetwork = "::/0".parse().unwrap();
+        assert_eq!(a.overlap_partition(b), (vec![a], None, vec![b]));
+    }
+
+    #[test]
+    #[cfg(feature = "smoltcp")]
+    fn smoltcp_round_trip() {
+        use super::*;
+        use smoltcp::wire::{IpCidr, Ipv4Cidr};
+
+        let net: IpNetwork = "10.1.2.3/8".parse().unwrap();
+        let cidr = IpCidr::from(net);
+        assert_eq!(
+            cidr,
+            IpCidr::Ipv4(Ipv4Cidr::new(std::net::Ipv4Addr::new(10, 1, 2, 3), 8))
+        );
+        assert_eq!(IpNetwork::from(cidr), net);
+
+        let net: IpNetwork = "2001:db8::1/64".parse().unwrap();
+        assert_eq!(IpNetwork::from(IpCidr::from(net)), net);
+    }
+
+    #[test]
+    fn count_unique_addresses_saturates() {
+        use super::*;
+        let nets: Vec<IpNetwork> = ["0.0.0.0/1", "128.0.0.0/1", "::/0", "::1/128"]
+            .iter()
+            .map(|n| n.parse().unwrap())
+            .collect();
+        assert_eq!(
+            count_unique_addresses(&nets),
+            (NetworkSize::V4(u32::MAX), NetworkSize::V6(u128::MAX))
+        );
+        assert_eq!(
+            count_unique_addresses(&[]),
+            (NetworkSize::V4(0), NetworkSize::V6(0))
+        );
+    }
+
+    #[test]
+    fn summarize_address_range_edges() {
+        use super::*;
+        let summarize = |first: &str, last: &str| {
+            summarize_address_range(first.parse().unwrap(), last.parse().unwrap())
+        };
+        let all = summarize("::", "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff").unwrap();
+        assert_eq!(all, ["::/0".parse::<IpNetwork>().unwrap()]);
+        let all = summarize("0.0.0.0", "255.255.255.255").unwrap();
+        assert_eq!(all, ["0.0.0.0/0".parse::<IpNetwork>().unwrap()]);
+        let one = summarize("10.0.0.1", "10.0.0.1").unwrap();
+        assert_eq!(one, ["10.0.0.1/32".parse::<IpNetwork>().unwrap()]);
+        assert!(summarize("10.0.0.1", "::1").is_err());
+    }
 }
@@ -0,0 +1,169 @@
+use crate::error::LoadError;
+use crate::IpNetwork;
+use std::io;
+
+/// Reads the networks stored in the column named `column` of a CSV document.
+///
+/// The first record must be a header row. Surrounding whitespace in the values is ignored.
+/// Loading stops at the first bad record and the returned `LoadError` reports its line.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{load_csv, IpNetwork};
+///
+/// let data = "network,geoname_id\n1.0.0.0/24,2077456\n2001:200::/32,1861060\n";
+/// let networks = load_csv(data.as_bytes(), "network").unwrap();
+/// assert_eq!(networks[0], "1.0.0.0/24".parse::<IpNetwork>().unwrap());
+/// assert_eq!(networks[1], "2001:200::/32".parse::<IpNetwork>().unwrap());
+///
+/// let err = load_csv("network\n1.0.0.0/24\nbogus\n".as_bytes(), "network").unwrap_err();
+/// assert_eq!(err.line(), 3);
+/// ```
+#[cfg(feature = "csv")]
+pub fn load_csv<R: io::Read>(reader: R, column: &str) -> Result<Vec<IpNetwork>, LoadError> {
+    let csv_error = |e: csv::Error| LoadError::Record {
+        line: e.position().map_or(0, csv::Position::line),
+        message: e.to_string(),
+    };
+
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let index = reader
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .position(|header| header.trim() == column)
+        .ok_or_else(|| LoadError::MissingField {
+            line: 1,
+            field: column.to_string(),
+        })?;
+
+    let mut networks = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        let line = record.position().map_or(0, csv::Position::line);
+        let value = record.get(index).ok_or_else(|| LoadError::MissingField {
+            line,
+            field: column.to_string(),
+        })?;
+        let network = value
+            .trim()
+            .parse()
+            .map_err(|error| LoadError::InvalidNetwork { line, error })?;
+        networks.push(network);
+    }
+    Ok(networks)
+}
+
+/// Reads the networks stored in the string field named `field` of every object in a JSON
+/// lines document.
+///
+/// Blank lines are skipped. Loading stops at the first bad record and the returned
+/// `LoadError` reports its line.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{load_json_lines, IpNetwork};
+///
+/// let data = r#"{"cidr": "192.0.2.0/24", "tag": "scanner"}
+/// {"cidr": "2001:db8::/32", "tag": "botnet"}
+/// "#;
+/// let networks = load_json_lines(data.as_bytes(), "cidr").unwrap();
+/// assert_eq!(networks[1], "2001:db8::/32".parse::<IpNetwork>().unwrap());
+/// ```
+#[cfg(feature = "jsonl")]
+pub fn load_json_lines<R: io::BufRead>(
+    reader: R,
+    field: &str,
+) -> Result<Vec<IpNetwork>, LoadError> {
+    let mut networks = Vec::new();
+    for (index, text) in reader.lines().enumerate() {
+        let line = index as u64 + 1;
+        let text = text.map_err(|e| LoadError::Record {
+            line,
+            message: e.to_string(),
+        })?;
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let record: serde_json::Value =
+            serde_json::from_str(&text).map_err(|e| LoadError::Record {
+                line,
+                message: e.to_string(),
+            })?;
+        let value = match record.get(field) {
+            Some(serde_json::Value::String(value)) => value,
+            Some(_) => {
+                return Err(LoadError::Record {
+                    line,
+                    message: format!("field {field} is not a string"),
+                })
+            }
+            None => {
+                return Err(LoadError::MissingField {
+                    line,
+                    field: field.to_string(),
+                })
+            }
+        };
+        let network = value
+            .parse()
+            .map_err(|error| LoadError::InvalidNetwork { line, error })?;
+        networks.push(network);
+    }
+    Ok(networks)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "csv")]
+    fn load_csv_errors() {
+        let err = load_csv("net\n10.0.0.0/8\n".as_bytes(), "network").unwrap_err();
+        assert_eq!(
+            err,
+            LoadError::MissingField {
+                line: 1,
+                field: "network".to_string()
+            }
+        );
+
+        let err = load_csv("a,network\n1,10.0.0.0/8\n2\n".as_bytes(), "network").unwrap_err();
+        assert_eq!(err.line(), 3);
+
+        let err = load_csv("network\n10.0.0.0/33\n".as_bytes(), "network").unwrap_err();
+        assert_eq!(
+            err,
+            LoadError::InvalidNetwork {
+                line: 2,
+                error: crate::IpNetworkError::InvalidAddr("10.0.0.0/33".to_string())
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "csv")]
+    fn load_csv_trims_values() {
+        let networks = load_csv("id, network\n1, 10.0.0.0/8 \n".as_bytes(), "network").unwrap();
+        assert_eq!(networks, vec!["10.0.0.0/8".parse().unwrap()]);
+    }
+
+    #[test]
+    #[cfg(feature = "jsonl")]
+    fn load_json_lines_errors() {
+        let data = "{\"cidr\": \"10.0.0.0/8\"}\n\n{\"cidr\": 10}\n";
+        let err = load_json_lines(data.as_bytes(), "cidr").unwrap_err();
+        assert_eq!(err.line(), 3);
+        assert!(matches!(err, LoadError::Record { .. }));
+
+        let err = load_json_lines("{\"net\": \"10.0.0.0/8\"}".as_bytes(), "cidr").unwrap_err();
+        assert!(matches!(err, LoadError::MissingField { line: 1, .. }));
+
+        let err = load_json_lines("not json".as_bytes(), "cidr").unwrap_err();
+        assert!(matches!(err, LoadError::Record { line: 1, .. }));
+    }
+}
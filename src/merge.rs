@@ -0,0 +1,238 @@
+/// The operations needed to merge a list of networks into the minimal equivalent list.
+pub(crate) trait Merge: Copy + Ord {
+    /// Returns the network with all host bits cleared.
+    fn normalize(self) -> Self;
+
+    /// Checks if `other`, a normalized network that sorts after `self`, lies within `self`.
+    fn covers(self, other: Self) -> bool;
+
+    /// Returns the parent network if `self` and `other` are its lower and upper halves.
+    fn join(self, other: Self) -> Option<Self>;
+
+    /// Returns the smallest normalized network containing both `self` and `other`, if they are
+    /// of the same family.
+    fn common_supernet(self, other: Self) -> Option<Self>;
+
+    /// Returns the number of addresses in the network minus one, which fits even for `::/0`.
+    fn last_offset(self) -> u128;
+}
+
+/// How much address space [`ip_merge_with_waste`](crate::ip_merge_with_waste) and its
+/// per-family variants may add to a merged network beyond the addresses of the input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WasteTolerance {
+    /// At most this many addresses not in the input per resulting network.
+    Addresses(u128),
+    /// At most this percentage of the addresses of each resulting network may be absent from
+    /// the input.
+    Percent(f64),
+}
+
+impl WasteTolerance {
+    /// Checks if a network with `last_offset + 1` addresses, `waste` of which are not in the
+    /// input, is acceptable.
+    fn allows(self, waste: u128, last_offset: u128) -> bool {
+        match self {
+            WasteTolerance::Addresses(max) => waste <= max,
+            WasteTolerance::Percent(percent) => {
+                waste as f64 <= percent / 100.0 * (last_offset as f64 + 1.0)
+            }
+        }
+    }
+}
+
+/// Sorts `networks` and merges overlapping and adjacent entries, reusing the allocation.
+///
+/// After sorting, every network is compared against the top of the already merged prefix of
+/// the vector, which behaves like a stack: covered networks are dropped, and whenever the two
+/// topmost entries are siblings they are replaced by their parent.
+pub(crate) fn merge_in_place<T: Merge>(networks: &mut Vec<T>) {
+    for net in networks.iter_mut() {
+        *net = net.normalize();
+    }
+    networks.sort_unstable();
+
+    let mut len = 0;
+    for i in 0..networks.len() {
+        let net = networks[i];
+        if len > 0 && networks[len - 1].covers(net) {
+            continue;
+        }
+        networks[len] = net;
+        len += 1;
+        while len >= 2 {
+            match networks[len - 2].join(networks[len - 1]) {
+                Some(parent) => {
+                    networks[len - 2] = parent;
+                    len -= 1;
+                }
+                None => break,
+            }
+        }
+    }
+    networks.truncate(len);
+}
+
+/// Merges `networks` like [`merge_in_place`], then also replaces runs of neighboring networks
+/// by their smallest common supernet when the addresses this adds are within `tolerance`.
+///
+/// Merging is greedy: each network is only tried against the networks right before it. The
+/// waste of a supernet is counted against the addresses of the original input, so consecutive
+/// merges cannot add up to more than `tolerance` in any resulting network.
+pub(crate) fn merge_with_waste_in_place<T: Merge>(
+    networks: &mut Vec<T>,
+    tolerance: WasteTolerance,
+) {
+    merge_in_place(networks);
+
+    // The number of input addresses within each network of the stack, minus one.
+    let mut used: Vec<u128> = networks.iter().map(|net| net.last_offset()).collect();
+    let mut len = 0;
+    for i in 0..networks.len() {
+        let net = networks[i];
+        if len > 0 && networks[len - 1].covers(net) {
+            used[len - 1] += used[i] + 1;
+            continue;
+        }
+        networks[len] = net;
+        used[len] = used[i];
+        len += 1;
+        while len >= 2 {
+            let Some(parent) = networks[len - 2].common_supernet(networks[len - 1]) else {
+                break;
+            };
+            // The stack is sorted and its networks are disjoint, so the ones within `parent`
+            // are a suffix of it.
+            let mut first = len - 2;
+            while first > 0 && parent.covers(networks[first - 1]) {
+                first -= 1;
+            }
+            let inside = used[first..len].iter().sum::<u128>() + (len - first - 1) as u128;
+            let waste = parent.last_offset() - inside;
+            if !tolerance.allows(waste, parent.last_offset()) {
+                break;
+            }
+            networks[first] = parent;
+            used[first] = inside;
+            len = first + 1;
+        }
+    }
+    networks.truncate(len);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::WasteTolerance;
+    use crate::{ip_merge_in_place, ipv4_merge_in_place, ipv6_merge_in_place};
+    use crate::{ip_merge_with_waste, ipv4_merge_with_waste, ipv6_merge_with_waste};
+    use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+
+    fn v4(nets: &[&str]) -> Vec<Ipv4Network> {
+        nets.iter().map(|n| n.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn merge_siblings_recursively() {
+        let mut nets = v4(&["10.0.0.128/25", "10.0.1.0/24", "10.0.0.0/25", "10.0.2.0/23"]);
+        ipv4_merge_in_place(&mut nets);
+        assert_eq!(nets, v4(&["10.0.0.0/22"]));
+    }
+
+    #[test]
+    fn merge_drops_covered_and_duplicates() {
+        let mut nets = v4(&["10.0.0.7/24", "10.0.0.0/24", "10.0.0.64/26", "10.0.0.1"]);
+        ipv4_merge_in_place(&mut nets);
+        assert_eq!(nets, v4(&["10.0.0.0/24"]));
+    }
+
+    #[test]
+    fn merge_keeps_non_siblings_apart() {
+        // Adjacent, but 10.0.1.0/24 and 10.0.2.0/24 do not share a /23.
+        let mut nets = v4(&["10.0.2.0/24", "10.0.1.0/24", "192.168.0.0/16"]);
+        ipv4_merge_in_place(&mut nets);
+        assert_eq!(nets, v4(&["10.0.1.0/24", "10.0.2.0/24", "192.168.0.0/16"]));
+    }
+
+    #[test]
+    fn merge_whole_space() {
+        let mut nets = v4(&["128.0.0.0/1", "0.0.0.0/1"]);
+        ipv4_merge_in_place(&mut nets);
+        assert_eq!(nets, v4(&["0.0.0.0/0"]));
+
+        let mut nets: Vec<Ipv6Network> = vec!["::/1".parse().unwrap(), "8000::/1".parse().unwrap()];
+        ipv6_merge_in_place(&mut nets);
+        assert_eq!(nets, vec!["::/0".parse().unwrap()]);
+    }
+
+    #[test]
+    fn merge_mixed_families() {
+        let mut nets: Vec<IpNetwork> = [
+            "2001:db8::/33",
+            "10.0.0.0/9",
+            "2001:db8:8000::/33",
+            "10.128.0.0/9",
+        ]
+        .iter()
+        .map(|n| n.parse().unwrap())
+        .collect();
+        let capacity = nets.capacity();
+        ip_merge_in_place(&mut nets);
+        let expected: Vec<IpNetwork> = vec![
+            "10.0.0.0/8".parse().unwrap(),
+            "2001:db8::/32".parse().unwrap(),
+        ];
+        assert_eq!(nets, expected);
+        assert_eq!(nets.capacity(), capacity);
+    }
+
+    #[test]
+    fn merge_with_waste_absolute() {
+        // 10.0.2.0/23 lacks the 128 addresses after 10.0.3.0/25, and 10.0.0.0/22 additionally
+        // lacks the 256 addresses of 10.0.1.0/24.
+        let nets = v4(&["10.0.0.0/24", "10.0.2.0/24", "10.0.3.0/25"]);
+        let mut merged = nets.clone();
+        ipv4_merge_with_waste(&mut merged, WasteTolerance::Addresses(127));
+        assert_eq!(merged, nets);
+
+        let mut merged = nets.clone();
+        ipv4_merge_with_waste(&mut merged, WasteTolerance::Addresses(383));
+        assert_eq!(merged, v4(&["10.0.0.0/24", "10.0.2.0/23"]));
+
+        let mut merged = nets;
+        ipv4_merge_with_waste(&mut merged, WasteTolerance::Addresses(384));
+        assert_eq!(merged, v4(&["10.0.0.0/22"]));
+    }
+
+    #[test]
+    fn merge_with_waste_does_not_accumulate() {
+        // Each /30 lacks a single address, so the /29 lacks two.
+        let nets = v4(&["10.0.0.0/31", "10.0.0.3/32", "10.0.0.4/31", "10.0.0.7/32"]);
+        let mut merged = nets.clone();
+        ipv4_merge_with_waste(&mut merged, WasteTolerance::Addresses(1));
+        assert_eq!(merged, v4(&["10.0.0.0/30", "10.0.0.4/30"]));
+
+        let mut merged = nets;
+        ipv4_merge_with_waste(&mut merged, WasteTolerance::Percent(25.0));
+        assert_eq!(merged, v4(&["10.0.0.0/29"]));
+    }
+
+    #[test]
+    fn merge_with_waste_whole_space() {
+        let mut nets: Vec<Ipv6Network> = vec!["::/1".parse().unwrap(), "c000::/2".parse().unwrap()];
+        ipv6_merge_with_waste(&mut nets, WasteTolerance::Percent(25.0));
+        assert_eq!(nets, vec!["::/0".parse().unwrap()]);
+
+        let mut nets: Vec<IpNetwork> = vec![
+            "0.0.0.0/1".parse().unwrap(),
+            "::/1".parse().unwrap(),
+            "c000::/2".parse().unwrap(),
+        ];
+        ip_merge_with_waste(&mut nets, WasteTolerance::Percent(24.9));
+        assert_eq!(nets.len(), 3);
+        ip_merge_with_waste(&mut nets, WasteTolerance::Addresses(u128::MAX));
+        assert_eq!(
+            nets,
+            vec!["0.0.0.0/1".parse().unwrap(), "::/0".parse().unwrap()]
+        );
+    }
+}
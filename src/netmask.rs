@@ -0,0 +1,259 @@
+use crate::error::IpNetworkError;
+use crate::ipv4::{ipv4_mask_to_prefix_checked, Ipv4Network};
+use crate::ipv6::{ipv6_mask_to_prefix_checked, Ipv6Network};
+use crate::prefix::{Ipv4Prefix, Ipv6Prefix};
+use std::{
+    convert::TryFrom,
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+/// An IPv4 network mask that is known to be contiguous, such as `255.255.255.0`.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::Ipv4Addr;
+/// use ipnetwork::{Ipv4Network, Netmask4};
+///
+/// let mask: Netmask4 = "255.255.255.0".parse().unwrap();
+/// assert_eq!(mask.prefix().get(), 24);
+///
+/// let net = Ipv4Network::with_validated_netmask(Ipv4Addr::new(10, 1, 2, 0), mask);
+/// assert_eq!(net.prefix(), 24);
+///
+/// assert!(Netmask4::new(Ipv4Addr::new(255, 0, 255, 0)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Netmask4(Ipv4Addr);
+
+/// An IPv6 network mask that is known to be contiguous, such as `ffff:ffff::`.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{Ipv6Prefix, Netmask6};
+///
+/// let mask: Netmask6 = "ffff:ffff::".parse().unwrap();
+/// assert_eq!(mask.prefix().get(), 32);
+/// assert_eq!(Netmask6::from_prefix(Ipv6Prefix::new(32).unwrap()), mask);
+///
+/// assert!("ffff::ffff".parse::<Netmask6>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Netmask6(Ipv6Addr);
+
+impl Netmask4 {
+    /// Constructs a new `Netmask4` from an `Ipv4Addr`.
+    ///
+    /// If the mask is not contiguous this will return an `IpNetworkError::InvalidNetmask`.
+    pub const fn new(mask: Ipv4Addr) -> Result<Self, IpNetworkError> {
+        match Self::new_checked(mask) {
+            Some(a) => Ok(a),
+            None => Err(IpNetworkError::InvalidNetmask),
+        }
+    }
+
+    /// Constructs a new `Netmask4` from an `Ipv4Addr`.
+    ///
+    /// If the mask is not contiguous this will return `None`. This is useful in const contexts
+    /// where [`Option::unwrap`] may be called to trigger a compile-time error if the mask is
+    /// invalid.
+    pub const fn new_checked(mask: Ipv4Addr) -> Option<Self> {
+        match ipv4_mask_to_prefix_checked(mask) {
+            Some(_) => Some(Netmask4(mask)),
+            None => None,
+        }
+    }
+
+    /// Constructs the `Netmask4` with the `prefix` most significant bits set.
+    pub const fn from_prefix(prefix: Ipv4Prefix) -> Self {
+        Netmask4(Ipv4Network::with_prefix(Ipv4Addr::UNSPECIFIED, prefix).mask())
+    }
+
+    /// Returns the number of leading bits set in this mask.
+    pub const fn prefix(self) -> Ipv4Prefix {
+        match ipv4_mask_to_prefix_checked(self.0) {
+            Some(prefix) => match Ipv4Prefix::new_checked(prefix) {
+                Some(prefix) => prefix,
+                None => unreachable!(),
+            },
+            None => unreachable!(),
+        }
+    }
+
+    /// Returns the mask as an `Ipv4Addr`.
+    pub const fn addr(self) -> Ipv4Addr {
+        self.0
+    }
+}
+
+impl Netmask6 {
+    /// Constructs a new `Netmask6` from an `Ipv6Addr`.
+    ///
+    /// If the mask is not contiguous this will return an `IpNetworkError::InvalidNetmask`.
+    pub const fn new(mask: Ipv6Addr) -> Result<Self, IpNetworkError> {
+        match Self::new_checked(mask) {
+            Some(a) => Ok(a),
+            None => Err(IpNetworkError::InvalidNetmask),
+        }
+    }
+
+    /// Constructs a new `Netmask6` from an `Ipv6Addr`.
+    ///
+    /// If the mask is not contiguous this will return `None`. This is useful in const contexts
+    /// where [`Option::unwrap`] may be called to trigger a compile-time error if the mask is
+    /// invalid.
+    pub const fn new_checked(mask: Ipv6Addr) -> Option<Self> {
+        match ipv6_mask_to_prefix_checked(mask) {
+            Some(_) => Some(Netmask6(mask)),
+            None => None,
+        }
+    }
+
+    /// Constructs the `Netmask6` with the `prefix` most significant bits set.
+    pub const fn from_prefix(prefix: Ipv6Prefix) -> Self {
+        Netmask6(Ipv6Network::with_prefix(Ipv6Addr::UNSPECIFIED, prefix).mask())
+    }
+
+    /// Returns the number of leading bits set in this mask.
+    pub const fn prefix(self) -> Ipv6Prefix {
+        match ipv6_mask_to_prefix_checked(self.0) {
+            Some(prefix) => match Ipv6Prefix::new_checked(prefix) {
+                Some(prefix) => prefix,
+                None => unreachable!(),
+            },
+            None => unreachable!(),
+        }
+    }
+
+    /// Returns the mask as an `Ipv6Addr`.
+    pub const fn addr(self) -> Ipv6Addr {
+        self.0
+    }
+}
+
+impl fmt::Display for Netmask4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Netmask4 {
+    type Err = IpNetworkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Netmask4::new(Ipv4Addr::from_str(s)?)
+    }
+}
+
+impl TryFrom<Ipv4Addr> for Netmask4 {
+    type Error = IpNetworkError;
+
+    fn try_from(mask: Ipv4Addr) -> Result<Self, Self::Error> {
+        Netmask4::new(mask)
+    }
+}
+
+impl From<Netmask4> for Ipv4Addr {
+    fn from(mask: Netmask4) -> Ipv4Addr {
+        mask.0
+    }
+}
+
+impl From<Ipv4Prefix> for Netmask4 {
+    fn from(prefix: Ipv4Prefix) -> Netmask4 {
+        Netmask4::from_prefix(prefix)
+    }
+}
+
+impl From<Netmask4> for Ipv4Prefix {
+    fn from(mask: Netmask4) -> Ipv4Prefix {
+        mask.prefix()
+    }
+}
+
+impl fmt::Display for Netmask6 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Netmask6 {
+    type Err = IpNetworkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Netmask6::new(Ipv6Addr::from_str(s)?)
+    }
+}
+
+impl TryFrom<Ipv6Addr> for Netmask6 {
+    type Error = IpNetworkError;
+
+    fn try_from(mask: Ipv6Addr) -> Result<Self, Self::Error> {
+        Netmask6::new(mask)
+    }
+}
+
+impl From<Netmask6> for Ipv6Addr {
+    fn from(mask: Netmask6) -> Ipv6Addr {
+        mask.0
+    }
+}
+
+impl From<Ipv6Prefix> for Netmask6 {
+    fn from(prefix: Ipv6Prefix) -> Netmask6 {
+        Netmask6::from_prefix(prefix)
+    }
+}
+
+impl From<Netmask6> for Ipv6Prefix {
+    fn from(mask: Netmask6) -> Ipv6Prefix {
+        mask.prefix()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn netmask4_prefix_round_trip() {
+        for prefix in 0..=32 {
+            let prefix = Ipv4Prefix::new(prefix).unwrap();
+            let mask = Netmask4::from_prefix(prefix);
+            assert_eq!(mask.prefix(), prefix);
+            assert_eq!(Netmask4::new(mask.addr()), Ok(mask));
+        }
+    }
+
+    #[test]
+    fn netmask6_prefix_round_trip() {
+        for prefix in 0..=128 {
+            let prefix = Ipv6Prefix::new(prefix).unwrap();
+            let mask = Netmask6::from_prefix(prefix);
+            assert_eq!(mask.prefix(), prefix);
+            assert_eq!(Netmask6::new(mask.addr()), Ok(mask));
+        }
+    }
+
+    #[test]
+    fn invalid_netmask() {
+        assert_eq!(
+            Netmask4::try_from(Ipv4Addr::new(255, 255, 0, 255)),
+            Err(IpNetworkError::InvalidNetmask)
+        );
+        assert_eq!(
+            "ffff:0:ffff::".parse::<Netmask6>(),
+            Err(IpNetworkError::InvalidNetmask)
+        );
+        assert!("255.255.0".parse::<Netmask4>().is_err());
+    }
+
+    #[test]
+    fn display_netmask() {
+        let mask = Netmask4::from_prefix(Ipv4Prefix::new(20).unwrap());
+        assert_eq!(mask.to_string(), "255.255.240.0");
+        let mask = Netmask6::from_prefix(Ipv6Prefix::new(20).unwrap());
+        assert_eq!(mask.to_string(), "ffff:f000::");
+    }
+}
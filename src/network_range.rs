@@ -0,0 +1,363 @@
+//! Iterators over consecutive networks of the same prefix, built from ranges of networks.
+//!
+//! The unstable `Step` trait is what would make `net_a..net_b` iterate directly. Until it is
+//! stable, convert the range with `TryFrom`, which also checks that both ends have the same
+//! prefix.
+
+use crate::error::IpNetworkError;
+use crate::range::{IPV4_BITS, IPV6_BITS};
+use crate::{Ipv4Network, Ipv6Network};
+use std::convert::TryFrom;
+use std::iter::FusedIterator;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::ops::{Range, RangeInclusive};
+
+fn prefix_mismatch(start: u8, end: u8) -> IpNetworkError {
+    IpNetworkError::InvalidRange(format!(
+        "the ends of a network range must have the same prefix, found /{start} and /{end}"
+    ))
+}
+
+/// An iterator over the consecutive `Ipv4Network`s of one prefix between two networks.
+///
+/// Both ends are taken with their host bits cleared. The iterator is empty if the start comes
+/// after the end, like an empty integer range.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use ipnetwork::{Ipv4Network, Ipv4NetworkRange};
+///
+/// let first: Ipv4Network = "10.0.0.0/24".parse().unwrap();
+/// let last: Ipv4Network = "10.0.255.0/24".parse().unwrap();
+/// let range = Ipv4NetworkRange::try_from(first..=last).unwrap();
+/// assert_eq!(range.clone().count(), 256);
+/// assert_eq!(range.clone().nth(7), Some("10.0.7.0/24".parse().unwrap()));
+/// assert_eq!(range.rev().next(), Some(last));
+///
+/// let mixed = "10.1.0.0/16".parse::<Ipv4Network>().unwrap();
+/// assert!(Ipv4NetworkRange::try_from(first..mixed).is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Ipv4NetworkRange {
+    front: u32,
+    back: u32,
+    prefix: u8,
+    exhausted: bool,
+}
+
+impl Ipv4NetworkRange {
+    /// Constructs the range of networks from `start` to `end`, both inclusive.
+    ///
+    /// Returns an `IpNetworkError::InvalidRange` if the prefixes of `start` and `end` differ.
+    pub fn new(start: Ipv4Network, end: Ipv4Network) -> Result<Self, IpNetworkError> {
+        if start.prefix() != end.prefix() {
+            return Err(prefix_mismatch(start.prefix(), end.prefix()));
+        }
+        let front = start.network().to_bits();
+        let back = end.network().to_bits();
+        Ok(Ipv4NetworkRange {
+            front,
+            back,
+            prefix: start.prefix(),
+            exhausted: front > back,
+        })
+    }
+
+    fn host_bits(&self) -> u32 {
+        u32::from(IPV4_BITS - self.prefix)
+    }
+
+    fn network(&self, addr: u32) -> Ipv4Network {
+        Ipv4Network::new_checked(Ipv4Addr::from_bits(addr), self.prefix).unwrap()
+    }
+
+    /// Returns the number of networks left, which only exceeds `u32::MAX` for the full range
+    /// of `/32`s.
+    fn remaining(&self) -> u64 {
+        if self.exhausted {
+            return 0;
+        }
+        u64::from(
+            (self.back - self.front)
+                .checked_shr(self.host_bits())
+                .unwrap_or(0),
+        ) + 1
+    }
+}
+
+impl Iterator for Ipv4NetworkRange {
+    type Item = Ipv4Network;
+
+    fn next(&mut self) -> Option<Ipv4Network> {
+        if self.exhausted {
+            return None;
+        }
+        let net = self.network(self.front);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.front += 1 << self.host_bits();
+        }
+        Some(net)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Ipv4Network> {
+        if u64::try_from(n).map_or(true, |n| n >= self.remaining()) {
+            self.exhausted = true;
+            return None;
+        }
+        // `n` is below the number of networks left, so the offset stays within the range.
+        self.front += (n as u32).checked_shl(self.host_bits()).unwrap_or(0);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for Ipv4NetworkRange {
+    fn next_back(&mut self) -> Option<Ipv4Network> {
+        if self.exhausted {
+            return None;
+        }
+        let net = self.network(self.back);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.back -= 1 << self.host_bits();
+        }
+        Some(net)
+    }
+}
+
+/// The full range of `/32`s yields 2<sup>32</sup> networks, which only fits in a `usize` on
+/// 64-bit targets.
+#[cfg(target_pointer_width = "64")]
+impl ExactSizeIterator for Ipv4NetworkRange {}
+
+impl FusedIterator for Ipv4NetworkRange {}
+
+impl TryFrom<RangeInclusive<Ipv4Network>> for Ipv4NetworkRange {
+    type Error = IpNetworkError;
+
+    fn try_from(range: RangeInclusive<Ipv4Network>) -> Result<Self, Self::Error> {
+        let (start, end) = range.into_inner();
+        Ipv4NetworkRange::new(start, end)
+    }
+}
+
+impl TryFrom<Range<Ipv4Network>> for Ipv4NetworkRange {
+    type Error = IpNetworkError;
+
+    /// Converts the range of networks from `start` up to, but not including, `end`.
+    fn try_from(range: Range<Ipv4Network>) -> Result<Self, Self::Error> {
+        let mut networks = Ipv4NetworkRange::new(range.start, range.end)?;
+        networks.next_back();
+        Ok(networks)
+    }
+}
+
+/// An iterator over the consecutive `Ipv6Network`s of one prefix between two networks.
+///
+/// See [`Ipv4NetworkRange`].
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use ipnetwork::{Ipv6Network, Ipv6NetworkRange};
+///
+/// let first: Ipv6Network = "2001:db8::/64".parse().unwrap();
+/// let end: Ipv6Network = "2001:db8:0:4::/64".parse().unwrap();
+/// let subnets: Vec<Ipv6Network> = Ipv6NetworkRange::try_from(first..end).unwrap().collect();
+/// assert_eq!(subnets.len(), 4);
+/// assert_eq!(subnets[3], "2001:db8:0:3::/64".parse().unwrap());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Ipv6NetworkRange {
+    front: u128,
+    back: u128,
+    prefix: u8,
+    exhausted: bool,
+}
+
+impl Ipv6NetworkRange {
+    /// Constructs the range of networks from `start` to `end`, both inclusive.
+    ///
+    /// Returns an `IpNetworkError::InvalidRange` if the prefixes of `start` and `end` differ.
+    pub fn new(start: Ipv6Network, end: Ipv6Network) -> Result<Self, IpNetworkError> {
+        if start.prefix() != end.prefix() {
+            return Err(prefix_mismatch(start.prefix(), end.prefix()));
+        }
+        let front = start.network().to_bits();
+        let back = end.network().to_bits();
+        Ok(Ipv6NetworkRange {
+            front,
+            back,
+            prefix: start.prefix(),
+            exhausted: front > back,
+        })
+    }
+
+    fn host_bits(&self) -> u32 {
+        u32::from(IPV6_BITS - self.prefix)
+    }
+
+    fn network(&self, addr: u128) -> Ipv6Network {
+        Ipv6Network::new_checked(Ipv6Addr::from_bits(addr), self.prefix).unwrap()
+    }
+
+    /// Returns the number of networks left minus one, which fits even for the full range of
+    /// `/128`s.
+    fn last_index(&self) -> u128 {
+        (self.back - self.front)
+            .checked_shr(self.host_bits())
+            .unwrap_or(0)
+    }
+}
+
+impl Iterator for Ipv6NetworkRange {
+    type Item = Ipv6Network;
+
+    fn next(&mut self) -> Option<Ipv6Network> {
+        if self.exhausted {
+            return None;
+        }
+        let net = self.network(self.front);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.front += 1 << self.host_bits();
+        }
+        Some(net)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Ipv6Network> {
+        if self.exhausted || n as u128 > self.last_index() {
+            self.exhausted = true;
+            return None;
+        }
+        // `n` is at most the last index, so the offset stays within the range.
+        self.front += (n as u128).checked_shl(self.host_bits()).unwrap_or(0);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.exhausted {
+            return (0, Some(0));
+        }
+        match usize::try_from(self.last_index()) {
+            Ok(last) if last < usize::MAX => (last + 1, Some(last + 1)),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for Ipv6NetworkRange {
+    fn next_back(&mut self) -> Option<Ipv6Network> {
+        if self.exhausted {
+            return None;
+        }
+        let net = self.network(self.back);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.back -= 1 << self.host_bits();
+        }
+        Some(net)
+    }
+}
+
+impl FusedIterator for Ipv6NetworkRange {}
+
+impl TryFrom<RangeInclusive<Ipv6Network>> for Ipv6NetworkRange {
+    type Error = IpNetworkError;
+
+    fn try_from(range: RangeInclusive<Ipv6Network>) -> Result<Self, Self::Error> {
+        let (start, end) = range.into_inner();
+        Ipv6NetworkRange::new(start, end)
+    }
+}
+
+impl TryFrom<Range<Ipv6Network>> for Ipv6NetworkRange {
+    type Error = IpNetworkError;
+
+    /// Converts the range of networks from `start` up to, but not including, `end`.
+    fn try_from(range: Range<Ipv6Network>) -> Result<Self, Self::Error> {
+        let mut networks = Ipv6NetworkRange::new(range.start, range.end)?;
+        networks.next_back();
+        Ok(networks)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::iter_nth_zero)]
+    fn ipv4_network_range_edges() {
+        let all = |prefix| {
+            let first = Ipv4Network::new(Ipv4Addr::UNSPECIFIED, prefix).unwrap();
+            let last = Ipv4Network::new(Ipv4Addr::BROADCAST, prefix).unwrap();
+            Ipv4NetworkRange::new(first, last).unwrap()
+        };
+        assert_eq!(all(0).collect::<Vec<_>>(), ["0.0.0.0/0".parse().unwrap()]);
+        assert_eq!(all(0).nth(0), Some("0.0.0.0/0".parse().unwrap()));
+        assert_eq!(all(0).nth(1), None);
+        assert_eq!(all(1).rev().count(), 2);
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(all(32).len(), 1 << 32);
+        let mut hosts = all(32);
+        assert_eq!(
+            hosts.nth(0xffff_fffe),
+            Some("255.255.255.254/32".parse().unwrap())
+        );
+        assert_eq!(
+            hosts.next_back(),
+            Some("255.255.255.255/32".parse().unwrap())
+        );
+        assert_eq!(hosts.next(), None);
+
+        let a: Ipv4Network = "10.0.3.7/24".parse().unwrap();
+        let b: Ipv4Network = "10.0.1.0/24".parse().unwrap();
+        assert_eq!(Ipv4NetworkRange::try_from(a..=b).unwrap().count(), 0);
+        assert_eq!(Ipv4NetworkRange::try_from(b..b).unwrap().count(), 0);
+        assert_eq!(Ipv4NetworkRange::try_from(b..a).unwrap().count(), 2);
+        let mut range = Ipv4NetworkRange::try_from(b..=a).unwrap();
+        assert_eq!(range.nth(3), None);
+        assert_eq!(range.next_back(), None);
+    }
+
+    #[test]
+    #[allow(clippy::iter_nth_zero)]
+    fn ipv6_network_range_edges() {
+        let first: Ipv6Network = "::/128".parse().unwrap();
+        let last: Ipv6Network = "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128"
+            .parse()
+            .unwrap();
+        let mut hosts = Ipv6NetworkRange::new(first, last).unwrap();
+        assert_eq!(hosts.size_hint(), (usize::MAX, None));
+        assert_eq!(hosts.next_back(), Some(last));
+        assert_eq!(
+            hosts.nth(usize::MAX).unwrap().ip().to_bits(),
+            usize::MAX as u128
+        );
+
+        let all: Ipv6Network = "::/0".parse().unwrap();
+        let mut whole = Ipv6NetworkRange::try_from(all..=all).unwrap();
+        assert_eq!(whole.size_hint(), (1, Some(1)));
+        assert_eq!(whole.next(), Some(all));
+        assert_eq!(whole.next(), None);
+        let mut whole = Ipv6NetworkRange::new(all, all).unwrap();
+        assert_eq!(whole.nth(0), Some(all));
+        assert_eq!(whole.nth(0), None);
+        assert!(Ipv6NetworkRange::try_from(all..first).is_err());
+    }
+}
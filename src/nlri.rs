@@ -0,0 +1,168 @@
+//! The variable-length prefix encoding of BGP UPDATE messages, as used for NLRI and withdrawn
+//! routes in [RFC 4271, section 4.3] and for multiprotocol NLRI in [RFC 4760].
+//!
+//! Each prefix is one length byte followed by the fewest address bytes that hold the prefix
+//! bits. Trailing bits past the prefix are written as zero and ignored when read.
+//!
+//! [RFC 4271, section 4.3]: https://www.rfc-editor.org/rfc/rfc4271#section-4.3
+//! [RFC 4760]: https://www.rfc-editor.org/rfc/rfc4760
+
+use crate::error::IpNetworkError;
+use crate::{Ipv4Network, Ipv6Network};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Reads one encoded prefix of at most `N * 8` bits from the start of `bytes`.
+///
+/// Returns the address bytes, the prefix length and the number of bytes read.
+fn read_prefix<const N: usize>(bytes: &[u8]) -> Result<([u8; N], u8, usize), IpNetworkError> {
+    let Some((&prefix, rest)) = bytes.split_first() else {
+        return Err(IpNetworkError::InvalidCidrFormat(
+            "missing NLRI prefix length".to_string(),
+        ));
+    };
+    if usize::from(prefix) > N * 8 {
+        return Err(IpNetworkError::InvalidPrefix);
+    }
+    let len = usize::from(prefix).div_ceil(8);
+    let Some(addr_bytes) = rest.get(..len) else {
+        return Err(IpNetworkError::InvalidCidrFormat(format!(
+            "NLRI prefix /{prefix} needs {len} address bytes, found {}",
+            rest.len()
+        )));
+    };
+    let mut addr = [0; N];
+    addr[..len].copy_from_slice(addr_bytes);
+    Ok((addr, prefix, 1 + len))
+}
+
+impl Ipv4Network {
+    /// Appends this network to `buf` in the BGP NLRI prefix encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let mut buf = Vec::new();
+    /// "10.1.2.3/12".parse::<Ipv4Network>().unwrap().to_nlri(&mut buf);
+    /// "0.0.0.0/0".parse::<Ipv4Network>().unwrap().to_nlri(&mut buf);
+    /// assert_eq!(buf, [12, 10, 0, 0]);
+    /// ```
+    pub fn to_nlri(&self, buf: &mut Vec<u8>) {
+        let len = usize::from(self.prefix()).div_ceil(8);
+        buf.push(self.prefix());
+        buf.extend_from_slice(&self.network().octets()[..len]);
+    }
+
+    /// Reads a network in the BGP NLRI prefix encoding from the start of `bytes`, returning it
+    /// with the number of bytes read.
+    ///
+    /// Bits of the last byte past the prefix are cleared. Returns an
+    /// `IpNetworkError::InvalidPrefix` if the length byte is greater than 32, or an
+    /// `IpNetworkError::InvalidCidrFormat` if `bytes` ends early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let mut bytes: &[u8] = &[24, 192, 0, 2, 8, 10];
+    /// let mut routes = Vec::new();
+    /// while !bytes.is_empty() {
+    ///     let (net, read) = Ipv4Network::from_nlri(bytes).unwrap();
+    ///     routes.push(net);
+    ///     bytes = &bytes[read..];
+    /// }
+    /// assert_eq!(routes, ["192.0.2.0/24".parse().unwrap(), "10.0.0.0/8".parse().unwrap()]);
+    /// ```
+    pub fn from_nlri(bytes: &[u8]) -> Result<(Self, usize), IpNetworkError> {
+        let (addr, prefix, read) = read_prefix::<4>(bytes)?;
+        let net = Ipv4Network::new(Ipv4Addr::from(addr), prefix)?;
+        Ok((Ipv4Network::new(net.network(), prefix)?, read))
+    }
+}
+
+impl Ipv6Network {
+    /// Appends this network to `buf` in the BGP NLRI prefix encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let mut buf = Vec::new();
+    /// "2001:db8::/32".parse::<Ipv6Network>().unwrap().to_nlri(&mut buf);
+    /// assert_eq!(buf, [32, 0x20, 0x01, 0x0d, 0xb8]);
+    /// ```
+    pub fn to_nlri(&self, buf: &mut Vec<u8>) {
+        let len = usize::from(self.prefix()).div_ceil(8);
+        buf.push(self.prefix());
+        buf.extend_from_slice(&self.network().octets()[..len]);
+    }
+
+    /// Reads a network in the BGP NLRI prefix encoding from the start of `bytes`, returning it
+    /// with the number of bytes read.
+    ///
+    /// See [`Ipv4Network::from_nlri`]. The length byte may be up to 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let (net, read) = Ipv6Network::from_nlri(&[33, 0x20, 0x01, 0x0d, 0xb8, 0xff]).unwrap();
+    /// assert_eq!(net, "2001:db8:8000::/33".parse().unwrap());
+    /// assert_eq!(read, 6);
+    /// ```
+    pub fn from_nlri(bytes: &[u8]) -> Result<(Self, usize), IpNetworkError> {
+        let (addr, prefix, read) = read_prefix::<16>(bytes)?;
+        let net = Ipv6Network::new(Ipv6Addr::from(addr), prefix)?;
+        Ok((Ipv6Network::new(net.network(), prefix)?, read))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nlri_round_trip() {
+        for net in ["0.0.0.0/0", "10.0.0.0/8", "172.16.0.0/12", "192.0.2.1/32"] {
+            let net: Ipv4Network = net.parse().unwrap();
+            let mut buf = vec![0xff];
+            net.to_nlri(&mut buf);
+            assert_eq!(Ipv4Network::from_nlri(&buf[1..]), Ok((net, buf.len() - 1)));
+        }
+        for net in ["::/0", "2001:db8::/31", "::1/128"] {
+            let net: Ipv6Network = net.parse().unwrap();
+            let mut buf = Vec::new();
+            net.to_nlri(&mut buf);
+            buf.push(0xff);
+            assert_eq!(Ipv6Network::from_nlri(&buf), Ok((net, buf.len() - 1)));
+        }
+    }
+
+    #[test]
+    fn nlri_errors() {
+        assert!(matches!(
+            Ipv4Network::from_nlri(&[]),
+            Err(IpNetworkError::InvalidCidrFormat(_))
+        ));
+        assert_eq!(
+            Ipv4Network::from_nlri(&[33, 0, 0, 0, 0, 0]),
+            Err(IpNetworkError::InvalidPrefix)
+        );
+        assert!(matches!(
+            Ipv4Network::from_nlri(&[17, 10, 0]),
+            Err(IpNetworkError::InvalidCidrFormat(_))
+        ));
+        assert_eq!(
+            Ipv6Network::from_nlri(&[129]),
+            Err(IpNetworkError::InvalidPrefix)
+        );
+        assert_eq!(
+            Ipv4Network::from_nlri(&[0]),
+            Ok(("0.0.0.0/0".parse().unwrap(), 1))
+        );
+    }
+}
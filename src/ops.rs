@@ -0,0 +1,144 @@
+//! Set operators on single networks.
+//!
+//! `a & b` is the network of addresses in both `a` and `b`, and `a | b` is the network of
+//! addresses in either, when those addresses form a single network. Both return `None`
+//! otherwise, and both results have their host bits cleared.
+
+use crate::merge::Merge;
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use std::ops::{BitAnd, BitOr};
+
+/// Returns the overlap of two networks, which is the smaller one if either contains the other.
+fn intersection<T: Merge>(a: T, b: T) -> Option<T> {
+    let (low, high) = ordered(a, b);
+    low.covers(high).then_some(high)
+}
+
+/// Returns the network covering exactly the addresses of both networks, which exists if one
+/// contains the other or they are the two halves of their parent.
+fn union<T: Merge>(a: T, b: T) -> Option<T> {
+    let (low, high) = ordered(a, b);
+    if low.covers(high) {
+        Some(low)
+    } else {
+        low.join(high)
+    }
+}
+
+fn ordered<T: Merge>(a: T, b: T) -> (T, T) {
+    let (a, b) = (a.normalize(), b.normalize());
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Returns the network of addresses in both networks, if they overlap.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::Ipv4Network;
+///
+/// let a: Ipv4Network = "10.0.0.0/8".parse().unwrap();
+/// let b: Ipv4Network = "10.1.2.3/24".parse().unwrap();
+/// assert_eq!(a & b, Some("10.1.2.0/24".parse().unwrap()));
+/// assert_eq!(b & "192.0.2.0/24".parse().unwrap(), None);
+/// ```
+impl BitAnd for Ipv4Network {
+    type Output = Option<Ipv4Network>;
+
+    fn bitand(self, other: Ipv4Network) -> Self::Output {
+        intersection(self, other)
+    }
+}
+
+/// Returns the network covering the addresses of both networks, if one contains the other or
+/// they are adjacent halves of a larger network.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::Ipv4Network;
+///
+/// let low: Ipv4Network = "10.0.0.0/25".parse().unwrap();
+/// let high: Ipv4Network = "10.0.0.128/25".parse().unwrap();
+/// assert_eq!(low | high, Some("10.0.0.0/24".parse().unwrap()));
+/// assert_eq!(low | "10.0.1.0/25".parse().unwrap(), None);
+/// ```
+impl BitOr for Ipv4Network {
+    type Output = Option<Ipv4Network>;
+
+    fn bitor(self, other: Ipv4Network) -> Self::Output {
+        union(self, other)
+    }
+}
+
+/// Returns the network of addresses in both networks, if they overlap.
+impl BitAnd for Ipv6Network {
+    type Output = Option<Ipv6Network>;
+
+    fn bitand(self, other: Ipv6Network) -> Self::Output {
+        intersection(self, other)
+    }
+}
+
+/// Returns the network covering the addresses of both networks, if one contains the other or
+/// they are adjacent halves of a larger network.
+impl BitOr for Ipv6Network {
+    type Output = Option<Ipv6Network>;
+
+    fn bitor(self, other: Ipv6Network) -> Self::Output {
+        union(self, other)
+    }
+}
+
+/// Returns the network of addresses in both networks, if they are of the same family and
+/// overlap.
+impl BitAnd for IpNetwork {
+    type Output = Option<IpNetwork>;
+
+    fn bitand(self, other: IpNetwork) -> Self::Output {
+        intersection(self, other)
+    }
+}
+
+/// Returns the network covering the addresses of both networks, if they are of the same
+/// family and one contains the other or they are adjacent halves of a larger network.
+impl BitOr for IpNetwork {
+    type Output = Option<IpNetwork>;
+
+    fn bitor(self, other: IpNetwork) -> Self::Output {
+        union(self, other)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn net(s: &str) -> IpNetwork {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn network_operators() {
+        assert_eq!(
+            net("10.0.0.0/8") & net("10.0.0.0/8"),
+            Some(net("10.0.0.0/8"))
+        );
+        assert_eq!(
+            net("10.0.0.1/8") | net("10.9.9.9/8"),
+            Some(net("10.0.0.0/8"))
+        );
+        assert_eq!(net("0.0.0.0/0") & net("::/0"), None);
+        assert_eq!(net("0.0.0.0/0") | net("::/0"), None);
+        assert_eq!(net("::/1") | net("8000::/1"), Some(net("::/0")));
+        assert_eq!(net("8000::/1") | net("::/1"), Some(net("::/0")));
+        assert_eq!(net("::/0") & net("::1/128"), Some(net("::1/128")));
+        // Adjacent networks whose union is not a single network.
+        assert_eq!(net("10.0.1.0/24") | net("10.0.2.0/24"), None);
+        assert_eq!(net("10.0.1.0/24") & net("10.0.2.0/24"), None);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::error::IpNetworkError;
+use crate::{Ipv4Network, Ipv6Network};
+use std::convert::TryFrom;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// A plain-old-data mirror of [`Ipv4Network`]: the four address bytes in network byte order
+/// followed by the prefix length.
+///
+/// The type is `#[repr(C)]`, has an alignment of 1 and no padding, so it can be placed in
+/// shared memory or on the wire as-is. With the `zerocopy` feature it implements the
+/// `zerocopy` traits needed to cast it from and to byte slices without `unsafe` code.
+///
+/// The prefix is not validated until the value is converted back into an `Ipv4Network`.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use ipnetwork::{Ipv4Network, PackedIpv4Network};
+///
+/// let net: Ipv4Network = "192.168.0.0/16".parse().unwrap();
+/// let packed = PackedIpv4Network::from(net);
+/// assert_eq!(packed.addr, [192, 168, 0, 0]);
+/// assert_eq!(packed.prefix, 16);
+/// assert_eq!(Ipv4Network::try_from(packed), Ok(net));
+///
+/// let bogus = PackedIpv4Network { addr: [0; 4], prefix: 33 };
+/// assert!(Ipv4Network::try_from(bogus).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout,
+        zerocopy::Unaligned
+    )
+)]
+#[repr(C)]
+pub struct PackedIpv4Network {
+    /// The address bytes in network byte order.
+    pub addr: [u8; 4],
+    /// The prefix length.
+    pub prefix: u8,
+}
+
+/// A plain-old-data mirror of [`Ipv6Network`]: the sixteen address bytes in network byte
+/// order followed by the prefix length.
+///
+/// See [`PackedIpv4Network`] for the layout guarantees.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use ipnetwork::{Ipv6Network, PackedIpv6Network};
+///
+/// let net: Ipv6Network = "2001:db8::/32".parse().unwrap();
+/// let packed = PackedIpv6Network::from(net);
+/// assert_eq!(packed.addr[..4], [0x20, 0x01, 0x0d, 0xb8]);
+/// assert_eq!(Ipv6Network::try_from(packed), Ok(net));
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout,
+        zerocopy::Unaligned
+    )
+)]
+#[repr(C)]
+pub struct PackedIpv6Network {
+    /// The address bytes in network byte order.
+    pub addr: [u8; 16],
+    /// The prefix length.
+    pub prefix: u8,
+}
+
+impl From<Ipv4Network> for PackedIpv4Network {
+    fn from(net: Ipv4Network) -> Self {
+        PackedIpv4Network {
+            addr: net.ip().octets(),
+            prefix: net.prefix(),
+        }
+    }
+}
+
+impl TryFrom<PackedIpv4Network> for Ipv4Network {
+    type Error = IpNetworkError;
+
+    fn try_from(packed: PackedIpv4Network) -> Result<Self, Self::Error> {
+        Ipv4Network::new(packed.addr.into(), packed.prefix)
+    }
+}
+
+impl From<Ipv6Network> for PackedIpv6Network {
+    fn from(net: Ipv6Network) -> Self {
+        PackedIpv6Network {
+            addr: net.ip().octets(),
+            prefix: net.prefix(),
+        }
+    }
+}
+
+impl TryFrom<PackedIpv6Network> for Ipv6Network {
+    type Error = IpNetworkError;
+
+    fn try_from(packed: PackedIpv6Network) -> Result<Self, Self::Error> {
+        Ipv6Network::new(packed.addr.into(), packed.prefix)
+    }
+}
+
+impl Ipv4Network {
+    /// Returns the network as five bytes: the address in network byte order followed by the
+    /// prefix length.
+    ///
+    /// This is the layout of [`PackedIpv4Network`]. The address keeps its host bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.168.1.7/16".parse().unwrap();
+    /// assert_eq!(net.to_bytes(), [192, 168, 1, 7, 16]);
+    /// ```
+    pub const fn to_bytes(&self) -> [u8; 5] {
+        let [a, b, c, d] = self.ip().octets();
+        [a, b, c, d, self.prefix()]
+    }
+
+    /// Constructs a network from the five bytes written by [`Ipv4Network::to_bytes`].
+    ///
+    /// Returns an `IpNetworkError::InvalidPrefix` if the last byte is greater than 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net = Ipv4Network::from_bytes([10, 0, 0, 0, 8]).unwrap();
+    /// assert_eq!(net, "10.0.0.0/8".parse().unwrap());
+    /// assert!(Ipv4Network::from_bytes([10, 0, 0, 0, 33]).is_err());
+    /// ```
+    pub fn from_bytes(bytes: [u8; 5]) -> Result<Self, IpNetworkError> {
+        let [a, b, c, d, prefix] = bytes;
+        Ipv4Network::new(Ipv4Addr::new(a, b, c, d), prefix)
+    }
+}
+
+impl Ipv6Network {
+    /// Returns the network as seventeen bytes: the address in network byte order followed by
+    /// the prefix length.
+    ///
+    /// This is the layout of [`PackedIpv6Network`]. The address keeps its host bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8::1/32".parse().unwrap();
+    /// let bytes = net.to_bytes();
+    /// assert_eq!(bytes[..4], [0x20, 0x01, 0x0d, 0xb8]);
+    /// assert_eq!(bytes[15..], [1, 32]);
+    /// ```
+    pub fn to_bytes(&self) -> [u8; 17] {
+        let mut bytes = [0; 17];
+        bytes[..16].copy_from_slice(&self.ip().octets());
+        bytes[16] = self.prefix();
+        bytes
+    }
+
+    /// Constructs a network from the seventeen bytes written by [`Ipv6Network::to_bytes`].
+    ///
+    /// Returns an `IpNetworkError::InvalidPrefix` if the last byte is greater than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let mut bytes = [0; 17];
+    /// bytes[15] = 1;
+    /// bytes[16] = 128;
+    /// assert_eq!(Ipv6Network::from_bytes(bytes), Ok("::1/128".parse().unwrap()));
+    /// ```
+    pub fn from_bytes(bytes: [u8; 17]) -> Result<Self, IpNetworkError> {
+        let mut addr = [0; 16];
+        addr.copy_from_slice(&bytes[..16]);
+        Ipv6Network::new(Ipv6Addr::from(addr), bytes[16])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn packed_layout() {
+        assert_eq!(std::mem::size_of::<PackedIpv4Network>(), 5);
+        assert_eq!(std::mem::align_of::<PackedIpv4Network>(), 1);
+        assert_eq!(std::mem::size_of::<PackedIpv6Network>(), 17);
+        assert_eq!(std::mem::align_of::<PackedIpv6Network>(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "zerocopy")]
+    fn packed_from_bytes() {
+        use zerocopy::{FromBytes, IntoBytes};
+
+        let bytes = [10, 0, 0, 0, 8, 192, 168, 0, 0, 16];
+        let nets = <[PackedIpv4Network]>::ref_from_bytes(&bytes).unwrap();
+        assert_eq!(
+            Ipv4Network::try_from(nets[1]),
+            Ok("192.168.0.0/16".parse().unwrap())
+        );
+        assert_eq!(nets.as_bytes(), bytes);
+
+        let packed = PackedIpv6Network::from("::1/128".parse::<Ipv6Network>().unwrap());
+        assert_eq!(packed.as_bytes()[15..], [1, 128]);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let v4: Ipv4Network = "255.255.255.255/32".parse().unwrap();
+        assert_eq!(Ipv4Network::from_bytes(v4.to_bytes()), Ok(v4));
+        let packed = PackedIpv4Network::from(v4);
+        assert_eq!(v4.to_bytes()[..4], packed.addr);
+        assert_eq!(v4.to_bytes()[4], packed.prefix);
+        let v6: Ipv6Network = "ff00::1/8".parse().unwrap();
+        assert_eq!(Ipv6Network::from_bytes(v6.to_bytes()), Ok(v6));
+        let mut bogus = v6.to_bytes();
+        bogus[16] = 129;
+        assert_eq!(
+            Ipv6Network::from_bytes(bogus),
+            Err(IpNetworkError::InvalidPrefix)
+        );
+    }
+}
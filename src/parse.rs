@@ -1,4 +1,6 @@
 use crate::error::IpNetworkError;
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use std::net::{IpAddr, Ipv4Addr};
 
 pub fn cidr_parts(cidr: &str) -> Result<(&str, Option<&str>), IpNetworkError> {
     // Try to find a single slash
@@ -31,3 +33,431 @@ pub fn parse_prefix(prefix: &str, max: u8) -> Result<u8, IpNetworkError> {
             }
         })
 }
+
+/// Splits the network at the start of `s` from the text following it.
+///
+/// The address is the longest leading run of hex digits, `.` and `:`, less any trailing `.` or
+/// `:` needed to make it parse. A `/` directly after it starts the prefix, which runs for as
+/// long as there are digits, or also `.` for an IPv4 netmask.
+fn leading_network(s: &str) -> Result<(IpNetwork, usize), IpNetworkError> {
+    let run = s
+        .bytes()
+        .take_while(|&c| c.is_ascii_hexdigit() || c == b'.' || c == b':')
+        .count();
+    let mut end = run;
+    let addr = loop {
+        if let Ok(addr) = s[..end].parse::<IpAddr>() {
+            break addr;
+        }
+        match s.as_bytes()[..end].last() {
+            Some(b'.') | Some(b':') => end -= 1,
+            _ => return Err(IpNetworkError::InvalidAddr(s[..run].to_string())),
+        }
+    };
+
+    let Some(prefix) = s[end..].strip_prefix('/') else {
+        return Ok((IpNetwork::from(addr), end));
+    };
+    let len = prefix
+        .bytes()
+        .take_while(|&c| c.is_ascii_digit() || (c == b'.' && addr.is_ipv4()))
+        .count();
+    let len = prefix[..len].trim_end_matches('.').len();
+    let consumed = end + 1 + len;
+    let net = match addr {
+        IpAddr::V4(_) => IpNetwork::V4(s[..consumed].parse()?),
+        IpAddr::V6(_) => IpNetwork::V6(s[..consumed].parse()?),
+    };
+    Ok((net, consumed))
+}
+
+impl IpNetwork {
+    /// Parses a network from the start of `s`, returning it with the number of bytes consumed.
+    ///
+    /// Unlike [`FromStr`](std::str::FromStr), the network does not have to make up the whole
+    /// string, which lets parsers for larger grammars read a network and continue after it. The
+    /// accepted syntax is the same. The network ends where a character that cannot continue it
+    /// appears, so it must be followed by something other than a hex digit, such as whitespace
+    /// or punctuation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let rule = "10.0.0.0/8,2001:db8::/32 deny";
+    /// let (first, len) = IpNetwork::parse_partial(rule).unwrap();
+    /// assert_eq!(first, "10.0.0.0/8".parse().unwrap());
+    /// assert_eq!(&rule[len..], ",2001:db8::/32 deny");
+    ///
+    /// let (second, len) = IpNetwork::parse_partial(&rule[11..]).unwrap();
+    /// assert_eq!(second, "2001:db8::/32".parse().unwrap());
+    /// assert_eq!(len, 13);
+    ///
+    /// // A trailing separator is not part of the network.
+    /// assert_eq!(IpNetwork::parse_partial("192.0.2.1.").unwrap().1, 9);
+    /// assert!(IpNetwork::parse_partial("deny 10.0.0.0/8").is_err());
+    /// ```
+    pub fn parse_partial(s: &str) -> Result<(IpNetwork, usize), IpNetworkError> {
+        leading_network(s)
+    }
+}
+
+impl Ipv4Network {
+    /// Parses an IPv4 network from the start of `s`, returning it with the number of bytes
+    /// consumed.
+    ///
+    /// See [`IpNetwork::parse_partial`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let (net, len) = Ipv4Network::parse_partial("10.0.0.0/255.0.0.0; next").unwrap();
+    /// assert_eq!(net.prefix(), 8);
+    /// assert_eq!(len, 18);
+    /// ```
+    pub fn parse_partial(s: &str) -> Result<(Ipv4Network, usize), IpNetworkError> {
+        match leading_network(s)? {
+            (IpNetwork::V4(net), len) => Ok((net, len)),
+            (IpNetwork::V6(_), len) => Err(IpNetworkError::InvalidAddr(s[..len].to_string())),
+        }
+    }
+}
+
+impl Ipv6Network {
+    /// Parses an IPv6 network from the start of `s`, returning it with the number of bytes
+    /// consumed.
+    ///
+    /// See [`IpNetwork::parse_partial`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let (net, len) = Ipv6Network::parse_partial("fe80::/10]").unwrap();
+    /// assert_eq!(net.prefix(), 10);
+    /// assert_eq!(len, 9);
+    /// ```
+    pub fn parse_partial(s: &str) -> Result<(Ipv6Network, usize), IpNetworkError> {
+        match leading_network(s)? {
+            (IpNetwork::V6(net), len) => Ok((net, len)),
+            (IpNetwork::V4(_), len) => Err(IpNetworkError::InvalidAddr(s[..len].to_string())),
+        }
+    }
+}
+
+/// Splits `s` into an address and a netmask or wildcard mask separated by whitespace, such as
+/// `192.168.0.0 255.255.255.0`.
+fn addr_mask_parts(s: &str) -> Result<(Ipv4Addr, Ipv4Addr), IpNetworkError> {
+    let mut tokens = s.split_whitespace();
+    let (Some(addr), Some(mask), None) = (tokens.next(), tokens.next(), tokens.next()) else {
+        return Err(IpNetworkError::InvalidCidrFormat(format!(
+            "expected an address and a mask separated by whitespace: {s}"
+        )));
+    };
+    let parse = |addr: &str| {
+        addr.parse::<Ipv4Addr>()
+            .map_err(|_| IpNetworkError::InvalidAddr(addr.to_string()))
+    };
+    Ok((parse(addr)?, parse(mask)?))
+}
+
+impl Ipv4Network {
+    /// Parses a network written as an address and a netmask separated by whitespace, as in
+    /// route table dumps and legacy configuration files.
+    ///
+    /// Returns an `IpNetworkError::InvalidNetmask` if the netmask is not contiguous.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net = Ipv4Network::from_addr_mask_str("192.168.0.0 255.255.255.0").unwrap();
+    /// assert_eq!(net, "192.168.0.0/24".parse().unwrap());
+    /// assert!(Ipv4Network::from_addr_mask_str("192.168.0.0/24").is_err());
+    /// assert!(Ipv4Network::from_addr_mask_str("192.168.0.0 0.0.0.255").is_err());
+    /// ```
+    pub fn from_addr_mask_str(s: &str) -> Result<Ipv4Network, IpNetworkError> {
+        let (addr, mask) = addr_mask_parts(s)?;
+        Ipv4Network::with_netmask(addr, mask)
+    }
+
+    /// Parses a network written as an address and a wildcard mask separated by whitespace, as
+    /// in Cisco ACLs.
+    ///
+    /// See [`Ipv4Network::with_wildcard_mask`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net = Ipv4Network::from_addr_wildcard_str("10.0.0.0 0.0.0.255").unwrap();
+    /// assert_eq!(net, "10.0.0.0/24".parse().unwrap());
+    /// assert!(Ipv4Network::from_addr_wildcard_str("10.0.0.0 255.255.255.0").is_err());
+    /// ```
+    pub fn from_addr_wildcard_str(s: &str) -> Result<Ipv4Network, IpNetworkError> {
+        let (addr, wildcard) = addr_mask_parts(s)?;
+        Ipv4Network::with_wildcard_mask(addr, wildcard)
+    }
+}
+
+/// Options controlling how lenient parsing is about input from other systems.
+///
+/// The defaults match [`FromStr`](std::str::FromStr): surrounding whitespace is an error, and so
+/// are IPv4 octets with leading zeros such as `010.1.1.1`, which some tools read as octal and
+/// others as decimal. Both can be allowed when a data source is known to produce them.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{IpNetwork, ParseOptions};
+///
+/// let strict = ParseOptions::new();
+/// assert!(strict.parse(" 010.001.0.0/16\n").is_err());
+///
+/// let lenient = ParseOptions::new()
+///     .trim_whitespace(true)
+///     .allow_leading_zeros(true);
+/// let net: IpNetwork = "10.1.0.0/16".parse().unwrap();
+/// assert_eq!(lenient.parse(" 010.001.0.0/16\n"), Ok(net));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    trim_whitespace: bool,
+    allow_leading_zeros: bool,
+}
+
+impl ParseOptions {
+    /// Constructs the strict default options.
+    pub const fn new() -> Self {
+        ParseOptions {
+            trim_whitespace: false,
+            allow_leading_zeros: false,
+        }
+    }
+
+    /// Sets whether whitespace around the network is ignored.
+    pub const fn trim_whitespace(self, trim: bool) -> Self {
+        ParseOptions {
+            trim_whitespace: trim,
+            ..self
+        }
+    }
+
+    /// Sets whether IPv4 octets may have leading zeros, in the address and in a dotted netmask.
+    ///
+    /// Octets with leading zeros are read as decimal, so `010.1.1.1` is `10.1.1.1`. IPv4
+    /// addresses embedded in IPv6 addresses, such as `::ffff:010.1.1.1`, are still rejected.
+    pub const fn allow_leading_zeros(self, allow: bool) -> Self {
+        ParseOptions {
+            allow_leading_zeros: allow,
+            ..self
+        }
+    }
+
+    /// Parses an IPv4 or IPv6 network with these options.
+    ///
+    /// Text containing a `:` is parsed as IPv6 and anything else as IPv4, and the error of
+    /// that parser is returned unchanged.
+    pub fn parse(&self, s: &str) -> Result<IpNetwork, IpNetworkError> {
+        if s.contains(':') {
+            self.parse_ipv6(s).map(IpNetwork::V6)
+        } else {
+            self.parse_ipv4(s).map(IpNetwork::V4)
+        }
+    }
+
+    /// Parses an IPv4 network with these options.
+    pub fn parse_ipv4(&self, s: &str) -> Result<Ipv4Network, IpNetworkError> {
+        let s = self.trim(s);
+        if !self.allow_leading_zeros {
+            return s.parse();
+        }
+        let (addr, prefix) = cidr_parts(s)?;
+        let mut cidr = strip_octet_zeros(addr);
+        if let Some(prefix) = prefix {
+            cidr.push('/');
+            cidr.push_str(&strip_octet_zeros(prefix));
+        }
+        cidr.parse()
+    }
+
+    /// Parses an IPv6 network with these options.
+    pub fn parse_ipv6(&self, s: &str) -> Result<Ipv6Network, IpNetworkError> {
+        self.trim(s).parse()
+    }
+
+    fn trim<'a>(&self, s: &'a str) -> &'a str {
+        if self.trim_whitespace {
+            s.trim()
+        } else {
+            s
+        }
+    }
+}
+
+/// Removes the leading zeros from the octets of a dotted-quad, leaving other text unchanged.
+fn strip_octet_zeros(s: &str) -> String {
+    let octets: Vec<&str> = s.split('.').collect();
+    let dotted_quad = octets.len() == 4
+        && octets
+            .iter()
+            .all(|octet| !octet.is_empty() && octet.bytes().all(|c| c.is_ascii_digit()));
+    if !dotted_quad {
+        return s.to_string();
+    }
+    let octets: Vec<&str> = octets
+        .iter()
+        .map(|octet| match octet.trim_start_matches('0') {
+            "" => "0",
+            digits => digits,
+        })
+        .collect();
+    octets.join(".")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn partial(s: &str) -> Result<(String, usize), IpNetworkError> {
+        IpNetwork::parse_partial(s).map(|(net, len)| (net.to_string(), len))
+    }
+
+    #[test]
+    fn parse_partial_boundaries() {
+        assert_eq!(partial("10.0.0.1"), Ok(("10.0.0.1/32".to_string(), 8)));
+        assert_eq!(partial("10.0.0.0/8 "), Ok(("10.0.0.0/8".to_string(), 10)));
+        assert_eq!(partial("::/0]"), Ok(("::/0".to_string(), 4)));
+        assert_eq!(partial("::1:"), Ok(("::1/128".to_string(), 3)));
+        assert_eq!(partial("::"), Ok(("::/128".to_string(), 2)));
+        assert_eq!(
+            partial("::ffff:1.2.3.4/96."),
+            Ok(("::ffff:1.2.3.4/96".to_string(), 17))
+        );
+        assert_eq!(
+            partial("10.0.0.0/255.255.0.0."),
+            Ok(("10.0.0.0/16".to_string(), 20))
+        );
+        assert_eq!(partial("10.0.0.0/8/8"), Ok(("10.0.0.0/8".to_string(), 10)));
+        assert_eq!(
+            partial("2001:db8::/32.5"),
+            Ok(("2001:db8::/32".to_string(), 13))
+        );
+    }
+
+    #[test]
+    fn parse_partial_errors() {
+        assert_eq!(partial(""), Err(IpNetworkError::InvalidAddr(String::new())));
+        assert_eq!(
+            partial("10.0.0.256/8"),
+            Err(IpNetworkError::InvalidAddr("10.0.0.256".to_string()))
+        );
+        assert_eq!(partial("10.0.0.0/33"), Err(IpNetworkError::InvalidPrefix));
+        assert_eq!(partial("10.0.0.0/ "), Err(IpNetworkError::InvalidPrefix));
+        assert_eq!(
+            Ipv4Network::parse_partial("::1 "),
+            Err(IpNetworkError::InvalidAddr("::1".to_string()))
+        );
+        assert!(Ipv6Network::parse_partial("10.0.0.0/8").is_err());
+    }
+
+    #[test]
+    fn parse_options_leading_zeros() {
+        let strict = ParseOptions::default();
+        let lenient = ParseOptions::new().allow_leading_zeros(true);
+        assert!(strict.parse_ipv4("010.1.1.1").is_err());
+        assert!(strict.parse_ipv4("10.1.1.0/255.255.255.000").is_err());
+        assert_eq!(
+            lenient.parse_ipv4("010.001.000.1/255.255.000.0"),
+            Ok("10.1.0.1/16".parse().unwrap())
+        );
+        assert_eq!(
+            lenient.parse_ipv4("0010.1.1.1/8"),
+            Ok("10.1.1.1/8".parse().unwrap())
+        );
+        assert!(lenient.parse_ipv4("0256.1.1.1").is_err());
+        assert!(lenient.parse_ipv4(" 10.1.1.1").is_err());
+        assert!(lenient.parse("::ffff:010.1.1.1").is_err());
+        assert_eq!(
+            lenient.parse("2001:0db8::/32"),
+            Ok("2001:db8::/32".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_options_whitespace() {
+        let trim = ParseOptions::new().trim_whitespace(true);
+        assert!(ParseOptions::new().parse("\t::1/128 ").is_err());
+        assert_eq!(trim.parse("\t::1/128 "), Ok("::1/128".parse().unwrap()));
+        assert!(trim.parse("10.0.0.0 /8").is_err());
+        assert!(trim.parse_ipv4("010.0.0.0").is_err());
+    }
+
+    #[test]
+    fn parse_options_errors() {
+        let options = ParseOptions::new().allow_leading_zeros(true);
+        assert_eq!(
+            options.parse("10.0.0.0/33"),
+            Err(IpNetworkError::InvalidPrefix)
+        );
+        assert_eq!(
+            options.parse("10.0.0.0/255.000.255.0"),
+            Err(IpNetworkError::InvalidNetmask)
+        );
+        assert_eq!(options.parse("::/129"), Err(IpNetworkError::InvalidPrefix));
+        assert!(matches!(
+            options.parse("10.0.0/8"),
+            Err(IpNetworkError::InvalidAddr(_))
+        ));
+    }
+
+    #[test]
+    fn addr_mask_str() {
+        let parse = Ipv4Network::from_addr_mask_str;
+        assert_eq!(
+            parse("\t10.1.2.3   255.255.0.0\n"),
+            Ok("10.1.2.3/16".parse().unwrap())
+        );
+        assert_eq!(parse("0.0.0.0 0.0.0.0"), Ok("0.0.0.0/0".parse().unwrap()));
+        assert_eq!(
+            parse("10.0.0.0 255.0.255.0"),
+            Err(IpNetworkError::InvalidNetmask)
+        );
+        assert!(matches!(
+            parse("10.0.0.0"),
+            Err(IpNetworkError::InvalidCidrFormat(_))
+        ));
+        assert!(matches!(
+            parse("10.0.0.0 255.0.0.0 x"),
+            Err(IpNetworkError::InvalidCidrFormat(_))
+        ));
+        assert_eq!(
+            parse("10.0.0.0 /8"),
+            Err(IpNetworkError::InvalidAddr("/8".to_string()))
+        );
+    }
+
+    #[test]
+    fn addr_wildcard_str() {
+        let parse = Ipv4Network::from_addr_wildcard_str;
+        assert_eq!(
+            parse("10.0.0.1 0.0.0.0"),
+            Ok("10.0.0.1/32".parse().unwrap())
+        );
+        assert_eq!(
+            parse("0.0.0.0 255.255.255.255"),
+            Ok("0.0.0.0/0".parse().unwrap())
+        );
+        assert_eq!(
+            parse("10.0.0.0 0.0.0.254"),
+            Err(IpNetworkError::InvalidNetmask)
+        );
+        assert!(parse("10.0.0.0/0.0.0.255").is_err());
+    }
+}
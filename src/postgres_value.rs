@@ -0,0 +1,102 @@
+//! `postgres` and `tokio-postgres` support for the `INET` and `CIDR` column types.
+//!
+//! Values use the binary wire format of both types. A `CIDR` value cannot have host bits set,
+//! so networks are written to `CIDR` columns with their host bits cleared, while `INET`
+//! columns keep the address as is.
+
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use bytes::BytesMut;
+use postgres_protocol::types::{inet_from_sql, inet_to_sql};
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::error::Error;
+
+type BoxError = Box<dyn Error + Sync + Send>;
+
+impl<'a> FromSql<'a> for IpNetwork {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        let inet = inet_from_sql(raw)?;
+        Ok(IpNetwork::new(inet.addr(), inet.netmask())?)
+    }
+
+    accepts!(INET, CIDR);
+}
+
+impl ToSql for IpNetwork {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        let addr = if *ty == Type::CIDR {
+            self.network()
+        } else {
+            self.ip()
+        };
+        inet_to_sql(addr, self.prefix(), out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(INET, CIDR);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Ipv4Network {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        match IpNetwork::from_sql(ty, raw)? {
+            IpNetwork::V4(net) => Ok(net),
+            IpNetwork::V6(net) => Err(format!("expected an IPv4 network, found {net}").into()),
+        }
+    }
+
+    accepts!(INET, CIDR);
+}
+
+impl ToSql for Ipv4Network {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        IpNetwork::V4(*self).to_sql(ty, out)
+    }
+
+    accepts!(INET, CIDR);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Ipv6Network {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        match IpNetwork::from_sql(ty, raw)? {
+            IpNetwork::V6(net) => Ok(net),
+            IpNetwork::V4(net) => Err(format!("expected an IPv6 network, found {net}").into()),
+        }
+    }
+
+    accepts!(INET, CIDR);
+}
+
+impl ToSql for Ipv6Network {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        IpNetwork::V6(*self).to_sql(ty, out)
+    }
+
+    accepts!(INET, CIDR);
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn postgres_wire_format() {
+        let net: IpNetwork = "192.0.2.1/24".parse().unwrap();
+        let mut inet = BytesMut::new();
+        net.to_sql(&Type::INET, &mut inet).unwrap();
+        assert_eq!(inet[..], [2, 24, 0, 4, 192, 0, 2, 1]);
+        assert_eq!(IpNetwork::from_sql(&Type::INET, &inet).unwrap(), net);
+
+        let mut cidr = BytesMut::new();
+        net.to_sql(&Type::CIDR, &mut cidr).unwrap();
+        assert_eq!(cidr[4..], [192, 0, 2, 0]);
+
+        let v6: Ipv6Network = "2001:db8::/32".parse().unwrap();
+        let mut buf = BytesMut::new();
+        v6.to_sql_checked(&Type::CIDR, &mut buf).unwrap();
+        assert_eq!(Ipv6Network::from_sql(&Type::CIDR, &buf).unwrap(), v6);
+        assert!(Ipv4Network::from_sql(&Type::CIDR, &buf).is_err());
+        assert!(v6.to_sql_checked(&Type::TEXT, &mut buf).is_err());
+    }
+}
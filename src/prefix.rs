@@ -0,0 +1,113 @@
+use crate::error::IpNetworkError;
+use crate::parse::parse_prefix;
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+/// A prefix length that is known to be valid for an address family whose addresses are `MAX`
+/// bits wide.
+///
+/// Use the [`Ipv4Prefix`] and [`Ipv6Prefix`] aliases rather than naming `MAX` directly.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::Ipv4Addr;
+/// use ipnetwork::{Ipv4Network, Ipv4Prefix};
+///
+/// let prefix: Ipv4Prefix = "24".parse().unwrap();
+/// let net = Ipv4Network::with_prefix(Ipv4Addr::new(10, 1, 2, 0), prefix);
+/// assert_eq!(net.prefix(), 24);
+///
+/// assert!(Ipv4Prefix::new(33).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PrefixLen<const MAX: u8>(u8);
+
+/// A prefix length between 0 and 32.
+pub type Ipv4Prefix = PrefixLen<32>;
+
+/// A prefix length between 0 and 128.
+pub type Ipv6Prefix = PrefixLen<128>;
+
+impl<const MAX: u8> PrefixLen<MAX> {
+    /// The longest valid prefix length.
+    pub const MAX: Self = PrefixLen(MAX);
+
+    /// Constructs a new `PrefixLen` from a `u8`.
+    ///
+    /// If the prefix is larger than `MAX` this will return an `IpNetworkError::InvalidPrefix`.
+    pub const fn new(prefix: u8) -> Result<Self, IpNetworkError> {
+        match Self::new_checked(prefix) {
+            Some(a) => Ok(a),
+            None => Err(IpNetworkError::InvalidPrefix),
+        }
+    }
+
+    /// Constructs a new `PrefixLen` from a `u8`.
+    ///
+    /// If the prefix is larger than `MAX` this will return `None`. This is useful in const
+    /// contexts, where [`Option::unwrap`] may be called to trigger a compile-time error in case
+    /// the prefix is an unexpected value.
+    pub const fn new_checked(prefix: u8) -> Option<Self> {
+        if prefix > MAX {
+            None
+        } else {
+            Some(PrefixLen(prefix))
+        }
+    }
+
+    /// Returns the prefix length as a `u8`.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl<const MAX: u8> fmt::Display for PrefixLen<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const MAX: u8> FromStr for PrefixLen<MAX> {
+    type Err = IpNetworkError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_prefix(s, MAX).map(PrefixLen)
+    }
+}
+
+impl<const MAX: u8> TryFrom<u8> for PrefixLen<MAX> {
+    type Error = IpNetworkError;
+
+    fn try_from(prefix: u8) -> Result<Self, Self::Error> {
+        Self::new(prefix)
+    }
+}
+
+impl<const MAX: u8> From<PrefixLen<MAX>> for u8 {
+    fn from(prefix: PrefixLen<MAX>) -> u8 {
+        prefix.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn create_prefix() {
+        assert_eq!(Ipv4Prefix::new(32).unwrap().get(), 32);
+        assert_eq!(Ipv4Prefix::new(33), Err(IpNetworkError::InvalidPrefix));
+        assert_eq!(Ipv6Prefix::new(128).unwrap().get(), 128);
+        assert_eq!(Ipv6Prefix::new(129), Err(IpNetworkError::InvalidPrefix));
+        assert_eq!(Ipv6Prefix::try_from(64).map(u8::from), Ok(64));
+        assert_eq!(Ipv4Prefix::MAX.get(), 32);
+    }
+
+    #[test]
+    fn parse_prefix() {
+        assert_eq!("0".parse::<Ipv4Prefix>().unwrap().get(), 0);
+        assert_eq!("33".parse::<Ipv6Prefix>().unwrap().to_string(), "33");
+        assert!("33".parse::<Ipv4Prefix>().is_err());
+        assert!("-1".parse::<Ipv4Prefix>().is_err());
+        assert!("a".parse::<Ipv6Prefix>().is_err());
+    }
+}
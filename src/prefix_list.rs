@@ -0,0 +1,137 @@
+use crate::error::LoadError;
+use crate::merge::Merge;
+use crate::IpNetwork;
+use std::io;
+
+/// Reads a plain-text prefix list with one network per line.
+///
+/// Everything from a `#` to the end of a line is a comment. Surrounding whitespace, blank lines
+/// and comment-only lines are ignored. Networks are returned in the order they appear. Loading
+/// stops at the first bad line and the returned `LoadError` reports it.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{load_prefix_list, IpNetwork};
+///
+/// let data = "# bogons\n10.0.0.0/8\n\n2001:db8::/32  # documentation\n";
+/// let networks = load_prefix_list(data.as_bytes()).unwrap();
+/// assert_eq!(networks[0], "10.0.0.0/8".parse::<IpNetwork>().unwrap());
+/// assert_eq!(networks[1], "2001:db8::/32".parse::<IpNetwork>().unwrap());
+///
+/// let err = load_prefix_list("10.0.0.0/8\n10.0.0.0/33\n".as_bytes()).unwrap_err();
+/// assert_eq!(err.line(), 2);
+/// ```
+pub fn load_prefix_list<R: io::BufRead>(reader: R) -> Result<Vec<IpNetwork>, LoadError> {
+    let mut networks = Vec::new();
+    for (index, text) in reader.lines().enumerate() {
+        let line = index as u64 + 1;
+        let text = text.map_err(|e| LoadError::Record {
+            line,
+            message: e.to_string(),
+        })?;
+        let value = match text.find('#') {
+            Some(comment) => &text[..comment],
+            None => &text,
+        }
+        .trim();
+        if value.is_empty() {
+            continue;
+        }
+
+        let network = value
+            .parse()
+            .map_err(|error| LoadError::InvalidNetwork { line, error })?;
+        networks.push(network);
+    }
+    Ok(networks)
+}
+
+/// Writes `networks` as a plain-text prefix list with one network per line, in the given order.
+///
+/// The output can be read back with [`load_prefix_list`].
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{write_prefix_list, IpNetwork};
+///
+/// let networks: Vec<IpNetwork> = ["2001:db8::/32", "10.0.0.1/8"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// let mut out = Vec::new();
+/// write_prefix_list(&mut out, &networks).unwrap();
+/// assert_eq!(out, b"2001:db8::/32\n10.0.0.1/8\n");
+/// ```
+pub fn write_prefix_list<W: io::Write>(mut writer: W, networks: &[IpNetwork]) -> io::Result<()> {
+    for net in networks {
+        writeln!(writer, "{net}")?;
+    }
+    Ok(())
+}
+
+/// Writes `networks` as a plain-text prefix list in canonical form: host bits cleared,
+/// sorted, and without duplicates.
+///
+/// IPv4 networks are written before IPv6 networks. Overlapping networks are all kept; use
+/// [`ip_merge_in_place`](crate::ip_merge_in_place) first to write the minimal list instead.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{write_prefix_list_sorted, IpNetwork};
+///
+/// let networks: Vec<IpNetwork> = ["2001:db8::/32", "10.0.0.1/8", "10.0.0.0/8"]
+///     .iter()
+///     .map(|n| n.parse().unwrap())
+///     .collect();
+/// let mut out = Vec::new();
+/// write_prefix_list_sorted(&mut out, &networks).unwrap();
+/// assert_eq!(out, b"10.0.0.0/8\n2001:db8::/32\n");
+/// ```
+pub fn write_prefix_list_sorted<W: io::Write>(writer: W, networks: &[IpNetwork]) -> io::Result<()> {
+    let mut sorted: Vec<IpNetwork> = networks.iter().map(|net| net.normalize()).collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+    write_prefix_list(writer, &sorted)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn prefix_list_round_trip() {
+        let data = "  192.0.2.0/24\t\n#\n\r\n::1 # loopback\r\n198.51.100.7/24\n";
+        let networks = load_prefix_list(data.as_bytes()).unwrap();
+        assert_eq!(networks.len(), 3);
+        assert_eq!(networks[1], "::1/128".parse().unwrap());
+
+        let mut out = Vec::new();
+        write_prefix_list(&mut out, &networks).unwrap();
+        assert_eq!(load_prefix_list(out.as_slice()).unwrap(), networks);
+
+        let mut out = Vec::new();
+        write_prefix_list_sorted(&mut out, &networks).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "192.0.2.0/24\n198.51.100.0/24\n::1/128\n"
+        );
+    }
+
+    #[test]
+    fn load_prefix_list_errors() {
+        let err = load_prefix_list("10.0.0.0/8\n# ok\nbogus # not ok\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            err,
+            LoadError::InvalidNetwork {
+                line: 3,
+                error: crate::IpNetworkError::InvalidAddr("bogus".to_string())
+            }
+        );
+
+        let err = load_prefix_list(&b"10.0.0.0/8\n\xff\n"[..]).unwrap_err();
+        assert!(matches!(err, LoadError::Record { line: 2, .. }));
+    }
+}
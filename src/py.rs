@@ -0,0 +1,123 @@
+//! Conversions between the network types and Python's `ipaddress` module.
+//!
+//! Networks are converted to `ipaddress.IPv4Network` and `ipaddress.IPv6Network` objects.
+//! Python networks cannot have host bits set, so those are cleared on the way out. Any Python
+//! object whose `str()` is in CIDR notation can be extracted, which includes networks,
+//! interfaces, addresses and plain strings.
+
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use pyo3::exceptions::PyValueError;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyAnyMethods, PyStringMethods, PyType};
+use pyo3::{Borrowed, Bound, FromPyObject, IntoPyObject, Py, PyAny, PyErr, Python};
+use std::fmt;
+
+/// Parses the `str()` of `obj` as a network.
+fn extract_str<T>(obj: Borrowed<'_, '_, PyAny>) -> Result<T, PyErr>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    obj.str()?
+        .to_cow()?
+        .parse()
+        .map_err(|e: T::Err| PyValueError::new_err(e.to_string()))
+}
+
+impl FromPyObject<'_, '_> for Ipv4Network {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> Result<Self, Self::Error> {
+        extract_str(obj)
+    }
+}
+
+impl FromPyObject<'_, '_> for Ipv6Network {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> Result<Self, Self::Error> {
+        extract_str(obj)
+    }
+}
+
+impl FromPyObject<'_, '_> for IpNetwork {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, '_, PyAny>) -> Result<Self, Self::Error> {
+        extract_str(obj)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for Ipv4Network {
+    type Target = PyAny;
+    type Output = Bound<'py, Self::Target>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        static IPV4_NETWORK: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+        IPV4_NETWORK
+            .import(py, "ipaddress", "IPv4Network")?
+            .call1(((self.network().to_bits(), self.prefix()),))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for Ipv6Network {
+    type Target = PyAny;
+    type Output = Bound<'py, Self::Target>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        static IPV6_NETWORK: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+        IPV6_NETWORK
+            .import(py, "ipaddress", "IPv6Network")?
+            .call1(((self.network().to_bits(), self.prefix()),))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for IpNetwork {
+    type Target = PyAny;
+    type Output = Bound<'py, Self::Target>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        match self {
+            IpNetwork::V4(net) => net.into_pyobject(py),
+            IpNetwork::V6(net) => net.into_pyobject(py),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pyo3::types::PyTypeMethods;
+
+    #[test]
+    fn python_round_trip() {
+        Python::initialize();
+        Python::attach(|py| {
+            let net: IpNetwork = "10.1.2.3/8".parse().unwrap();
+            let obj = net.into_pyobject(py).unwrap();
+            assert_eq!(obj.str().unwrap().to_cow().unwrap(), "10.0.0.0/8");
+            let class = obj.get_type().name().unwrap();
+            assert_eq!(class.to_cow().unwrap(), "IPv4Network");
+            let back: IpNetwork = obj.extract().unwrap();
+            assert_eq!(back, "10.0.0.0/8".parse().unwrap());
+
+            let net: Ipv6Network = "2001:db8::/32".parse().unwrap();
+            let back: Ipv6Network = net.into_pyobject(py).unwrap().extract().unwrap();
+            assert_eq!(back, net);
+
+            let iface = py
+                .import("ipaddress")
+                .unwrap()
+                .call_method1("ip_interface", ("192.0.2.7/24",))
+                .unwrap();
+            let net: Ipv4Network = iface.extract().unwrap();
+            assert_eq!(net, "192.0.2.7/24".parse().unwrap());
+
+            let bogus = "10.0.0.0/33".into_pyobject(py).unwrap();
+            assert!(bogus.extract::<IpNetwork>().is_err());
+        });
+    }
+}
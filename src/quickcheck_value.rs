@@ -0,0 +1,99 @@
+//! `quickcheck` support for property tests.
+//!
+//! Networks shrink by moving the address toward the network address, then by shortening the
+//! prefix toward `/0`, so failing properties reduce to the simplest network that still fails.
+
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use quickcheck::{Arbitrary, Gen};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+impl Arbitrary for Ipv4Network {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let addr = Ipv4Addr::from_bits(u32::arbitrary(g));
+        let prefix = u8::arbitrary(g) % 33;
+        Ipv4Network::new(addr, prefix).unwrap()
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let (network, prefix) = (self.network().to_bits(), self.prefix());
+        let host = self.ip().to_bits() - network;
+        let addr = self.ip();
+        let hosts = host
+            .shrink()
+            .map(move |host| Ipv4Network::new(Ipv4Addr::from_bits(network | host), prefix));
+        let prefixes = prefix
+            .shrink()
+            .map(move |prefix| Ipv4Network::new(addr, prefix));
+        Box::new(hosts.chain(prefixes).map(Result::unwrap))
+    }
+}
+
+impl Arbitrary for Ipv6Network {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let addr = Ipv6Addr::from_bits(u128::arbitrary(g));
+        let prefix = u8::arbitrary(g) % 129;
+        Ipv6Network::new(addr, prefix).unwrap()
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let (network, prefix) = (self.network().to_bits(), self.prefix());
+        let host = self.ip().to_bits() - network;
+        let addr = self.ip();
+        let hosts = host
+            .shrink()
+            .map(move |host| Ipv6Network::new(Ipv6Addr::from_bits(network | host), prefix));
+        let prefixes = prefix
+            .shrink()
+            .map(move |prefix| Ipv6Network::new(addr, prefix));
+        Box::new(hosts.chain(prefixes).map(Result::unwrap))
+    }
+}
+
+impl Arbitrary for IpNetwork {
+    fn arbitrary(g: &mut Gen) -> Self {
+        if bool::arbitrary(g) {
+            IpNetwork::V6(Ipv6Network::arbitrary(g))
+        } else {
+            IpNetwork::V4(Ipv4Network::arbitrary(g))
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        match self {
+            IpNetwork::V4(net) => Box::new(net.shrink().map(IpNetwork::V4)),
+            IpNetwork::V6(net) => Box::new(net.shrink().map(IpNetwork::V6)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn quickcheck_shrinks_toward_network_and_prefix() {
+        let net: Ipv4Network = "203.0.113.77/27".parse().unwrap();
+        assert_eq!(
+            net.shrink().next(),
+            Some("203.0.113.64/27".parse().unwrap())
+        );
+        assert!(net.shrink().all(|n| n != net));
+
+        // Following shrink candidates that keep a non-zero prefix ends at a /1 with no host
+        // bits.
+        let mut current = net;
+        while let Some(smaller) = current.shrink().find(|n| n.prefix() > 0) {
+            current = smaller;
+        }
+        assert_eq!(current.prefix(), 1);
+        assert_eq!(current.ip(), current.network());
+
+        fn prefix_fits(net: IpNetwork) -> bool {
+            net.prefix() <= if net.is_ipv4() { 32 } else { 128 }
+        }
+        QuickCheck::new()
+            .tests(200)
+            .quickcheck(prefix_fits as fn(IpNetwork) -> bool);
+    }
+}
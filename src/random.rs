@@ -0,0 +1,316 @@
+//! Random sampling of addresses and subnets from networks, behind the `rand` feature.
+//!
+//! Every sample is drawn uniformly by filling the host bits of the network with random bits,
+//! which also works for `/0` networks.
+
+use crate::error::IpNetworkError;
+use crate::range::{bounds, network};
+use crate::{IpNetwork, IpNetworkSet, Ipv4Network, Ipv6Network};
+use rand::Rng;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+impl Ipv4Network {
+    /// Returns an address of this network chosen uniformly at random.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let net: Ipv4Network = "10.0.0.0/8".parse().unwrap();
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// assert!(net.contains(net.random_addr(&mut rng)));
+    /// ```
+    pub fn random_addr<R: Rng + ?Sized>(&self, rng: &mut R) -> Ipv4Addr {
+        let host = rng.random::<u32>() & !self.mask().to_bits();
+        Ipv4Addr::from_bits(self.network().to_bits() | host)
+    }
+
+    /// Returns a subnet of this network with prefix `new_prefix`, chosen uniformly at random
+    /// among all such subnets, with its host bits cleared.
+    ///
+    /// Returns an `IpNetworkError::InvalidPrefix` if `new_prefix` is shorter than the prefix of
+    /// this network or longer than 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let pool: Ipv4Network = "10.0.0.0/8".parse().unwrap();
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// let subnet = pool.random_subnet(&mut rng, 24).unwrap();
+    /// assert_eq!(subnet.prefix(), 24);
+    /// assert!(subnet.is_subnet_of(pool));
+    /// assert!(pool.random_subnet(&mut rng, 7).is_err());
+    /// ```
+    pub fn random_subnet<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        new_prefix: u8,
+    ) -> Result<Ipv4Network, IpNetworkError> {
+        if new_prefix < self.prefix() {
+            return Err(IpNetworkError::InvalidPrefix);
+        }
+        let subnet = Ipv4Network::new(self.random_addr(rng), new_prefix)?;
+        Ipv4Network::new(subnet.network(), new_prefix)
+    }
+}
+
+impl Ipv6Network {
+    /// Returns an address of this network chosen uniformly at random.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let net: Ipv6Network = "2001:db8::/32".parse().unwrap();
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// assert!(net.contains(net.random_addr(&mut rng)));
+    /// ```
+    pub fn random_addr<R: Rng + ?Sized>(&self, rng: &mut R) -> Ipv6Addr {
+        let host = rng.random::<u128>() & !self.mask().to_bits();
+        Ipv6Addr::from_bits(self.network().to_bits() | host)
+    }
+
+    /// Returns a subnet of this network with prefix `new_prefix`, chosen uniformly at random
+    /// among all such subnets, with its host bits cleared.
+    ///
+    /// Returns an `IpNetworkError::InvalidPrefix` if `new_prefix` is shorter than the prefix of
+    /// this network or longer than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let pool: Ipv6Network = "fd00::/8".parse().unwrap();
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// let subnet = pool.random_subnet(&mut rng, 64).unwrap();
+    /// assert_eq!(subnet.prefix(), 64);
+    /// assert!(subnet.is_subnet_of(pool));
+    /// ```
+    pub fn random_subnet<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        new_prefix: u8,
+    ) -> Result<Ipv6Network, IpNetworkError> {
+        if new_prefix < self.prefix() {
+            return Err(IpNetworkError::InvalidPrefix);
+        }
+        let subnet = Ipv6Network::new(self.random_addr(rng), new_prefix)?;
+        Ipv6Network::new(subnet.network(), new_prefix)
+    }
+}
+
+impl Ipv6Network {
+    /// Returns a locally assigned Unique Local Address `/48` with a random 40-bit Global ID,
+    /// as required by [RFC 4193, section 3.2].
+    ///
+    /// Use [`Ipv6Network::ula_subnet`] to number the `/64`s inside it.
+    ///
+    /// [RFC 4193, section 3.2]: https://www.rfc-editor.org/rfc/rfc4193#section-3.2
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// let site = Ipv6Network::random_ula(&mut rng);
+    /// assert_eq!(site.prefix(), 48);
+    /// assert!(site.is_unique_local());
+    /// let lan = site.ula_subnet(1).unwrap();
+    /// assert!(lan.is_subnet_of(site));
+    /// ```
+    pub fn random_ula<R: Rng + ?Sized>(rng: &mut R) -> Ipv6Network {
+        let global_id = u128::from(rng.random::<u64>() & 0xff_ffff_ffff);
+        let addr = Ipv6Addr::from_bits(0xfd << 120 | global_id << 80);
+        Ipv6Network::new(addr, 48).unwrap()
+    }
+}
+
+impl IpNetwork {
+    /// Returns an address of this network chosen uniformly at random.
+    ///
+    /// See [`Ipv4Network::random_addr`] and [`Ipv6Network::random_addr`].
+    pub fn random_addr<R: Rng + ?Sized>(&self, rng: &mut R) -> IpAddr {
+        match self {
+            IpNetwork::V4(net) => IpAddr::V4(net.random_addr(rng)),
+            IpNetwork::V6(net) => IpAddr::V6(net.random_addr(rng)),
+        }
+    }
+
+    /// Returns a subnet of this network with prefix `new_prefix`, chosen uniformly at random
+    /// among all such subnets, with its host bits cleared.
+    ///
+    /// See [`Ipv4Network::random_subnet`] and [`Ipv6Network::random_subnet`].
+    pub fn random_subnet<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        new_prefix: u8,
+    ) -> Result<IpNetwork, IpNetworkError> {
+        match self {
+            IpNetwork::V4(net) => net.random_subnet(rng, new_prefix).map(IpNetwork::V4),
+            IpNetwork::V6(net) => net.random_subnet(rng, new_prefix).map(IpNetwork::V6),
+        }
+    }
+}
+
+impl IpNetworkSet {
+    /// Returns an address of this set chosen uniformly at random, so each network is picked
+    /// with a probability proportional to its size, or `None` if the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkSet};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let sources: IpNetworkSet = ["10.0.0.0/8", "192.0.2.0/24", "2001:db8::/32"]
+    ///     .iter()
+    ///     .map(|n| n.parse::<IpNetwork>().unwrap())
+    ///     .collect();
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// assert!(sources.contains(sources.random_addr(&mut rng).unwrap()));
+    /// assert_eq!(IpNetworkSet::new().random_addr(&mut rng), None);
+    /// ```
+    pub fn random_addr<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<IpAddr> {
+        let size = self.size();
+        let ipv4 = u128::from(size.ipv4());
+        // The index of the address counts the IPv4 addresses first. With all IPv6 addresses,
+        // there can be up to 2^128 + 2^32 of them, so indexes past a `u128` carry a high bit
+        // and are drawn as 129 random bits until one is in range.
+        let (high, index) = match size.ipv6().and_then(|ipv6| ipv6.checked_add(ipv4)) {
+            Some(0) => return None,
+            Some(total) => (false, rng.random_range(0..total)),
+            None => {
+                let excess = size.ipv6().map_or(ipv4, |ipv6| ipv6.wrapping_add(ipv4));
+                loop {
+                    let (high, low) = (rng.random::<bool>(), rng.random::<u128>());
+                    if !high || low < excess {
+                        break (high, low);
+                    }
+                }
+            }
+        };
+        let (v6, mut offset) = if !high && index < ipv4 {
+            (false, index)
+        } else {
+            // Less than the number of IPv6 addresses, so the wrapped difference is exact.
+            (true, index.wrapping_sub(ipv4))
+        };
+        for &net in self.iter().filter(|net| net.is_ipv6() == v6) {
+            let (bits, start, end) = bounds(net);
+            if offset <= end - start {
+                return Some(network(bits, start + offset, bits).ip());
+            }
+            offset -= end - start + 1;
+        }
+        unreachable!("the index is within the size of the set")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn random_addr_covers_network() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let net: IpNetwork = "192.0.2.4/30".parse().unwrap();
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            let IpAddr::V4(ip) = net.random_addr(&mut rng) else {
+                panic!("sampled an address of the wrong family");
+            };
+            seen[usize::from(ip.octets()[3] - 4)] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+
+        let host: IpNetwork = "2001:db8::1/128".parse().unwrap();
+        assert_eq!(host.random_addr(&mut rng), host.ip());
+        let all: IpNetwork = "0.0.0.0/0".parse().unwrap();
+        assert!(all.contains(all.random_addr(&mut rng)));
+    }
+
+    #[test]
+    fn random_subnet_bounds() {
+        let mut rng = SmallRng::seed_from_u64(2);
+        let net: IpNetwork = "192.0.2.0/24".parse().unwrap();
+        assert_eq!(net.random_subnet(&mut rng, 24), Ok(net));
+        assert_eq!(
+            net.random_subnet(&mut rng, 23),
+            Err(IpNetworkError::InvalidPrefix)
+        );
+        assert_eq!(
+            net.random_subnet(&mut rng, 33),
+            Err(IpNetworkError::InvalidPrefix)
+        );
+        let mut seen = [false; 2];
+        for _ in 0..50 {
+            let half = net.random_subnet(&mut rng, 25).unwrap();
+            assert_eq!(half.ip(), half.network());
+            seen[usize::from(half.ip() != net.ip())] = true;
+        }
+        assert_eq!(seen, [true; 2]);
+
+        let all: Ipv6Network = "::/0".parse().unwrap();
+        let host = all.random_subnet(&mut rng, 128).unwrap();
+        assert_eq!(host.prefix(), 128);
+    }
+
+    #[test]
+    fn random_set_addr_is_uniform() {
+        let mut rng = SmallRng::seed_from_u64(4);
+        let set: IpNetworkSet = ["10.0.0.0/31", "10.0.1.0/30", "::1/128"]
+            .iter()
+            .map(|n| n.parse::<IpNetwork>().unwrap())
+            .collect();
+        let mut counts = std::collections::BTreeMap::new();
+        for _ in 0..7000 {
+            *counts
+                .entry(set.random_addr(&mut rng).unwrap())
+                .or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 7);
+        assert!(counts.keys().all(|&ip| set.contains(ip)));
+        assert!(
+            counts.values().all(|&n| (800..1200).contains(&n)),
+            "{counts:?}"
+        );
+
+        let all: IpNetworkSet = ["0.0.0.0/0", "::/0"]
+            .iter()
+            .map(|n| n.parse::<IpNetwork>().unwrap())
+            .collect();
+        for _ in 0..100 {
+            // IPv4 addresses are picked with probability 2^32 / (2^128 + 2^32).
+            assert!(all.random_addr(&mut rng).unwrap().is_ipv6());
+        }
+    }
+
+    #[test]
+    fn random_ula_layout() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let a = Ipv6Network::random_ula(&mut rng);
+        let b = Ipv6Network::random_ula(&mut rng);
+        assert_ne!(a, b);
+        for site in [a, b] {
+            assert_eq!(site.ip(), site.network());
+            assert_eq!(site.ip().segments()[0] >> 8, 0xfd);
+            assert_eq!(
+                site.ula_subnet(0xffff).unwrap().broadcast(),
+                site.broadcast()
+            );
+        }
+    }
+}
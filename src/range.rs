@@ -0,0 +1,159 @@
+//! Conversions between networks and inclusive ranges of addresses stored as integers.
+//!
+//! IPv4 addresses are widened to `u128`, so the same arithmetic serves both families. The
+//! width of the family is passed along as the number of address bits.
+
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+pub(crate) const IPV4_BITS: u8 = 32;
+pub(crate) const IPV6_BITS: u8 = 128;
+
+/// Returns the number of address bits of the family of `net` and its first and last address.
+pub(crate) fn bounds(net: IpNetwork) -> (u8, u128, u128) {
+    match net {
+        IpNetwork::V4(net) => (
+            IPV4_BITS,
+            net.network().to_bits().into(),
+            net.broadcast().to_bits().into(),
+        ),
+        IpNetwork::V6(net) => (
+            IPV6_BITS,
+            net.network().to_bits(),
+            net.broadcast().to_bits(),
+        ),
+    }
+}
+
+/// Builds the network of the family with `bits` address bits starting at `start`.
+pub(crate) fn network(bits: u8, start: u128, prefix: u8) -> IpNetwork {
+    if bits == IPV4_BITS {
+        let addr = Ipv4Addr::from_bits(start as u32);
+        IpNetwork::V4(Ipv4Network::new_checked(addr, prefix).unwrap())
+    } else {
+        let addr = Ipv6Addr::from_bits(start);
+        IpNetwork::V6(Ipv6Network::new_checked(addr, prefix).unwrap())
+    }
+}
+
+/// Appends the minimal list of networks covering the addresses from `start` to `end`, both
+/// inclusive, to `out`.
+pub(crate) fn push_cidrs(bits: u8, mut start: u128, end: u128, out: &mut Vec<IpNetwork>) {
+    debug_assert!(start <= end);
+    loop {
+        // The largest block must both start aligned at `start` and fit before `end`.
+        let span = end - start;
+        let fit = match span.checked_add(1) {
+            Some(count) => 127 - count.leading_zeros(),
+            None => 128,
+        };
+        let align = start.trailing_zeros();
+        let host_bits = fit.min(align).min(u32::from(bits)) as u8;
+        out.push(network(bits, start, bits - host_bits));
+
+        if host_bits == 128 {
+            return;
+        }
+        let last = start + ((1 << host_bits) - 1);
+        if last >= end {
+            return;
+        }
+        start = last + 1;
+    }
+}
+
+/// A boundary between covered and uncovered addresses: either the first address of a range,
+/// or the address right after its end. `None` stands for the end of the address space.
+type Boundary = (u8, Option<u128>);
+
+/// Returns the boundaries of the ranges covered by `networks`.
+fn boundaries(networks: &[IpNetwork]) -> Vec<Boundary> {
+    networks
+        .iter()
+        .flat_map(|&net| {
+            let (bits, start, end) = bounds(net);
+            let after = match bits {
+                IPV4_BITS if end == u128::from(u32::MAX) => None,
+                _ => end.checked_add(1),
+            };
+            [(bits, Some(start)), (bits, after)]
+        })
+        .collect()
+}
+
+/// Returns the minimal list of networks covering the addresses in exactly one of `a` and `b`.
+///
+/// Both inputs are merged first, so overlapping networks within one input are fine.
+pub(crate) fn symmetric_difference(a: &[IpNetwork], b: &[IpNetwork]) -> Vec<IpNetwork> {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    crate::ip_merge_in_place(&mut a);
+    crate::ip_merge_in_place(&mut b);
+
+    // After merging, the ranges of each input are disjoint, so crossing a boundary flips
+    // whether an address is covered by exactly one input. Boundaries at the same address
+    // cancel out in pairs, and the remaining ones delimit the result.
+    let mut points = boundaries(&a);
+    points.extend(boundaries(&b));
+    // `None` sorts first in `Option`, so order the end of the address space last explicitly.
+    points.sort_unstable_by_key(|&(bits, at)| (bits, at.is_none(), at));
+    let mut toggles: Vec<Boundary> = Vec::with_capacity(points.len());
+    for point in points {
+        if toggles.last() == Some(&point) {
+            toggles.pop();
+        } else {
+            toggles.push(point);
+        }
+    }
+
+    let mut out = Vec::new();
+    for pair in toggles.chunks_exact(2) {
+        let (bits, start) = pair[0];
+        let start = start.expect("a range cannot start past the address space");
+        let end = match pair[1].1 {
+            Some(after) => after - 1,
+            None if bits == IPV4_BITS => u128::from(u32::MAX),
+            None => u128::MAX,
+        };
+        push_cidrs(bits, start, end, &mut out);
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn nets(nets: &[&str]) -> Vec<IpNetwork> {
+        nets.iter().map(|n| n.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn push_cidrs_unaligned() {
+        let mut out = Vec::new();
+        push_cidrs(IPV4_BITS, 1, 6, &mut out);
+        assert_eq!(
+            out,
+            nets(&["0.0.0.1/32", "0.0.0.2/31", "0.0.0.4/31", "0.0.0.6/32"])
+        );
+    }
+
+    #[test]
+    fn push_cidrs_whole_space() {
+        let mut out = Vec::new();
+        push_cidrs(IPV4_BITS, 0, u32::MAX.into(), &mut out);
+        push_cidrs(IPV6_BITS, 0, u128::MAX, &mut out);
+        assert_eq!(out, nets(&["0.0.0.0/0", "::/0"]));
+    }
+
+    #[test]
+    fn symmetric_difference_cancels_shared_boundaries() {
+        let a = nets(&["10.0.0.0/24", "::/0"]);
+        let b = nets(&["10.0.0.0/25", "10.0.1.0/24", "::/1"]);
+        assert_eq!(
+            symmetric_difference(&a, &b),
+            nets(&["10.0.0.128/25", "10.0.1.0/24", "8000::/1"])
+        );
+        assert_eq!(symmetric_difference(&a, &a), Vec::new());
+    }
+}
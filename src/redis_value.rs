@@ -0,0 +1,75 @@
+//! Conversions for storing networks in Redis keys and values as strings in CIDR notation.
+
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use redis::{FromRedisValue, ParsingError, RedisWrite, ToRedisArgs, Value};
+use std::{fmt, str::FromStr};
+
+/// Reads `v` as a string and parses it as a network.
+fn parse_value<T>(v: Value) -> Result<T, ParsingError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let s = String::from_redis_value(v)?;
+    s.parse()
+        .map_err(|e| ParsingError::from(format!("invalid network {:?}: {}", s, e)))
+}
+
+impl ToRedisArgs for Ipv4Network {
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        out.write_arg_fmt(self)
+    }
+}
+
+impl FromRedisValue for Ipv4Network {
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        parse_value(v)
+    }
+}
+
+impl ToRedisArgs for Ipv6Network {
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        out.write_arg_fmt(self)
+    }
+}
+
+impl FromRedisValue for Ipv6Network {
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        parse_value(v)
+    }
+}
+
+impl ToRedisArgs for IpNetwork {
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        out.write_arg_fmt(self)
+    }
+}
+
+impl FromRedisValue for IpNetwork {
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        parse_value(v)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn redis_round_trip() {
+        let net: IpNetwork = "192.0.2.0/24".parse().unwrap();
+        assert_eq!(net.to_redis_args(), vec![b"192.0.2.0/24".to_vec()]);
+
+        let value = Value::BulkString(b"2001:db8::/32".to_vec());
+        assert_eq!(
+            IpNetwork::from_redis_value(value),
+            Ok("2001:db8::/32".parse().unwrap())
+        );
+        let value = Value::SimpleString("10.0.0.0/8".to_string());
+        assert!(Ipv4Network::from_redis_value(value).is_ok());
+
+        let value = Value::BulkString(b"10.0.0.0/8".to_vec());
+        assert!(Ipv6Network::from_redis_value(value).is_err());
+        assert!(IpNetwork::from_redis_value(Value::Nil).is_err());
+    }
+}
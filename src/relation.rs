@@ -0,0 +1,73 @@
+/// Describes how the address ranges of two networks relate to each other.
+///
+/// Returned by [`IpNetwork::relation`](crate::IpNetwork::relation) and the equivalent methods
+/// on [`Ipv4Network`](crate::Ipv4Network) and [`Ipv6Network`](crate::Ipv6Network). Host bits
+/// are ignored, so only the covered addresses are compared.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum NetworkRelation {
+    /// Both networks cover exactly the same addresses.
+    Equal,
+    /// The first network is strictly contained in the second one.
+    SubsetOf,
+    /// The first network strictly contains the second one.
+    SupersetOf,
+    /// The networks share some, but not all, addresses and neither contains the other.
+    ///
+    /// Two CIDR networks are always either nested or disjoint, so this is only produced when
+    /// comparing ranges that are not aligned on a prefix boundary.
+    Overlapping,
+    /// The networks have no address in common.
+    Disjoint,
+}
+
+impl NetworkRelation {
+    /// Classifies the inclusive ranges `a` and `b`.
+    pub(crate) fn between<T: Ord>(a: (T, T), b: (T, T)) -> NetworkRelation {
+        let (a_start, a_end) = a;
+        let (b_start, b_end) = b;
+        if a_end < b_start || b_end < a_start {
+            NetworkRelation::Disjoint
+        } else if a_start == b_start && a_end == b_end {
+            NetworkRelation::Equal
+        } else if b_start <= a_start && a_end <= b_end {
+            NetworkRelation::SubsetOf
+        } else if a_start <= b_start && b_end <= a_end {
+            NetworkRelation::SupersetOf
+        } else {
+            NetworkRelation::Overlapping
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn relation_between_ranges() {
+        assert_eq!(
+            NetworkRelation::between((0, 9), (0, 9)),
+            NetworkRelation::Equal
+        );
+        assert_eq!(
+            NetworkRelation::between((2, 3), (0, 9)),
+            NetworkRelation::SubsetOf
+        );
+        assert_eq!(
+            NetworkRelation::between((0, 9), (9, 9)),
+            NetworkRelation::SupersetOf
+        );
+        assert_eq!(
+            NetworkRelation::between((0, 5), (5, 9)),
+            NetworkRelation::Overlapping
+        );
+        assert_eq!(
+            NetworkRelation::between((0, 4), (5, 9)),
+            NetworkRelation::Disjoint
+        );
+        assert_eq!(
+            NetworkRelation::between((6, 9), (0, 5)),
+            NetworkRelation::Disjoint
+        );
+    }
+}
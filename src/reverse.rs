@@ -0,0 +1,355 @@
+//! Reverse DNS names in the `in-addr.arpa` and `ip6.arpa` trees.
+//!
+//! Names are written without the trailing root dot. Reverse zones are delegated on label
+//! boundaries, which are octets for IPv4 and nibbles for IPv6, so a network whose prefix does
+//! not fall on a boundary is covered by several zones.
+
+use crate::error::IpNetworkError;
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use std::fmt::Write;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Returns the name of the reverse zone holding the first `labels` octets of `addr`.
+fn ipv4_zone(addr: Ipv4Addr, labels: usize) -> String {
+    let mut name = String::new();
+    for octet in addr.octets()[..labels].iter().rev() {
+        write!(name, "{octet}.").unwrap();
+    }
+    name.push_str("in-addr.arpa");
+    name
+}
+
+/// Returns the name of the reverse zone holding the first `labels` nibbles of `addr`.
+fn ipv6_zone(addr: Ipv6Addr, labels: usize) -> String {
+    let bits = addr.to_bits();
+    let mut name = String::new();
+    for i in (0..labels).rev() {
+        write!(name, "{:x}.", (bits >> (124 - 4 * i)) & 0xf).unwrap();
+    }
+    name.push_str("ip6.arpa");
+    name
+}
+
+/// Returns the `in-addr.arpa` or `ip6.arpa` name of the PTR record for `ip`.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::ptr_record_name;
+///
+/// assert_eq!(ptr_record_name("192.0.2.5".parse().unwrap()), "5.2.0.192.in-addr.arpa");
+///
+/// let name = ptr_record_name("2001:db8::1".parse().unwrap());
+/// assert!(name.starts_with("1.0.0.0.0.0.0.0."));
+/// assert!(name.ends_with(".8.b.d.0.1.0.0.2.ip6.arpa"));
+/// ```
+pub fn ptr_record_name(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(ip) => ipv4_zone(ip, 4),
+        IpAddr::V6(ip) => ipv6_zone(ip, 32),
+    }
+}
+
+impl Ipv4Network {
+    /// Returns the `in-addr.arpa` zones that together hold the PTR records of this network.
+    ///
+    /// A prefix on an octet boundary gives a single zone. Any other prefix is rounded up to
+    /// the next boundary, giving one zone per subnet of that size, up to 128 for a `/25`.
+    /// See [`Ipv4Network::classless_reverse_zone`] for delegating such small networks instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.168.0.0/16".parse().unwrap();
+    /// assert_eq!(net.reverse_zones(), ["168.192.in-addr.arpa"]);
+    ///
+    /// let net: Ipv4Network = "10.4.0.0/15".parse().unwrap();
+    /// assert_eq!(net.reverse_zones(), ["4.10.in-addr.arpa", "5.10.in-addr.arpa"]);
+    /// ```
+    pub fn reverse_zones(&self) -> Vec<String> {
+        let labels = usize::from(self.prefix()).div_ceil(8);
+        let extra_bits = labels as u32 * 8 - u32::from(self.prefix());
+        let step = 1u32.checked_shl(32 - labels as u32 * 8).unwrap_or(0);
+        let start = self.network().to_bits();
+        (0..1u32 << extra_bits)
+            .map(|i| ipv4_zone(Ipv4Addr::from_bits(start + i * step), labels))
+            .collect()
+    }
+
+    /// Returns the [RFC 2317] classless delegation zone for a network of `/25` to `/32`, or
+    /// `None` for a shorter prefix.
+    ///
+    /// The zone is named after the first address and the prefix length, inside the zone of
+    /// the enclosing `/24`, such as `0/25.2.0.192.in-addr.arpa` for `192.0.2.0/25`.
+    ///
+    /// [RFC 2317]: https://tools.ietf.org/html/rfc2317
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.0.2.128/26".parse().unwrap();
+    /// assert_eq!(
+    ///     net.classless_reverse_zone().as_deref(),
+    ///     Some("128/26.2.0.192.in-addr.arpa")
+    /// );
+    /// assert_eq!("192.0.2.0/24".parse::<Ipv4Network>().unwrap().classless_reverse_zone(), None);
+    /// ```
+    pub fn classless_reverse_zone(&self) -> Option<String> {
+        if self.prefix() < 25 {
+            return None;
+        }
+        let network = self.network();
+        Some(format!(
+            "{}/{}.{}",
+            network.octets()[3],
+            self.prefix(),
+            ipv4_zone(network, 3)
+        ))
+    }
+
+    /// Returns the CNAME records that point the PTR names of this network into its
+    /// [RFC 2317] classless delegation zone, as `(owner, target)` pairs, or `None` for a
+    /// prefix shorter than `/25`.
+    ///
+    /// The owners belong in the zone of the enclosing `/24`, one per address of the network.
+    ///
+    /// [RFC 2317]: https://tools.ietf.org/html/rfc2317
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv4Network;
+    ///
+    /// let net: Ipv4Network = "192.0.2.0/25".parse().unwrap();
+    /// let cnames = net.classless_cname_records().unwrap();
+    /// assert_eq!(cnames.len(), 128);
+    /// assert_eq!(cnames[1].0, "1.2.0.192.in-addr.arpa");
+    /// assert_eq!(cnames[1].1, "1.0/25.2.0.192.in-addr.arpa");
+    /// ```
+    pub fn classless_cname_records(&self) -> Option<Vec<(String, String)>> {
+        let zone = self.classless_reverse_zone()?;
+        let records = self
+            .iter()
+            .map(|ip| {
+                let host = ip.octets()[3];
+                (ipv4_zone(ip, 4), format!("{host}.{zone}"))
+            })
+            .collect();
+        Some(records)
+    }
+}
+
+impl Ipv6Network {
+    /// Returns the `ip6.arpa` zones that together hold the PTR records of this network.
+    ///
+    /// A prefix on a nibble boundary gives a single zone. Any other prefix is rounded up to
+    /// the next boundary, giving up to eight zones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::Ipv6Network;
+    ///
+    /// let net: Ipv6Network = "2001:db8::/32".parse().unwrap();
+    /// assert_eq!(net.reverse_zones(), ["8.b.d.0.1.0.0.2.ip6.arpa"]);
+    ///
+    /// let net: Ipv6Network = "2001:db8::/31".parse().unwrap();
+    /// assert_eq!(
+    ///     net.reverse_zones(),
+    ///     ["8.b.d.0.1.0.0.2.ip6.arpa", "9.b.d.0.1.0.0.2.ip6.arpa"]
+    /// );
+    /// ```
+    pub fn reverse_zones(&self) -> Vec<String> {
+        let labels = usize::from(self.prefix()).div_ceil(4);
+        let extra_bits = labels as u32 * 4 - u32::from(self.prefix());
+        let step = 1u128.checked_shl(128 - labels as u32 * 4).unwrap_or(0);
+        let start = self.network().to_bits();
+        (0..1u128 << extra_bits)
+            .map(|i| ipv6_zone(Ipv6Addr::from_bits(start + i * step), labels))
+            .collect()
+    }
+}
+
+/// Returns the labels of `name` in front of the `zone` suffix, compared case-insensitively.
+fn labels_under<'a>(name: &'a str, zone: &str) -> Option<&'a str> {
+    let split = name.len().checked_sub(zone.len())?;
+    let (labels, suffix) = (name.get(..split)?, name.get(split..)?);
+    if !suffix.eq_ignore_ascii_case(zone) {
+        return None;
+    }
+    match labels {
+        "" => Some(""),
+        _ => labels.strip_suffix('.').filter(|labels| !labels.is_empty()),
+    }
+}
+
+impl IpNetwork {
+    /// Returns the `in-addr.arpa` or `ip6.arpa` zones that together hold the PTR records of
+    /// this network.
+    ///
+    /// See [`Ipv4Network::reverse_zones`] and [`Ipv6Network::reverse_zones`].
+    pub fn reverse_zones(&self) -> Vec<String> {
+        match self {
+            IpNetwork::V4(net) => net.reverse_zones(),
+            IpNetwork::V6(net) => net.reverse_zones(),
+        }
+    }
+
+    /// Parses an `in-addr.arpa` or `ip6.arpa` name into the network it covers.
+    ///
+    /// Each label fixes one octet or nibble, so the prefix is 8 bits per label for IPv4 and 4
+    /// bits per label for IPv6. The suffix is matched case-insensitively and a trailing root
+    /// dot is accepted.
+    ///
+    /// Returns an `IpNetworkError::InvalidCidrFormat` if the name is not in either tree, has
+    /// too many labels, or a label is not a decimal octet or a single hexadecimal digit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetwork;
+    ///
+    /// let net = IpNetwork::from_reverse_zone("0.168.192.in-addr.arpa").unwrap();
+    /// assert_eq!(net, "192.168.0.0/24".parse().unwrap());
+    ///
+    /// let net = IpNetwork::from_reverse_zone("8.b.d.0.1.0.0.2.ip6.arpa.").unwrap();
+    /// assert_eq!(net, "2001:db8::/32".parse().unwrap());
+    ///
+    /// assert!(IpNetwork::from_reverse_zone("example.com").is_err());
+    /// ```
+    pub fn from_reverse_zone(name: &str) -> Result<IpNetwork, IpNetworkError> {
+        let invalid = || {
+            IpNetworkError::InvalidCidrFormat(format!(
+                "expected an in-addr.arpa or ip6.arpa name: {name}"
+            ))
+        };
+        let trimmed = name.strip_suffix('.').unwrap_or(name);
+        if let Some(labels) = labels_under(trimmed, "in-addr.arpa") {
+            let mut octets = [0u8; 4];
+            let mut count = 0;
+            for label in labels.rsplit('.').filter(|_| !labels.is_empty()) {
+                let canonical = label.len() <= 3
+                    && label.bytes().all(|b| b.is_ascii_digit())
+                    && (label == "0" || !label.starts_with('0'));
+                let octet = octets.get_mut(count).ok_or_else(invalid)?;
+                *octet = label
+                    .parse()
+                    .ok()
+                    .filter(|_| canonical)
+                    .ok_or_else(invalid)?;
+                count += 1;
+            }
+            let net = Ipv4Network::new(Ipv4Addr::from(octets), count as u8 * 8)?;
+            Ok(IpNetwork::V4(net))
+        } else if let Some(labels) = labels_under(trimmed, "ip6.arpa") {
+            let mut bits = 0u128;
+            let mut count = 0;
+            for label in labels.rsplit('.').filter(|_| !labels.is_empty()) {
+                let nibble = match label.as_bytes() {
+                    [digit] => char::from(*digit).to_digit(16).ok_or_else(invalid)?,
+                    _ => return Err(invalid()),
+                };
+                if count == 32 {
+                    return Err(invalid());
+                }
+                bits |= u128::from(nibble) << (124 - 4 * count);
+                count += 1;
+            }
+            let net = Ipv6Network::new(Ipv6Addr::from_bits(bits), count as u8 * 4)?;
+            Ok(IpNetwork::V6(net))
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reverse_zones_boundaries() {
+        let zones = |s: &str| s.parse::<IpNetwork>().unwrap().reverse_zones();
+        assert_eq!(zones("0.0.0.0/0"), ["in-addr.arpa"]);
+        assert_eq!(zones("::/0"), ["ip6.arpa"]);
+        assert_eq!(zones("0.0.0.0/1").len(), 128);
+        assert_eq!(zones("128.0.0.0/1")[127], "255.in-addr.arpa");
+        assert_eq!(zones("192.0.2.7/32"), ["7.2.0.192.in-addr.arpa"]);
+        let hosts = zones("192.0.2.128/25");
+        assert_eq!(hosts.len(), 128);
+        assert_eq!(hosts[0], "128.2.0.192.in-addr.arpa");
+        assert_eq!(hosts[127], "255.2.0.192.in-addr.arpa");
+        assert_eq!(zones("ffff::/15"), ["e.f.f.f.ip6.arpa", "f.f.f.f.ip6.arpa"]);
+        let last = zones("ffff:ffff:ffff:ffff:ffff:ffff:ffff:fff8/125");
+        assert_eq!(last.len(), 8);
+        assert_eq!(
+            last[7],
+            ptr_record_name("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn classless_delegation() {
+        let net = |s: &str| s.parse::<Ipv4Network>().unwrap();
+        assert_eq!(net("10.0.0.0/24").classless_reverse_zone(), None);
+        assert_eq!(net("10.0.0.0/24").classless_cname_records(), None);
+        assert_eq!(
+            net("10.0.0.255/32").classless_reverse_zone().as_deref(),
+            Some("255/32.0.0.10.in-addr.arpa")
+        );
+        assert_eq!(
+            net("10.0.0.99/27").classless_cname_records().unwrap()[31],
+            (
+                "127.0.0.10.in-addr.arpa".to_string(),
+                "127.96/27.0.0.10.in-addr.arpa".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn from_reverse_zone_round_trip() {
+        for net in [
+            "0.0.0.0/0",
+            "10.0.0.0/8",
+            "192.0.2.7/32",
+            "::/0",
+            "2001:db8:f::/52",
+        ] {
+            let net: IpNetwork = net.parse().unwrap();
+            for zone in net.reverse_zones() {
+                assert_eq!(IpNetwork::from_reverse_zone(&zone), Ok(net));
+            }
+        }
+        let ip = "2001:db8::abcd".parse().unwrap();
+        assert_eq!(
+            IpNetwork::from_reverse_zone(&ptr_record_name(ip)),
+            Ok(IpNetwork::from(ip))
+        );
+        assert_eq!(
+            IpNetwork::from_reverse_zone("2.0.192.IN-ADDR.ARPA."),
+            Ok("192.0.2.0/24".parse().unwrap())
+        );
+        for name in [
+            "",
+            "arpa",
+            ".in-addr.arpa",
+            "1..in-addr.arpa",
+            "1.2.3.4.5.in-addr.arpa",
+            "256.in-addr.arpa",
+            "01.in-addr.arpa",
+            "+1.in-addr.arpa",
+            "10.ip6.arpa",
+            "g.ip6.arpa",
+            "1.2in-addr.arpa",
+            "2.0.192.in-addr.arpa..",
+        ] {
+            assert!(IpNetwork::from_reverse_zone(name).is_err(), "{name}");
+        }
+        let too_long = "0.".repeat(33) + "ip6.arpa";
+        assert!(IpNetwork::from_reverse_zone(&too_long).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! Conversions for using networks as sea-orm entity columns.
+//!
+//! Networks are bound and read as strings in CIDR notation, so the backing column is `TEXT` on
+//! every backend. On Postgres, an `INET` or `CIDR` column can be used by casting it to `text`
+//! when selecting and back when inserting.
+
+use crate::{IpNetwork, IpNetworkError, Ipv4Network, Ipv6Network};
+use sea_orm::sea_query::{ArrayType, ColumnType, Nullable, Value, ValueType, ValueTypeErr};
+use sea_orm::{ColIdx, DbErr, QueryResult, TryGetError, TryGetable};
+use std::str::FromStr;
+
+/// Parses a network out of a string value, rejecting every other kind of value.
+fn parse_value<T: FromStr<Err = IpNetworkError>>(v: Value) -> Result<T, ValueTypeErr> {
+    match v {
+        Value::String(Some(s)) => s.parse().map_err(|_| ValueTypeErr),
+        _ => Err(ValueTypeErr),
+    }
+}
+
+/// Reads the column at `index` as a string and parses it as a network.
+fn get_parsed<T, I>(res: &QueryResult, index: I) -> Result<T, TryGetError>
+where
+    T: FromStr<Err = IpNetworkError>,
+    I: ColIdx,
+{
+    let s = String::try_get_by(res, index)?;
+    s.parse()
+        .map_err(|e| TryGetError::DbErr(DbErr::Type(format!("invalid network {:?}: {}", s, e))))
+}
+
+fn null_string() -> Value {
+    Value::String(None)
+}
+
+fn string_column() -> ColumnType {
+    ColumnType::Text
+}
+
+impl From<Ipv4Network> for Value {
+    fn from(net: Ipv4Network) -> Value {
+        Value::String(Some(Box::new(net.to_string())))
+    }
+}
+
+impl ValueType for Ipv4Network {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        parse_value(v)
+    }
+
+    fn type_name() -> String {
+        "Ipv4Network".to_owned()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::String
+    }
+
+    fn column_type() -> ColumnType {
+        string_column()
+    }
+}
+
+impl Nullable for Ipv4Network {
+    fn null() -> Value {
+        null_string()
+    }
+}
+
+impl TryGetable for Ipv4Network {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        get_parsed(res, index)
+    }
+}
+
+impl From<Ipv6Network> for Value {
+    fn from(net: Ipv6Network) -> Value {
+        Value::String(Some(Box::new(net.to_string())))
+    }
+}
+
+impl ValueType for Ipv6Network {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        parse_value(v)
+    }
+
+    fn type_name() -> String {
+        "Ipv6Network".to_owned()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::String
+    }
+
+    fn column_type() -> ColumnType {
+        string_column()
+    }
+}
+
+impl Nullable for Ipv6Network {
+    fn null() -> Value {
+        null_string()
+    }
+}
+
+impl TryGetable for Ipv6Network {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        get_parsed(res, index)
+    }
+}
+
+impl From<IpNetwork> for Value {
+    fn from(net: IpNetwork) -> Value {
+        Value::String(Some(Box::new(net.to_string())))
+    }
+}
+
+impl ValueType for IpNetwork {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        parse_value(v)
+    }
+
+    fn type_name() -> String {
+        "IpNetwork".to_owned()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::String
+    }
+
+    fn column_type() -> ColumnType {
+        string_column()
+    }
+}
+
+impl Nullable for IpNetwork {
+    fn null() -> Value {
+        null_string()
+    }
+}
+
+impl TryGetable for IpNetwork {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        get_parsed(res, index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sea_orm_value_round_trip() {
+        let net: IpNetwork = "2001:db8::/32".parse().unwrap();
+        let value = Value::from(net);
+        assert_eq!(value, Value::String(Some(Box::new("2001:db8::/32".into()))));
+        assert_eq!(<IpNetwork as ValueType>::try_from(value).unwrap(), net);
+
+        let net: Ipv4Network = "10.0.0.0/8".parse().unwrap();
+        assert_eq!(Ipv4Network::unwrap(net.into()), net);
+        assert_eq!(
+            <Option<Ipv4Network> as ValueType>::unwrap(Ipv4Network::null()),
+            None
+        );
+    }
+
+    #[test]
+    fn sea_orm_value_rejects_other_values() {
+        assert!(<Ipv6Network as ValueType>::try_from(Value::Int(Some(1))).is_err());
+        assert!(<Ipv6Network as ValueType>::try_from(Value::String(None)).is_err());
+        let bad = Value::String(Some(Box::new("10.0.0.0/8".into())));
+        assert!(<Ipv6Network as ValueType>::try_from(bad).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+//! Externally tagged serde representation for `IpNetwork`.
+//!
+//! By default `IpNetwork` serializes to its plain CIDR string. This module can be used with
+//! `#[serde(with = "ipnetwork::serde_tagged")]` to instead make the address family explicit,
+//! as in `{"v4": "10.0.0.0/8"}` or `{"v6": "2001:db8::/32"}`.
+//!
+//! # Examples
+//!
+//! ```
+//! use ipnetwork::IpNetwork;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Route {
+//!     #[serde(with = "ipnetwork::serde_tagged")]
+//!     dest: IpNetwork,
+//! }
+//!
+//! let route = Route { dest: "10.0.0.0/8".parse().unwrap() };
+//! let json = serde_json::to_string(&route).unwrap();
+//! assert_eq!(json, r#"{"dest":{"v4":"10.0.0.0/8"}}"#);
+//!
+//! let route: Route = serde_json::from_str(r#"{"dest":{"v6":"2001:db8::/32"}}"#).unwrap();
+//! assert_eq!(route.dest, "2001:db8::/32".parse::<IpNetwork>().unwrap());
+//! ```
+
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use serde::de::{self, EnumAccess, VariantAccess, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+
+const VARIANTS: &[&str] = &["v4", "v6"];
+
+/// Serializes an `IpNetwork` as a single-entry map keyed by its address family.
+pub fn serialize<S>(network: &IpNetwork, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match network {
+        IpNetwork::V4(net) => serializer.serialize_newtype_variant("IpNetwork", 0, "v4", net),
+        IpNetwork::V6(net) => serializer.serialize_newtype_variant("IpNetwork", 1, "v6", net),
+    }
+}
+
+/// Deserializes an `IpNetwork` from a single-entry map keyed by its address family.
+///
+/// The network string must match the family named by the key.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<IpNetwork, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_enum("IpNetwork", VARIANTS, TaggedVisitor)
+}
+
+enum Family {
+    V4,
+    V6,
+}
+
+impl<'de> serde::Deserialize<'de> for Family {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(FamilyVisitor)
+    }
+}
+
+struct FamilyVisitor;
+
+impl Visitor<'_> for FamilyVisitor {
+    type Value = Family;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("`v4` or `v6`")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Family, E> {
+        match value {
+            0 => Ok(Family::V4),
+            1 => Ok(Family::V6),
+            _ => Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(value),
+                &self,
+            )),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Family, E> {
+        match value {
+            "v4" => Ok(Family::V4),
+            "v6" => Ok(Family::V6),
+            _ => Err(de::Error::unknown_variant(value, VARIANTS)),
+        }
+    }
+}
+
+struct TaggedVisitor;
+
+impl<'de> Visitor<'de> for TaggedVisitor {
+    type Value = IpNetwork;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an IP network tagged with its address family")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<IpNetwork, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        match data.variant()? {
+            (Family::V4, v) => v.newtype_variant::<Ipv4Network>().map(IpNetwork::V4),
+            (Family::V6, v) => v.newtype_variant::<Ipv6Network>().map(IpNetwork::V6),
+        }
+    }
+}
@@ -0,0 +1,725 @@
+use crate::merge::{merge_in_place, Merge};
+use crate::range::{bounds, push_cidrs, IPV4_BITS};
+use crate::{
+    ip_find_containing, summarize_address_range, IpNetwork, IpNetworkError, IpNetworkIterator,
+};
+use std::iter::FusedIterator;
+use std::net::IpAddr;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+
+/// A set of addresses, stored as the minimal sorted list of networks covering them.
+///
+/// Networks are merged on insertion, so overlapping and adjacent networks collapse, and the
+/// set operations work on the addresses covered rather than on the networks inserted.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{IpNetwork, IpNetworkSet};
+///
+/// let parse = |nets: &[&str]| -> IpNetworkSet {
+///     nets.iter().map(|n| n.parse::<IpNetwork>().unwrap()).collect()
+/// };
+/// let allow = parse(&["10.0.0.0/8", "192.0.2.0/24"]);
+/// let deny = parse(&["10.128.0.0/9"]);
+/// let acl = allow.difference(&deny);
+///
+/// let nets: Vec<String> = acl.iter().map(|n| n.to_string()).collect();
+/// assert_eq!(nets, ["10.0.0.0/9", "192.0.2.0/24"]);
+/// assert!(acl.contains("10.1.2.3".parse().unwrap()));
+/// assert!(!acl.contains("10.200.0.1".parse().unwrap()));
+/// assert!(acl.contains_network("192.0.2.128/25".parse().unwrap()));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IpNetworkSet {
+    networks: Vec<IpNetwork>,
+}
+
+impl IpNetworkSet {
+    /// Constructs a new, empty `IpNetworkSet`.
+    pub const fn new() -> Self {
+        IpNetworkSet {
+            networks: Vec::new(),
+        }
+    }
+
+    /// Returns the number of networks needed to cover the set.
+    pub fn len(&self) -> usize {
+        self.networks.len()
+    }
+
+    /// Returns `true` if the set contains no addresses.
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+
+    /// Returns the number of addresses in the set, counted per family.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkSet};
+    ///
+    /// let set: IpNetworkSet = ["10.0.0.0/24", "10.0.0.128/25", "0.0.0.0/0", "::/0"]
+    ///     .iter()
+    ///     .map(|n| n.parse::<IpNetwork>().unwrap())
+    ///     .collect();
+    /// let size = set.size();
+    /// assert_eq!(size.ipv4(), 1 << 32);
+    /// assert_eq!(size.ipv6(), None);
+    /// assert_eq!(size.ipv6_log2(), Some(128));
+    /// ```
+    pub fn size(&self) -> IpNetworkSetSize {
+        let mut size = IpNetworkSetSize::default();
+        for &net in &self.networks {
+            let (bits, start, end) = bounds(net);
+            if bits == IPV4_BITS {
+                size.ipv4 += (end - start) as u64 + 1;
+            } else {
+                // `end - start + 1` is 2^128 for `::/0`, which carries into the high bit.
+                let (low, carry) = size.ipv6_low.overflowing_add(end - start);
+                let (low, carry_one) = low.overflowing_add(1);
+                size.ipv6_low = low;
+                size.ipv6_high |= carry || carry_one;
+            }
+        }
+        size
+    }
+
+    /// Returns an iterator over the minimal sorted list of networks covering the set.
+    pub fn iter(&self) -> std::slice::Iter<'_, IpNetwork> {
+        self.networks.iter()
+    }
+
+    /// Returns an iterator over every address of the set, in ascending order with IPv4
+    /// addresses first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::IpAddr;
+    /// use ipnetwork::{IpNetwork, IpNetworkSet};
+    ///
+    /// let set: IpNetworkSet = ["10.0.0.6/31", "10.0.0.0/31"]
+    ///     .iter()
+    ///     .map(|n| n.parse::<IpNetwork>().unwrap())
+    ///     .collect();
+    /// let addrs: Vec<IpAddr> = set.iter_addresses().collect();
+    /// let expected = ["10.0.0.0", "10.0.0.1", "10.0.0.6", "10.0.0.7"];
+    /// assert_eq!(addrs, expected.map(|a| a.parse::<IpAddr>().unwrap()));
+    /// ```
+    pub fn iter_addresses(&self) -> IpNetworkSetAddresses<'_> {
+        IpNetworkSetAddresses {
+            networks: self.networks.iter(),
+            current: None,
+        }
+    }
+
+    /// Returns the minimal sorted list of networks covering the set.
+    pub fn as_slice(&self) -> &[IpNetwork] {
+        &self.networks
+    }
+
+    /// Adds the addresses of `network` to the set, in `O(log n)` time plus the number of
+    /// networks it merges with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkSet};
+    ///
+    /// let mut set = IpNetworkSet::new();
+    /// for net in ["10.0.0.1/32", "10.0.0.2/31", "10.0.0.0/32", "192.0.2.0/24"] {
+    ///     set.insert(net.parse().unwrap());
+    /// }
+    /// let nets: Vec<String> = set.iter().map(|n| n.to_string()).collect();
+    /// assert_eq!(nets, ["10.0.0.0/30", "192.0.2.0/24"]);
+    /// ```
+    pub fn insert(&mut self, network: IpNetwork) {
+        let (bits, start, end) = bounds(network);
+        // The networks overlapping or adjacent to `network`, found by binary search since the
+        // list is sorted and disjoint.
+        let mut lo = self.networks.partition_point(|&net| {
+            let (net_bits, _, net_end) = bounds(net);
+            (net_bits, net_end) < (bits, start.saturating_sub(1))
+        });
+        let mut hi = self.networks.partition_point(|&net| {
+            let (net_bits, net_start, _) = bounds(net);
+            (net_bits, net_start) <= (bits, end.saturating_add(1))
+        });
+        let (mut first, mut last) = (start, end);
+        if lo < hi {
+            first = first.min(bounds(self.networks[lo]).1);
+            last = last.max(bounds(self.networks[hi - 1]).2);
+        }
+        // The minimal list covers each run of contiguous addresses separately, so the whole
+        // run around `network` is replaced by the minimal cover of the extended run.
+        while let Some(&net) = lo.checked_sub(1).and_then(|i| self.networks.get(i)) {
+            let (net_bits, net_start, net_end) = bounds(net);
+            if net_bits != bits || net_end.checked_add(1) != Some(first) {
+                break;
+            }
+            first = net_start;
+            lo -= 1;
+        }
+        while let Some(&net) = self.networks.get(hi) {
+            let (net_bits, net_start, net_end) = bounds(net);
+            if net_bits != bits || last.checked_add(1) != Some(net_start) {
+                break;
+            }
+            last = net_end;
+            hi += 1;
+        }
+        let mut cover = Vec::new();
+        push_cidrs(bits, first, last, &mut cover);
+        self.networks.splice(lo..hi, cover);
+    }
+
+    /// Keeps only the networks of the minimal list covering the set for which `f` returns
+    /// `true`.
+    ///
+    /// Dropping networks from the minimal list leaves it minimal, so no merging is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkSet};
+    ///
+    /// let mut set: IpNetworkSet = ["10.0.0.0/8", "2001:db8::/32"]
+    ///     .iter()
+    ///     .map(|n| n.parse::<IpNetwork>().unwrap())
+    ///     .collect();
+    /// set.retain(|net| net.is_ipv4());
+    /// assert_eq!(set.as_slice(), ["10.0.0.0/8".parse::<IpNetwork>().unwrap()]);
+    /// ```
+    pub fn retain<F: FnMut(&IpNetwork) -> bool>(&mut self, f: F) {
+        self.networks.retain(f);
+    }
+
+    /// Adds the addresses from `first` to `last`, both inclusive, to the set.
+    ///
+    /// Returns an `IpNetworkError::InvalidRange` if `first` and `last` are of different families
+    /// or `first` comes after `last`, see [`summarize_address_range`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkSet};
+    ///
+    /// let mut set = IpNetworkSet::new();
+    /// set.insert_range("10.0.0.0".parse().unwrap(), "10.0.0.2".parse().unwrap())
+    ///     .unwrap();
+    /// set.insert_range("10.0.0.3".parse().unwrap(), "10.0.0.3".parse().unwrap())
+    ///     .unwrap();
+    /// assert_eq!(set.as_slice(), ["10.0.0.0/30".parse::<IpNetwork>().unwrap()]);
+    /// ```
+    pub fn insert_range(&mut self, first: IpAddr, last: IpAddr) -> Result<(), IpNetworkError> {
+        self.extend(summarize_address_range(first, last)?);
+        Ok(())
+    }
+
+    /// Checks if `ip` is in the set, in `O(log n)` time.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        ip_find_containing(&self.networks, ip).is_some()
+    }
+
+    /// Checks if every address of `network` is in the set.
+    pub fn contains_network(&self, network: IpNetwork) -> bool {
+        ip_find_containing(&self.networks, network.network())
+            .is_some_and(|&outer| outer.covers(network))
+    }
+
+    /// Checks if every address of the set is in `other`.
+    pub fn is_subset(&self, other: &IpNetworkSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Checks if every address of the set is in `network`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkSet};
+    ///
+    /// let requested: IpNetworkSet = ["10.1.0.0/16", "10.2.0.0/24"]
+    ///     .iter()
+    ///     .map(|n| n.parse::<IpNetwork>().unwrap())
+    ///     .collect();
+    /// assert!(requested.covered_by("10.0.0.0/8".parse().unwrap()));
+    /// assert!(!requested.covered_by("10.1.0.0/16".parse().unwrap()));
+    /// ```
+    pub fn covered_by(&self, network: IpNetwork) -> bool {
+        self.is_subset(&IpNetworkSet::from(vec![network]))
+    }
+
+    /// Returns the set of addresses in `self`, `other` or both.
+    pub fn union(&self, other: &IpNetworkSet) -> IpNetworkSet {
+        self.networks
+            .iter()
+            .chain(&other.networks)
+            .copied()
+            .collect()
+    }
+
+    /// Returns the set of addresses in both `self` and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkSet};
+    ///
+    /// let a: IpNetworkSet = ["10.0.0.0/24".parse::<IpNetwork>().unwrap()].into_iter().collect();
+    /// let b: IpNetworkSet = ["10.0.0.128/25".parse::<IpNetwork>().unwrap()].into_iter().collect();
+    /// assert_eq!(a.intersection(&b), b);
+    /// ```
+    pub fn intersection(&self, other: &IpNetworkSet) -> IpNetworkSet {
+        // Both lists are sorted and disjoint, so a single merge-like pass finds every overlap.
+        // Overlaps can be adjacent, so the result is merged again.
+        let (mut i, mut j) = (0, 0);
+        let mut out = Vec::new();
+        while let (Some(&a), Some(&b)) = (self.networks.get(i), other.networks.get(j)) {
+            let (a_bits, a_start, a_end) = bounds(a);
+            let (b_bits, b_start, b_end) = bounds(b);
+            if a_bits == b_bits {
+                let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+                if start <= end {
+                    push_cidrs(a_bits, start, end, &mut out);
+                }
+            }
+            if (a_bits, a_end) < (b_bits, b_end) {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IpNetworkSet::from(out)
+    }
+
+    /// Returns the set of addresses in `self` but not in `other`.
+    pub fn difference(&self, other: &IpNetworkSet) -> IpNetworkSet {
+        let mut out = Vec::new();
+        let mut first = 0;
+        for &net in &self.networks {
+            let (bits, start, end) = bounds(net);
+            // Skip the networks of `other` that end before this one starts. They also end
+            // before every later network of `self` starts.
+            while other.networks.get(first).is_some_and(|&hole| {
+                let (hole_bits, _, hole_end) = bounds(hole);
+                (hole_bits, hole_end) < (bits, start)
+            }) {
+                first += 1;
+            }
+            let mut next = Some(start);
+            for &hole in &other.networks[first..] {
+                let (hole_bits, hole_start, hole_end) = bounds(hole);
+                let Some(from) = next else { break };
+                if hole_bits != bits || hole_start > end {
+                    break;
+                }
+                if hole_start > from {
+                    push_cidrs(bits, from, hole_start - 1, &mut out);
+                }
+                next = if hole_end >= end {
+                    None
+                } else {
+                    Some(hole_end + 1)
+                };
+            }
+            if let Some(from) = next {
+                push_cidrs(bits, from, end, &mut out);
+            }
+        }
+        IpNetworkSet::from(out)
+    }
+}
+
+/// Collects the networks first and merges them once, in `O(n log n)` time.
+impl Extend<IpNetwork> for IpNetworkSet {
+    fn extend<T: IntoIterator<Item = IpNetwork>>(&mut self, iter: T) {
+        self.networks.extend(iter);
+        merge_in_place(&mut self.networks);
+    }
+}
+
+impl FromIterator<IpNetwork> for IpNetworkSet {
+    fn from_iter<T: IntoIterator<Item = IpNetwork>>(iter: T) -> Self {
+        let mut set = IpNetworkSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl From<Vec<IpNetwork>> for IpNetworkSet {
+    fn from(mut networks: Vec<IpNetwork>) -> Self {
+        merge_in_place(&mut networks);
+        IpNetworkSet { networks }
+    }
+}
+
+impl From<IpNetworkSet> for Vec<IpNetwork> {
+    fn from(set: IpNetworkSet) -> Self {
+        set.networks
+    }
+}
+
+impl<'a> IntoIterator for &'a IpNetworkSet {
+    type Item = &'a IpNetwork;
+    type IntoIter = std::slice::Iter<'a, IpNetwork>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for IpNetworkSet {
+    type Item = IpNetwork;
+    type IntoIter = std::vec::IntoIter<IpNetwork>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.networks.into_iter()
+    }
+}
+
+/// The number of addresses in an [`IpNetworkSet`], returned by [`IpNetworkSet::size`].
+///
+/// A set can hold all 2<sup>32</sup> IPv4 and 2<sup>128</sup> IPv6 addresses at once, so the
+/// families are counted separately, in types wide enough for their whole address space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IpNetworkSetSize {
+    ipv4: u64,
+    ipv6_low: u128,
+    ipv6_high: bool,
+}
+
+impl IpNetworkSetSize {
+    /// Returns the number of IPv4 addresses.
+    pub fn ipv4(&self) -> u64 {
+        self.ipv4
+    }
+
+    /// Returns the number of IPv6 addresses, or `None` if the set holds all 2<sup>128</sup> of
+    /// them, which does not fit in a `u128`.
+    pub fn ipv6(&self) -> Option<u128> {
+        (!self.ipv6_high).then_some(self.ipv6_low)
+    }
+
+    /// Returns the base 2 logarithm of the number of IPv6 addresses if it is a power of two.
+    pub fn ipv6_log2(&self) -> Option<u32> {
+        match self.ipv6() {
+            None => Some(128),
+            Some(count) => count.is_power_of_two().then(|| count.trailing_zeros()),
+        }
+    }
+
+    /// Checks if the set holds no addresses.
+    pub fn is_zero(&self) -> bool {
+        *self == IpNetworkSetSize::default()
+    }
+}
+
+/// An iterator over the addresses of an [`IpNetworkSet`], returned by
+/// [`IpNetworkSet::iter_addresses`].
+#[derive(Clone, Debug)]
+pub struct IpNetworkSetAddresses<'a> {
+    networks: std::slice::Iter<'a, IpNetwork>,
+    current: Option<IpNetworkIterator>,
+}
+
+impl Iterator for IpNetworkSetAddresses<'_> {
+    type Item = IpAddr;
+
+    fn next(&mut self) -> Option<IpAddr> {
+        loop {
+            if let Some(addr) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(addr);
+            }
+            self.current = Some(self.networks.next()?.iter());
+        }
+    }
+}
+
+impl FusedIterator for IpNetworkSetAddresses<'_> {}
+
+/// Returns the set of addresses in `self`, `other` or both.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{IpNetwork, IpNetworkSet};
+///
+/// let low: IpNetwork = "10.0.0.0/25".parse().unwrap();
+/// let high: IpNetwork = "10.0.0.128/25".parse().unwrap();
+/// let set: IpNetworkSet = [low].into_iter().collect();
+/// let both = &set | high;
+/// assert_eq!(both.as_slice(), ["10.0.0.0/24".parse::<IpNetwork>().unwrap()]);
+/// assert_eq!(&both & high, [high].into_iter().collect());
+/// assert_eq!(&both & &set, set);
+/// ```
+impl BitOr<&IpNetworkSet> for &IpNetworkSet {
+    type Output = IpNetworkSet;
+
+    fn bitor(self, other: &IpNetworkSet) -> IpNetworkSet {
+        self.union(other)
+    }
+}
+
+/// Returns the set of addresses in `self` or in `network`.
+impl BitOr<IpNetwork> for &IpNetworkSet {
+    type Output = IpNetworkSet;
+
+    fn bitor(self, network: IpNetwork) -> IpNetworkSet {
+        self.union(&IpNetworkSet::from(vec![network]))
+    }
+}
+
+/// Returns the set of addresses in both `self` and `other`.
+impl BitAnd<&IpNetworkSet> for &IpNetworkSet {
+    type Output = IpNetworkSet;
+
+    fn bitand(self, other: &IpNetworkSet) -> IpNetworkSet {
+        self.intersection(other)
+    }
+}
+
+/// Returns the set of addresses in both `self` and `network`.
+impl BitAnd<IpNetwork> for &IpNetworkSet {
+    type Output = IpNetworkSet;
+
+    fn bitand(self, network: IpNetwork) -> IpNetworkSet {
+        self.intersection(&IpNetworkSet::from(vec![network]))
+    }
+}
+
+/// Returns the set of addresses in `self` but not in `other`.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::{IpNetwork, IpNetworkSet};
+///
+/// let parse = |nets: &[&str]| -> IpNetworkSet {
+///     nets.iter().map(|n| n.parse::<IpNetwork>().unwrap()).collect()
+/// };
+/// let mut allowed = parse(&["10.0.0.0/8"]);
+/// allowed -= parse(&["10.0.0.0/9"]);
+/// allowed |= parse(&["192.0.2.0/24"]);
+/// allowed &= parse(&["10.128.0.0/16", "192.0.2.0/25"]);
+/// assert_eq!(allowed, parse(&["10.128.0.0/16", "192.0.2.0/25"]));
+/// assert_eq!(&allowed - &parse(&["192.0.2.0/24"]), parse(&["10.128.0.0/16"]));
+/// ```
+impl Sub<&IpNetworkSet> for &IpNetworkSet {
+    type Output = IpNetworkSet;
+
+    fn sub(self, other: &IpNetworkSet) -> IpNetworkSet {
+        self.difference(other)
+    }
+}
+
+/// Removes the addresses of `other` from the set.
+impl SubAssign<&IpNetworkSet> for IpNetworkSet {
+    fn sub_assign(&mut self, other: &IpNetworkSet) {
+        *self = self.difference(other);
+    }
+}
+
+/// Removes the addresses of `other` from the set.
+impl SubAssign for IpNetworkSet {
+    fn sub_assign(&mut self, other: IpNetworkSet) {
+        *self -= &other;
+    }
+}
+
+/// Adds the addresses of `other` to the set.
+impl BitOrAssign<&IpNetworkSet> for IpNetworkSet {
+    fn bitor_assign(&mut self, other: &IpNetworkSet) {
+        *self = self.union(other);
+    }
+}
+
+/// Adds the addresses of `other` to the set.
+impl BitOrAssign for IpNetworkSet {
+    fn bitor_assign(&mut self, other: IpNetworkSet) {
+        *self |= &other;
+    }
+}
+
+/// Keeps only the addresses of the set that are also in `other`.
+impl BitAndAssign<&IpNetworkSet> for IpNetworkSet {
+    fn bitand_assign(&mut self, other: &IpNetworkSet) {
+        *self = self.intersection(other);
+    }
+}
+
+/// Keeps only the addresses of the set that are also in `other`.
+impl BitAndAssign for IpNetworkSet {
+    fn bitand_assign(&mut self, other: IpNetworkSet) {
+        *self &= &other;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn set(nets: &[&str]) -> IpNetworkSet {
+        nets.iter()
+            .map(|n| n.parse::<IpNetwork>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn set_operations_match_addresses() {
+        let a = set(&["0.0.0.3/32", "0.0.0.8/29", "0.0.0.32/27", "::/127"]);
+        let b = set(&["0.0.0.0/28", "0.0.0.40/30", "0.0.0.63/32", "::1/128"]);
+        let union = a.union(&b);
+        let intersection = a.intersection(&b);
+        let difference = a.difference(&b);
+        for i in 0..80 {
+            let ip = IpAddr::V4(Ipv4Addr::from_bits(i));
+            let (in_a, in_b) = (a.contains(ip), b.contains(ip));
+            assert_eq!(union.contains(ip), in_a || in_b, "{ip}");
+            assert_eq!(intersection.contains(ip), in_a && in_b, "{ip}");
+            assert_eq!(difference.contains(ip), in_a && !in_b, "{ip}");
+        }
+        assert_eq!(difference, b.union(&a).difference(&b));
+        assert_eq!(intersection.intersection(&a), intersection);
+        assert_eq!(
+            difference
+                .iter()
+                .filter(|n| n.is_ipv6())
+                .collect::<Vec<_>>(),
+            [&"::/128".parse::<IpNetwork>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn set_insert_matches_merge() {
+        // Pick /29 to /32 networks of 0.0.0.0/26 in a scrambled order. Host bits are ignored.
+        let mut networks = Vec::new();
+        for prefix in 29..=32u8 {
+            for i in 0..64u32 {
+                if (i * 7 + u32::from(prefix)) % 5 < 2 {
+                    let addr = Ipv4Addr::from_bits((i * 37) % 64);
+                    networks.push(IpNetwork::new(addr.into(), prefix).unwrap());
+                }
+            }
+        }
+        networks.push("0.0.0.0/0".parse().unwrap());
+        networks.push("::/0".parse().unwrap());
+        let mut incremental = IpNetworkSet::new();
+        for (n, &net) in networks.iter().enumerate() {
+            incremental.insert(net);
+            assert_eq!(
+                incremental,
+                IpNetworkSet::from(networks[..=n].to_vec()),
+                "after inserting {net}"
+            );
+        }
+    }
+
+    #[test]
+    fn set_insert_joins_neighbours() {
+        let mut s = set(&["10.0.0.1/32", "10.0.0.2/31", "10.0.0.5/32"]);
+        s.insert("10.0.0.0/32".parse().unwrap());
+        assert_eq!(s, set(&["10.0.0.0/30", "10.0.0.5/32"]));
+        s.insert("10.0.0.4/32".parse().unwrap());
+        s.insert("10.0.0.6/31".parse().unwrap());
+        assert_eq!(s, set(&["10.0.0.0/29"]));
+        s.insert("255.255.255.255/32".parse().unwrap());
+        s.insert("::/128".parse().unwrap());
+        s.insert(
+            "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(s.len(), 4);
+    }
+
+    #[test]
+    fn set_whole_space() {
+        let all = set(&["0.0.0.0/0", "::/0"]);
+        let some = set(&["255.255.255.255/32", "ffff::/16"]);
+        assert_eq!(all.intersection(&some), some);
+        assert_eq!(some.difference(&all), IpNetworkSet::new());
+        assert_eq!(all.difference(&some).union(&some), all);
+        assert!(all.contains_network("::/1".parse().unwrap()));
+        assert!(!some.contains_network("255.255.255.254/31".parse().unwrap()));
+        assert!(some.is_subset(&all));
+        assert!(!all.is_subset(&some));
+        assert!(IpNetworkSet::new().covered_by("::/128".parse().unwrap()));
+        assert!(!some.covered_by("::/0".parse().unwrap()));
+    }
+
+    #[test]
+    fn set_assign_operators() {
+        let a = set(&["10.0.0.0/8", "::/0"]);
+        let b = set(&["10.0.0.0/9", "2001:db8::/32"]);
+        let mut c = a.clone();
+        c -= &b;
+        assert_eq!(c, a.difference(&b));
+        assert_eq!(&a - &b, c);
+        c |= b.clone();
+        assert_eq!(c, a);
+        c &= &b;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn set_insert_range() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        let mut s = set(&["10.0.0.0/24"]);
+        s.insert_range(ip("10.0.0.200"), ip("10.0.1.255")).unwrap();
+        s.insert_range(ip("::"), ip("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"))
+            .unwrap();
+        assert_eq!(s, set(&["10.0.0.0/23", "::/0"]));
+        assert!(s.insert_range(ip("10.0.0.1"), ip("::1")).is_err());
+        assert!(s.insert_range(ip("10.0.0.1"), ip("10.0.0.0")).is_err());
+        assert_eq!(s, set(&["10.0.0.0/23", "::/0"]));
+    }
+
+    #[test]
+    fn set_retain() {
+        let mut s = set(&["10.0.0.0/25", "10.0.1.0/24", "::/0"]);
+        s.retain(|net| net.prefix() < 25);
+        assert_eq!(s, set(&["10.0.1.0/24", "::/0"]));
+    }
+
+    #[test]
+    fn set_size() {
+        assert!(IpNetworkSet::new().size().is_zero());
+        let s = set(&[
+            "10.0.0.0/31",
+            "10.0.0.1/32",
+            "10.0.1.0/24",
+            "::/1",
+            "8000::/2",
+        ]);
+        let size = s.size();
+        assert_eq!(size.ipv4(), 258);
+        assert_eq!(size.ipv6(), Some(3 << 126));
+        assert_eq!(size.ipv6_log2(), None);
+        let size = set(&["::/1", "8000::/1"]).size();
+        assert_eq!((size.ipv4(), size.ipv6()), (0, None));
+        assert_eq!(set(&["2001:db8::/32"]).size().ipv6_log2(), Some(96));
+    }
+
+    #[test]
+    fn set_iter_addresses() {
+        let s = set(&["0.0.0.255/32", "0.0.1.0/31", "::/127"]);
+        let addrs: Vec<String> = s.iter_addresses().map(|a| a.to_string()).collect();
+        assert_eq!(addrs, ["0.0.0.255", "0.0.1.0", "0.0.1.1", "::", "::1"]);
+        assert_eq!(IpNetworkSet::new().iter_addresses().next(), None);
+    }
+
+    #[test]
+    fn set_operators() {
+        let a = set(&["10.0.0.0/8", "::/0"]);
+        let b = set(&["10.0.0.0/9", "2001:db8::/32", "192.0.2.0/24"]);
+        assert_eq!(&a | &b, a.union(&b));
+        assert_eq!(&a & &b, a.intersection(&b));
+        let v6: IpNetwork = "::/0".parse().unwrap();
+        assert_eq!(&b & v6, set(&["2001:db8::/32"]));
+        assert_eq!(&b | v6, set(&["10.0.0.0/9", "192.0.2.0/24", "::/0"]));
+    }
+}
@@ -37,6 +37,84 @@ impl NetworkSize {
             V6(a) => a,
         }
     }
+
+    /// Returns a short, approximate description of the size for dashboards and summaries.
+    ///
+    /// Sizes below a thousand are written out. Larger sizes are rounded to one decimal with a
+    /// `K`, `M`, `B` or `T` suffix, and sizes of a thousand trillion and more are written as a
+    /// power of two when they are one, or in scientific notation otherwise. `V6(u128::MAX)`,
+    /// which [`Ipv6Network::size`](crate::Ipv6Network::size) returns for `::/0`, is written as
+    /// `2^128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{Ipv6Network, NetworkSize};
+    ///
+    /// assert_eq!(NetworkSize::V4(1).to_human_string(), "1 address");
+    /// assert_eq!(NetworkSize::V4(256).to_human_string(), "256 addresses");
+    /// assert_eq!(NetworkSize::V4(65536).to_human_string(), "65.5K addresses");
+    ///
+    /// let net: Ipv6Network = "2001:db8::/32".parse().unwrap();
+    /// assert_eq!(NetworkSize::V6(net.size()).to_human_string(), "2^96 addresses");
+    /// ```
+    pub fn to_human_string(&self) -> String {
+        const UNITS: [(u128, &str); 4] = [
+            (1_000, "K"),
+            (1_000_000, "M"),
+            (1_000_000_000, "B"),
+            (1_000_000_000_000, "T"),
+        ];
+        const POWER_THRESHOLD: u128 = 1_000_000_000_000_000;
+
+        let n = self.as_u128();
+        let count = if n < UNITS[0].0 {
+            n.to_string()
+        } else if n < POWER_THRESHOLD {
+            let index = UNITS.iter().rposition(|&(unit, _)| n >= unit).unwrap_or(0);
+            let (unit, mut suffix) = UNITS[index];
+            let mut tenths = (n * 10 + unit / 2) / unit;
+            // Rounding up to a thousand units moves to the next unit.
+            if tenths >= 10_000 && index + 1 < UNITS.len() {
+                suffix = UNITS[index + 1].1;
+                tenths = 10;
+            }
+            match tenths % 10 {
+                0 => format!("{}{suffix}", tenths / 10),
+                frac => format!("{}.{frac}{suffix}", tenths / 10),
+            }
+        } else if n.is_power_of_two() {
+            format!("2^{}", n.trailing_zeros())
+        } else if n == u128::MAX {
+            "2^128".to_string()
+        } else {
+            format!("{:.2e}", n as f64)
+        };
+        let noun = if n == 1 { "address" } else { "addresses" };
+        format!("{count} {noun}")
+    }
+
+    /// Returns the exact size with its digits grouped in threes by commas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::NetworkSize;
+    ///
+    /// assert_eq!(NetworkSize::V4(999).to_grouped_string(), "999");
+    /// assert_eq!(NetworkSize::V4(16777216).to_grouped_string(), "16,777,216");
+    /// ```
+    pub fn to_grouped_string(&self) -> String {
+        let digits = self.as_u128().to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
 }
 
 impl From<u32> for NetworkSize {
@@ -204,4 +282,29 @@ mod tests {
         // implies hash(a) == hash(b)
         assert_eq!(hash_a, hash_b);
     }
+
+    #[test]
+    fn test_to_human_string() {
+        assert_eq!(V4(0).to_human_string(), "0 addresses");
+        assert_eq!(V4(999).to_human_string(), "999 addresses");
+        assert_eq!(V4(1000).to_human_string(), "1K addresses");
+        assert_eq!(V4(999_949).to_human_string(), "999.9K addresses");
+        assert_eq!(V4(999_950).to_human_string(), "1M addresses");
+        assert_eq!(V4(u32::MAX).to_human_string(), "4.3B addresses");
+        assert_eq!(V6(1 << 49).to_human_string(), "562.9T addresses");
+        assert_eq!(V6(1 << 64).to_human_string(), "2^64 addresses");
+        assert_eq!(V6(3 << 64).to_human_string(), "5.53e19 addresses");
+        assert_eq!(V6(u128::MAX).to_human_string(), "2^128 addresses");
+    }
+
+    #[test]
+    fn test_to_grouped_string() {
+        assert_eq!(V4(0).to_grouped_string(), "0");
+        assert_eq!(V4(1000).to_grouped_string(), "1,000");
+        assert_eq!(V4(123_456).to_grouped_string(), "123,456");
+        assert_eq!(
+            V6(u128::MAX).to_grouped_string(),
+            "340,282,366,920,938,463,463,374,607,431,768,211,455"
+        );
+    }
 }
@@ -0,0 +1,218 @@
+//! sqlx support for MySQL and SQLite, which have no native network type.
+//!
+//! Networks are encoded as `TEXT` in CIDR notation and parsed again when decoded, so a value
+//! that does not hold a valid network fails to decode with the parse error.
+
+use crate::IpNetworkError;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode};
+use std::{fmt, str::FromStr};
+
+/// Encodes `net` as a string in CIDR notation.
+fn encode_text<'q, DB>(
+    net: &impl fmt::Display,
+    buf: &mut DB::ArgumentBuffer<'q>,
+) -> Result<IsNull, BoxDynError>
+where
+    DB: Database,
+    String: Encode<'q, DB>,
+{
+    <String as Encode<'q, DB>>::encode(net.to_string(), buf)
+}
+
+/// Decodes `value` as a string and parses it as a network.
+fn decode_text<'r, DB, T>(value: DB::ValueRef<'r>) -> Result<T, BoxDynError>
+where
+    DB: Database,
+    &'r str: Decode<'r, DB>,
+    T: FromStr<Err = IpNetworkError>,
+{
+    Ok(<&str as Decode<'r, DB>>::decode(value)?.parse()?)
+}
+
+#[cfg(feature = "sqlx-mysql")]
+mod mysql {
+    use super::{decode_text, encode_text};
+    use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+    use sqlx::{Decode, Encode, Type};
+
+    impl Type<MySql> for Ipv4Network {
+        fn type_info() -> MySqlTypeInfo {
+            <str as Type<MySql>>::type_info()
+        }
+
+        fn compatible(ty: &MySqlTypeInfo) -> bool {
+            <str as Type<MySql>>::compatible(ty)
+        }
+    }
+
+    impl Encode<'_, MySql> for Ipv4Network {
+        fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+            encode_text::<MySql>(self, buf)
+        }
+    }
+
+    impl<'r> Decode<'r, MySql> for Ipv4Network {
+        fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+            decode_text::<MySql, _>(value)
+        }
+    }
+
+    impl Type<MySql> for Ipv6Network {
+        fn type_info() -> MySqlTypeInfo {
+            <str as Type<MySql>>::type_info()
+        }
+
+        fn compatible(ty: &MySqlTypeInfo) -> bool {
+            <str as Type<MySql>>::compatible(ty)
+        }
+    }
+
+    impl Encode<'_, MySql> for Ipv6Network {
+        fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+            encode_text::<MySql>(self, buf)
+        }
+    }
+
+    impl<'r> Decode<'r, MySql> for Ipv6Network {
+        fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+            decode_text::<MySql, _>(value)
+        }
+    }
+
+    impl Type<MySql> for IpNetwork {
+        fn type_info() -> MySqlTypeInfo {
+            <str as Type<MySql>>::type_info()
+        }
+
+        fn compatible(ty: &MySqlTypeInfo) -> bool {
+            <str as Type<MySql>>::compatible(ty)
+        }
+    }
+
+    impl Encode<'_, MySql> for IpNetwork {
+        fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+            encode_text::<MySql>(self, buf)
+        }
+    }
+
+    impl<'r> Decode<'r, MySql> for IpNetwork {
+        fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+            decode_text::<MySql, _>(value)
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlite {
+    use super::{decode_text, encode_text};
+    use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+    use sqlx::{Decode, Encode, Type};
+
+    impl Type<Sqlite> for Ipv4Network {
+        fn type_info() -> SqliteTypeInfo {
+            <str as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <str as Type<Sqlite>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Sqlite> for Ipv4Network {
+        fn encode_by_ref(
+            &self,
+            buf: &mut Vec<SqliteArgumentValue<'q>>,
+        ) -> Result<IsNull, BoxDynError> {
+            encode_text::<Sqlite>(self, buf)
+        }
+    }
+
+    impl<'r> Decode<'r, Sqlite> for Ipv4Network {
+        fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+            decode_text::<Sqlite, _>(value)
+        }
+    }
+
+    impl Type<Sqlite> for Ipv6Network {
+        fn type_info() -> SqliteTypeInfo {
+            <str as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <str as Type<Sqlite>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Sqlite> for Ipv6Network {
+        fn encode_by_ref(
+            &self,
+            buf: &mut Vec<SqliteArgumentValue<'q>>,
+        ) -> Result<IsNull, BoxDynError> {
+            encode_text::<Sqlite>(self, buf)
+        }
+    }
+
+    impl<'r> Decode<'r, Sqlite> for Ipv6Network {
+        fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+            decode_text::<Sqlite, _>(value)
+        }
+    }
+
+    impl Type<Sqlite> for IpNetwork {
+        fn type_info() -> SqliteTypeInfo {
+            <str as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <str as Type<Sqlite>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Sqlite> for IpNetwork {
+        fn encode_by_ref(
+            &self,
+            buf: &mut Vec<SqliteArgumentValue<'q>>,
+        ) -> Result<IsNull, BoxDynError> {
+            encode_text::<Sqlite>(self, buf)
+        }
+    }
+
+    impl<'r> Decode<'r, Sqlite> for IpNetwork {
+        fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+            decode_text::<Sqlite, _>(value)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use crate::{IpNetwork, Ipv4Network};
+        use sqlx::{Connection, SqliteConnection};
+
+        #[test]
+        fn sqlite_round_trip() {
+            futures::executor::block_on(async {
+                let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+                let net: IpNetwork = "2001:db8::/32".parse().unwrap();
+                let read: IpNetwork = sqlx::query_scalar("SELECT ?")
+                    .bind(net)
+                    .fetch_one(&mut conn)
+                    .await
+                    .unwrap();
+                assert_eq!(read, net);
+
+                let invalid = sqlx::query_scalar::<_, Ipv4Network>("SELECT '10.0.0.0/33'")
+                    .fetch_one(&mut conn)
+                    .await;
+                assert!(invalid.is_err());
+            });
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! `proptest` strategies for generating networks.
+//!
+//! # Examples
+//!
+//! ```
+//! use ipnetwork::strategy::{disjoint_pair, subnet_of};
+//! use ipnetwork::NetworkRelation;
+//! use proptest::prelude::*;
+//!
+//! let parent = "10.0.0.0/8".parse().unwrap();
+//! proptest!(|(net in subnet_of(parent))| {
+//!     prop_assert!(net.prefix() >= 8);
+//!     prop_assert_ne!(net.relation(parent), NetworkRelation::SupersetOf);
+//!     prop_assert_ne!(net.relation(parent), NetworkRelation::Disjoint);
+//! });
+//!
+//! proptest!(|((a, b) in disjoint_pair())| {
+//!     prop_assert_eq!(a.relation(b), NetworkRelation::Disjoint);
+//! });
+//! ```
+
+use crate::merge::Merge;
+use crate::range::{bounds, network};
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use proptest::prelude::*;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Generates IPv4 networks with any address and any valid prefix.
+///
+/// The address may have host bits set. Values shrink toward `0.0.0.0/0`.
+pub fn any_ipv4_network() -> impl Strategy<Value = Ipv4Network> {
+    (any::<u32>(), 0..=32u8)
+        .prop_map(|(addr, prefix)| Ipv4Network::new(Ipv4Addr::from_bits(addr), prefix).unwrap())
+}
+
+/// Generates IPv6 networks with any address and any valid prefix.
+///
+/// The address may have host bits set. Values shrink toward `::/0`.
+pub fn any_ipv6_network() -> impl Strategy<Value = Ipv6Network> {
+    (any::<u128>(), 0..=128u8)
+        .prop_map(|(addr, prefix)| Ipv6Network::new(Ipv6Addr::from_bits(addr), prefix).unwrap())
+}
+
+/// Generates networks of either family with any address and any valid prefix.
+pub fn any_ip_network() -> impl Strategy<Value = IpNetwork> {
+    prop_oneof![
+        any_ipv4_network().prop_map(IpNetwork::V4),
+        any_ipv6_network().prop_map(IpNetwork::V6),
+    ]
+}
+
+/// Generates the subnets of `net`, including `net` itself, with their host bits cleared.
+///
+/// Values shrink toward `net` and toward its first subnet of each prefix.
+pub fn subnet_of(net: IpNetwork) -> impl Strategy<Value = IpNetwork> {
+    let (bits, start, end) = bounds(net);
+    (net.prefix()..=bits, any::<u128>()).prop_map(move |(prefix, offset)| {
+        network(bits, start | (offset & (end - start)), prefix).normalize()
+    })
+}
+
+/// Generates pairs of networks of the same family that share no address, with their host
+/// bits cleared.
+///
+/// The networks lie in opposite halves of a common parent, so they range from adjacent
+/// halves to far-apart subnets, in either order.
+pub fn disjoint_pair() -> impl Strategy<Value = (IpNetwork, IpNetwork)> {
+    let parent = prop_oneof![
+        (any::<u32>(), 0..32u8).prop_map(|(addr, prefix)| {
+            IpNetwork::V4(Ipv4Network::new(Ipv4Addr::from_bits(addr), prefix).unwrap())
+        }),
+        (any::<u128>(), 0..128u8).prop_map(|(addr, prefix)| {
+            IpNetwork::V6(Ipv6Network::new(Ipv6Addr::from_bits(addr), prefix).unwrap())
+        }),
+    ];
+    parent.prop_flat_map(|parent| {
+        let (bits, start, end) = bounds(parent);
+        let half = parent.prefix() + 1;
+        let low = network(bits, start, half);
+        let high = network(bits, end, half).normalize();
+        (subnet_of(low), subnet_of(high), any::<bool>()).prop_map(|(a, b, swap)| {
+            if swap {
+                (b, a)
+            } else {
+                (a, b)
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NetworkRelation;
+
+    proptest! {
+        #[test]
+        fn subnet_of_stays_inside(
+            (net, sub) in any_ip_network().prop_flat_map(|net| (Just(net), subnet_of(net)))
+        ) {
+            prop_assert!(matches!(
+                sub.relation(net),
+                NetworkRelation::SubsetOf | NetworkRelation::Equal
+            ));
+            prop_assert_eq!(sub, sub.normalize());
+        }
+
+        #[test]
+        fn disjoint_pair_is_disjoint((a, b) in disjoint_pair()) {
+            prop_assert_eq!(a.relation(b), NetworkRelation::Disjoint);
+            prop_assert_eq!(a.is_ipv4(), b.is_ipv4());
+        }
+    }
+}
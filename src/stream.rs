@@ -0,0 +1,133 @@
+use futures_core::Stream;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+const DEFAULT_BUDGET: usize = 128;
+
+/// Adapts an address iterator into a `futures_core::Stream`.
+///
+/// Address iterators never block, so a naive stream over one would always be ready and could
+/// starve other tasks on the same executor while scanning a large network. This adapter
+/// yields control back to the executor after every `budget` addresses. Chunking or rate
+/// limiting can be layered on top with the usual stream combinators.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{executor::block_on, StreamExt};
+/// use ipnetwork::{IpNetworkStream, Ipv4Network};
+///
+/// let net: Ipv4Network = "192.168.0.0/24".parse().unwrap();
+/// let addrs: Vec<_> = block_on(IpNetworkStream::new(net.iter()).collect());
+/// assert_eq!(addrs.len(), 256);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IpNetworkStream<I> {
+    iter: I,
+    budget: usize,
+    remaining: usize,
+}
+
+impl<I: Iterator> IpNetworkStream<I> {
+    /// Constructs a stream over `iter` that yields to the executor every 128 addresses.
+    pub fn new(iter: I) -> Self {
+        Self::with_budget(iter, DEFAULT_BUDGET)
+    }
+
+    /// Constructs a stream over `iter` that yields to the executor every `budget` addresses.
+    ///
+    /// A `budget` of 0 never yields, which is only appropriate for small networks.
+    pub fn with_budget(iter: I, budget: usize) -> Self {
+        IpNetworkStream {
+            iter,
+            budget,
+            remaining: budget,
+        }
+    }
+
+    /// Consumes the stream, returning the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: Iterator + Unpin> Stream for IpNetworkStream<I> {
+    type Item = I::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+        let this = &mut *self;
+        if this.budget != 0 {
+            if this.remaining == 0 {
+                this.remaining = this.budget;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            this.remaining -= 1;
+        }
+        Poll::Ready(this.iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{IpNetwork, Ipv6Network};
+    use futures::{executor::block_on, task::noop_waker, StreamExt};
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn stream_matches_iter() {
+        let net: IpNetwork = "10.0.0.0/23".parse().unwrap();
+        let addrs: Vec<_> = block_on(IpNetworkStream::new(net.iter()).collect());
+        assert!(addrs.into_iter().eq(net.iter()));
+    }
+
+    #[test]
+    fn stream_yields_after_budget() {
+        let net: Ipv6Network = "2001:db8::/126".parse().unwrap();
+        let mut stream = IpNetworkStream::with_budget(net.iter(), 2);
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut poll = || Pin::new(&mut stream).poll_next(&mut cx);
+
+        assert_eq!(
+            poll(),
+            Poll::Ready(Some(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0)))
+        );
+        assert_eq!(
+            poll(),
+            Poll::Ready(Some(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)))
+        );
+        assert_eq!(poll(), Poll::Pending);
+        assert_eq!(
+            poll(),
+            Poll::Ready(Some(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2)))
+        );
+        assert_eq!(
+            poll(),
+            Poll::Ready(Some(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 3)))
+        );
+        assert_eq!(poll(), Poll::Pending);
+        assert_eq!(poll(), Poll::Ready(None));
+    }
+
+    #[test]
+    fn stream_without_budget_never_yields() {
+        let net: IpNetwork = "10.0.0.0/28".parse().unwrap();
+        let mut stream = IpNetworkStream::with_budget(net.iter(), 0);
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        for addr in net.iter() {
+            assert_eq!(
+                Pin::new(&mut stream).poll_next(&mut cx),
+                Poll::Ready(Some(addr))
+            );
+        }
+    }
+}
@@ -0,0 +1,796 @@
+use crate::merge::Merge;
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// A map from networks to values with longest-prefix-match lookups.
+///
+/// The networks are kept in a path-compressed binary trie per address family, so lookups take
+/// time proportional to the prefix length rather than to the number of networks. Networks are
+/// stored with their host bits cleared, so `10.0.0.1/24` and `10.0.0.0/24` are the same key.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::IpAddr;
+/// use ipnetwork::{IpNetwork, IpNetworkTable};
+///
+/// let mut routes = IpNetworkTable::new();
+/// routes.insert("0.0.0.0/0".parse().unwrap(), "default");
+/// routes.insert("10.0.0.0/8".parse().unwrap(), "internal");
+/// routes.insert("10.1.0.0/16".parse().unwrap(), "lab");
+///
+/// let (net, via) = routes.longest_match("10.1.2.3".parse().unwrap()).unwrap();
+/// assert_eq!((net, *via), ("10.1.0.0/16".parse::<IpNetwork>().unwrap(), "lab"));
+/// assert_eq!(routes.longest_match("192.0.2.1".parse().unwrap()).unwrap().1, &"default");
+/// assert_eq!(routes.longest_match("::1".parse().unwrap()), None);
+///
+/// assert_eq!(routes.remove("10.1.0.0/16".parse().unwrap()), Some("lab"));
+/// assert_eq!(routes.longest_match("10.1.2.3".parse().unwrap()).unwrap().1, &"internal");
+/// ```
+///
+/// With the `serde` feature, a table is serialized as a map from networks to values.
+#[derive(Clone, Debug)]
+pub struct IpNetworkTable<V> {
+    v4: Option<Box<Node<V>>>,
+    v6: Option<Box<Node<V>>>,
+    len: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, V> serde::Deserialize<'de> for IpNetworkTable<V>
+where
+    V: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let entries = std::collections::BTreeMap::<IpNetwork, V>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<V> serde::Serialize for IpNetworkTable<V>
+where
+    V: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self)
+    }
+}
+
+/// A trie node. Keys are left-aligned in a `u128`, so IPv4 networks use the top 32 bits.
+///
+/// Nodes without a value only exist to join two subtries, so they always have both children.
+#[derive(Clone, Debug)]
+struct Node<V> {
+    key: u128,
+    prefix: u8,
+    value: Option<V>,
+    children: [Option<Box<Node<V>>>; 2],
+}
+
+impl<V> Node<V> {
+    fn leaf(key: u128, prefix: u8, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            prefix,
+            value: Some(value),
+            children: [None, None],
+        })
+    }
+
+    /// Checks if the network of this node contains the left-aligned `key` of length `prefix`.
+    fn covers(&self, key: u128, prefix: u8) -> bool {
+        self.prefix <= prefix && common_prefix(self.key, key, self.prefix) == self.prefix
+    }
+}
+
+fn mask(key: u128, prefix: u8) -> u128 {
+    key & u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0)
+}
+
+/// Returns bit `index` of `key`, counting from the most significant bit.
+fn bit(key: u128, index: u8) -> usize {
+    ((key >> (127 - index)) & 1) as usize
+}
+
+/// Returns the number of leading bits `a` and `b` have in common, up to `max`.
+fn common_prefix(a: u128, b: u128, max: u8) -> u8 {
+    ((a ^ b).leading_zeros() as u8).min(max)
+}
+
+fn to_key(network: IpNetwork) -> (u128, u8) {
+    match network {
+        IpNetwork::V4(net) => (u128::from(net.network().to_bits()) << 96, net.prefix()),
+        IpNetwork::V6(net) => (net.network().to_bits(), net.prefix()),
+    }
+}
+
+fn to_network(v6: bool, key: u128, prefix: u8) -> IpNetwork {
+    if v6 {
+        IpNetwork::V6(Ipv6Network::new_checked(Ipv6Addr::from_bits(key), prefix).unwrap())
+    } else {
+        let addr = Ipv4Addr::from_bits((key >> 96) as u32);
+        IpNetwork::V4(Ipv4Network::new_checked(addr, prefix).unwrap())
+    }
+}
+
+fn insert<V>(slot: &mut Option<Box<Node<V>>>, key: u128, prefix: u8, value: V) -> Option<V> {
+    let Some(node) = slot else {
+        *slot = Some(Node::leaf(key, prefix, value));
+        return None;
+    };
+    let common = common_prefix(node.key, key, node.prefix.min(prefix));
+    if common == node.prefix && common == prefix {
+        return node.value.replace(value);
+    }
+    if common == node.prefix {
+        return insert(&mut node.children[bit(key, common)], key, prefix, value);
+    }
+    // The new network splits the edge leading to `node`, either as its parent or as its
+    // sibling under a new joining node.
+    let old = slot.take().unwrap();
+    let mut parent = if common == prefix {
+        Node::leaf(key, prefix, value)
+    } else {
+        let mut join = Box::new(Node {
+            key: mask(key, common),
+            prefix: common,
+            value: None,
+            children: [None, None],
+        });
+        join.children[bit(key, common)] = Some(Node::leaf(key, prefix, value));
+        join
+    };
+    let side = bit(old.key, common);
+    parent.children[side] = Some(old);
+    *slot = Some(parent);
+    None
+}
+
+fn get_mut<V>(slot: &mut Option<Box<Node<V>>>, key: u128, prefix: u8) -> Option<&mut V> {
+    let node = slot.as_mut()?;
+    if !node.covers(key, prefix) {
+        return None;
+    }
+    if node.prefix == prefix {
+        node.value.as_mut()
+    } else {
+        get_mut(&mut node.children[bit(key, node.prefix)], key, prefix)
+    }
+}
+
+/// Removes the values of the subtrie at `slot` for which `f` returns `false`, and returns how
+/// many were removed.
+fn retain<V, F>(slot: &mut Option<Box<Node<V>>>, v6: bool, f: &mut F) -> usize
+where
+    F: FnMut(IpNetwork, &mut V) -> bool,
+{
+    let Some(node) = slot else {
+        return 0;
+    };
+    let mut removed = 0;
+    if let Some(value) = &mut node.value {
+        if !f(to_network(v6, node.key, node.prefix), value) {
+            node.value = None;
+            removed += 1;
+        }
+    }
+    for child in &mut node.children {
+        removed += retain(child, v6, f);
+    }
+    if node.value.is_none() {
+        match &mut node.children {
+            [Some(_), Some(_)] => {}
+            [child, None] | [None, child] => *slot = child.take(),
+        }
+    }
+    removed
+}
+
+fn remove<V>(slot: &mut Option<Box<Node<V>>>, key: u128, prefix: u8) -> Option<V> {
+    let node = slot.as_mut()?;
+    if !node.covers(key, prefix) {
+        return None;
+    }
+    let removed = if node.prefix == prefix {
+        node.value.take()
+    } else {
+        remove(&mut node.children[bit(key, node.prefix)], key, prefix)
+    };
+    // Drop nodes that no longer hold a value or join two subtries.
+    if removed.is_some() && node.value.is_none() {
+        match &mut node.children {
+            [Some(_), Some(_)] => {}
+            [child, None] | [None, child] => *slot = child.take(),
+        }
+    }
+    removed
+}
+
+impl<V> IpNetworkTable<V> {
+    /// Constructs a new, empty `IpNetworkTable`.
+    pub const fn new() -> Self {
+        IpNetworkTable {
+            v4: None,
+            v6: None,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of networks in the table.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the table contains no networks.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn root(&self, v6: bool) -> &Option<Box<Node<V>>> {
+        if v6 {
+            &self.v6
+        } else {
+            &self.v4
+        }
+    }
+
+    fn root_mut(&mut self, v6: bool) -> &mut Option<Box<Node<V>>> {
+        if v6 {
+            &mut self.v6
+        } else {
+            &mut self.v4
+        }
+    }
+
+    /// Inserts `value` for `network`, with its host bits cleared, and returns the value
+    /// previously stored for that network.
+    pub fn insert(&mut self, network: IpNetwork, value: V) -> Option<V> {
+        let (key, prefix) = to_key(network);
+        let old = insert(self.root_mut(network.is_ipv6()), key, prefix, value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Removes `network`, with its host bits cleared, and returns its value.
+    ///
+    /// Networks that are contained in or contain `network` are kept.
+    pub fn remove(&mut self, network: IpNetwork) -> Option<V> {
+        let (key, prefix) = to_key(network);
+        let removed = remove(self.root_mut(network.is_ipv6()), key, prefix);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Keeps only the networks for which `f` returns `true`, passing it each network and a
+    /// mutable reference to its value in the order of `IpNetwork`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetworkTable;
+    ///
+    /// let mut ttls = IpNetworkTable::new();
+    /// ttls.insert("192.0.2.0/24".parse().unwrap(), 1);
+    /// ttls.insert("198.51.100.0/24".parse().unwrap(), 3);
+    /// ttls.retain(|_, ttl| {
+    ///     *ttl -= 1;
+    ///     *ttl > 0
+    /// });
+    /// assert_eq!(ttls.len(), 1);
+    /// assert_eq!(ttls.exact_match("198.51.100.0/24".parse().unwrap()), Some(&2));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(IpNetwork, &mut V) -> bool,
+    {
+        self.len -= retain(&mut self.v4, false, &mut f);
+        self.len -= retain(&mut self.v6, true, &mut f);
+    }
+
+    /// Returns the value stored for exactly `network`, with its host bits cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetworkTable;
+    ///
+    /// let mut table = IpNetworkTable::new();
+    /// table.insert("2001:db8::/32".parse().unwrap(), 1);
+    /// assert_eq!(table.exact_match("2001:db8::1/32".parse().unwrap()), Some(&1));
+    /// assert_eq!(table.exact_match("2001:db8::/48".parse().unwrap()), None);
+    /// ```
+    pub fn exact_match(&self, network: IpNetwork) -> Option<&V> {
+        let (key, prefix) = to_key(network);
+        let mut node = self.root(network.is_ipv6()).as_deref()?;
+        while node.covers(key, prefix) {
+            if node.prefix == prefix {
+                return node.value.as_ref();
+            }
+            node = node.children[bit(key, node.prefix)].as_deref()?;
+        }
+        None
+    }
+
+    /// Returns a mutable reference to the value stored for exactly `network`, with its host
+    /// bits cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetworkTable;
+    ///
+    /// let mut table = IpNetworkTable::new();
+    /// table.insert("10.0.0.0/8".parse().unwrap(), 1);
+    /// *table.get_mut("10.0.0.0/8".parse().unwrap()).unwrap() += 1;
+    /// assert_eq!(table.exact_match("10.0.0.0/8".parse().unwrap()), Some(&2));
+    /// assert_eq!(table.get_mut("10.0.0.0/16".parse().unwrap()), None);
+    /// ```
+    pub fn get_mut(&mut self, network: IpNetwork) -> Option<&mut V> {
+        let (key, prefix) = to_key(network);
+        get_mut(self.root_mut(network.is_ipv6()), key, prefix)
+    }
+
+    /// Returns the entry for `network`, with its host bits cleared, for in-place updates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkTable};
+    ///
+    /// let mut bytes = IpNetworkTable::new();
+    /// for (net, len) in [("10.0.0.0/8", 100), ("192.0.2.0/24", 40), ("10.0.0.0/8", 60)] {
+    ///     let net: IpNetwork = net.parse().unwrap();
+    ///     bytes.entry(net).and_modify(|total| *total += len).or_insert(len);
+    /// }
+    /// assert_eq!(bytes.exact_match("10.0.0.0/8".parse().unwrap()), Some(&160));
+    /// assert_eq!(bytes.exact_match("192.0.2.0/24".parse().unwrap()), Some(&40));
+    /// ```
+    pub fn entry(&mut self, network: IpNetwork) -> IpNetworkTableEntry<'_, V> {
+        IpNetworkTableEntry {
+            table: self,
+            network: network.normalize(),
+        }
+    }
+
+    /// Returns the networks in the table that contain `ip` with their values, least specific
+    /// first, by walking the trie from the root towards `ip`.
+    fn matches(&self, ip: IpAddr) -> impl Iterator<Item = (IpNetwork, &V)> {
+        let (key, prefix) = to_key(IpNetwork::from(ip));
+        let covering = move |node: &&Node<V>| node.covers(key, prefix);
+        let first = self.root(ip.is_ipv6()).as_deref().filter(covering);
+        std::iter::successors(first, move |node| {
+            if node.prefix == prefix {
+                return None;
+            }
+            node.children[bit(key, node.prefix)]
+                .as_deref()
+                .filter(covering)
+        })
+        .filter_map(move |node| {
+            let network = to_network(ip.is_ipv6(), node.key, node.prefix);
+            node.value.as_ref().map(|value| (network, value))
+        })
+    }
+
+    /// Returns the most specific network in the table that contains `ip`, together with its
+    /// value.
+    pub fn longest_match(&self, ip: IpAddr) -> Option<(IpNetwork, &V)> {
+        self.matches(ip).last()
+    }
+
+    /// Returns the least specific network in the table that contains `ip`, together with its
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkTable};
+    ///
+    /// let mut table = IpNetworkTable::new();
+    /// table.insert("10.0.0.0/8".parse().unwrap(), "site");
+    /// table.insert("10.1.0.0/16".parse().unwrap(), "building");
+    /// let (net, _) = table.shortest_match("10.1.2.3".parse().unwrap()).unwrap();
+    /// assert_eq!(net, "10.0.0.0/8".parse::<IpNetwork>().unwrap());
+    /// ```
+    pub fn shortest_match(&self, ip: IpAddr) -> Option<(IpNetwork, &V)> {
+        self.matches(ip).next()
+    }
+
+    /// Returns every network in the table that contains `ip` with its value, most specific
+    /// first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetworkTable;
+    ///
+    /// let mut table = IpNetworkTable::new();
+    /// table.insert("0.0.0.0/0".parse().unwrap(), "default");
+    /// table.insert("10.0.0.0/8".parse().unwrap(), "site");
+    /// table.insert("10.1.0.0/16".parse().unwrap(), "building");
+    /// table.insert("10.2.0.0/16".parse().unwrap(), "other building");
+    /// let chain: Vec<_> = table
+    ///     .longest_match_all("10.1.2.3".parse().unwrap())
+    ///     .into_iter()
+    ///     .map(|(_, &policy)| policy)
+    ///     .collect();
+    /// assert_eq!(chain, ["building", "site", "default"]);
+    /// ```
+    pub fn longest_match_all(&self, ip: IpAddr) -> Vec<(IpNetwork, &V)> {
+        let mut matches: Vec<_> = self.matches(ip).collect();
+        matches.reverse();
+        matches
+    }
+
+    /// Returns an iterator over the networks in the table and their values, in the order of
+    /// `IpNetwork`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::{IpNetwork, IpNetworkTable};
+    ///
+    /// let mut table = IpNetworkTable::new();
+    /// for net in ["::/0", "10.0.0.0/16", "10.0.0.0/8"] {
+    ///     table.insert(net.parse().unwrap(), ());
+    /// }
+    /// let nets: Vec<String> = table.iter().map(|(net, _)| net.to_string()).collect();
+    /// assert_eq!(nets, ["10.0.0.0/8", "10.0.0.0/16", "::/0"]);
+    /// ```
+    pub fn iter(&self) -> IpNetworkTableIter<'_, V> {
+        let mut stack = Vec::new();
+        stack.extend(self.v6.as_deref().map(|node| (true, node)));
+        stack.extend(self.v4.as_deref().map(|node| (false, node)));
+        IpNetworkTableIter {
+            stack,
+            remaining: self.len,
+        }
+    }
+
+    /// Returns an iterator over mutable references to the values in the table, in the order of
+    /// their networks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ipnetwork::IpNetworkTable;
+    ///
+    /// let mut table = IpNetworkTable::new();
+    /// table.insert("10.0.0.0/8".parse().unwrap(), 1);
+    /// table.insert("::/0".parse().unwrap(), 2);
+    /// table.values_mut().for_each(|hits| *hits = 0);
+    /// assert!(table.iter().all(|(_, &hits)| hits == 0));
+    /// ```
+    pub fn values_mut(&mut self) -> IpNetworkTableValuesMut<'_, V> {
+        let mut stack = Vec::new();
+        stack.extend(self.v6.as_deref_mut());
+        stack.extend(self.v4.as_deref_mut());
+        IpNetworkTableValuesMut {
+            stack,
+            remaining: self.len,
+        }
+    }
+}
+
+impl<V> Default for IpNetworkTable<V> {
+    fn default() -> Self {
+        IpNetworkTable::new()
+    }
+}
+
+impl<V> Extend<(IpNetwork, V)> for IpNetworkTable<V> {
+    fn extend<T: IntoIterator<Item = (IpNetwork, V)>>(&mut self, iter: T) {
+        for (network, value) in iter {
+            self.insert(network, value);
+        }
+    }
+}
+
+impl<V> FromIterator<(IpNetwork, V)> for IpNetworkTable<V> {
+    fn from_iter<T: IntoIterator<Item = (IpNetwork, V)>>(iter: T) -> Self {
+        let mut table = IpNetworkTable::new();
+        table.extend(iter);
+        table
+    }
+}
+
+impl<'a, V> IntoIterator for &'a IpNetworkTable<V> {
+    type Item = (IpNetwork, &'a V);
+    type IntoIter = IpNetworkTableIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of an [`IpNetworkTable`], returned by
+/// [`IpNetworkTable::iter`].
+#[derive(Clone, Debug)]
+pub struct IpNetworkTableIter<'a, V> {
+    stack: Vec<(bool, &'a Node<V>)>,
+    remaining: usize,
+}
+
+impl<'a, V> Iterator for IpNetworkTableIter<'a, V> {
+    type Item = (IpNetwork, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // A pre-order walk yields each network before the longer ones it contains.
+        while let Some((v6, node)) = self.stack.pop() {
+            for child in node.children.iter().rev() {
+                self.stack.extend(child.as_deref().map(|child| (v6, child)));
+            }
+            if let Some(value) = &node.value {
+                self.remaining -= 1;
+                return Some((to_network(v6, node.key, node.prefix), value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<V> ExactSizeIterator for IpNetworkTableIter<'_, V> {}
+
+/// An iterator over mutable references to the values of an [`IpNetworkTable`], returned by
+/// [`IpNetworkTable::values_mut`].
+#[derive(Debug)]
+pub struct IpNetworkTableValuesMut<'a, V> {
+    stack: Vec<&'a mut Node<V>>,
+    remaining: usize,
+}
+
+impl<'a, V> Iterator for IpNetworkTableValuesMut<'a, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            let Node {
+                value, children, ..
+            } = node;
+            for child in children.iter_mut().rev() {
+                self.stack.extend(child.as_deref_mut());
+            }
+            if let Some(value) = value {
+                self.remaining -= 1;
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<V> ExactSizeIterator for IpNetworkTableValuesMut<'_, V> {}
+
+/// A network of an [`IpNetworkTable`] that may or may not hold a value, returned by
+/// [`IpNetworkTable::entry`].
+#[derive(Debug)]
+pub struct IpNetworkTableEntry<'a, V> {
+    table: &'a mut IpNetworkTable<V>,
+    network: IpNetwork,
+}
+
+impl<'a, V> IpNetworkTableEntry<'a, V> {
+    /// Returns the network of this entry, with its host bits cleared.
+    pub fn key(&self) -> IpNetwork {
+        self.network
+    }
+
+    /// Calls `f` on the value of this entry if it has one.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        if let Some(value) = self.table.get_mut(self.network) {
+            f(value);
+        }
+        self
+    }
+
+    /// Inserts `default` if this entry has no value, and returns a mutable reference to the
+    /// value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if this entry has no value, and returns a mutable
+    /// reference to the value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        if self.table.exact_match(self.network).is_none() {
+            self.table.insert(self.network, default());
+        }
+        self.table.get_mut(self.network).unwrap()
+    }
+
+    /// Inserts the default value if this entry has no value, and returns a mutable reference
+    /// to the value.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn net(s: &str) -> IpNetwork {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn table_matches_linear_scan() {
+        let networks = [
+            "0.0.0.0/0",
+            "10.0.0.0/8",
+            "10.0.0.0/9",
+            "10.128.0.0/9",
+            "10.64.0.0/10",
+            "10.64.0.1/32",
+            "10.64.0.0/32",
+            "192.0.2.0/24",
+            "192.0.2.128/25",
+            "2001:db8::/32",
+            "2001:db8:8000::/33",
+            "::1/128",
+        ];
+        let table: IpNetworkTable<usize> = networks
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (net(s), i))
+            .collect();
+        assert_eq!(table.len(), networks.len());
+
+        let probes = [
+            "10.64.0.1",
+            "10.64.0.2",
+            "10.200.0.0",
+            "11.0.0.0",
+            "192.0.2.200",
+            "2001:db8:ffff::1",
+            "2001:db8::1",
+            "::1",
+            "::2",
+        ];
+        for probe in probes {
+            let ip: IpAddr = probe.parse().unwrap();
+            let expected = networks
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| net(s).contains(ip))
+                .max_by_key(|(_, s)| net(s).prefix())
+                .map(|(i, s)| (net(s), i));
+            let found = table.longest_match(ip).map(|(n, &i)| (n, i));
+            assert_eq!(found, expected, "{probe}");
+
+            let mut covering: Vec<(IpNetwork, usize)> = networks
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| net(s).contains(ip))
+                .map(|(i, s)| (net(s), i))
+                .collect();
+            covering.sort_by_key(|&(n, _)| std::cmp::Reverse(n.prefix()));
+            let all: Vec<(IpNetwork, usize)> = table
+                .longest_match_all(ip)
+                .into_iter()
+                .map(|(n, &i)| (n, i))
+                .collect();
+            assert_eq!(all, covering, "{probe}");
+            let shortest = table.shortest_match(ip).map(|(n, &i)| (n, i));
+            assert_eq!(shortest, covering.last().copied(), "{probe}");
+        }
+
+        let mut sorted: Vec<IpNetwork> = networks.iter().map(|s| net(s)).collect();
+        sorted.sort();
+        let iterated: Vec<IpNetwork> = table.iter().map(|(n, _)| n).collect();
+        assert_eq!(iterated, sorted);
+    }
+
+    #[test]
+    fn table_insert_remove() {
+        let mut table = IpNetworkTable::new();
+        assert_eq!(table.insert(net("10.1.0.0/16"), 1), None);
+        assert_eq!(table.insert(net("10.2.0.0/16"), 2), None);
+        assert_eq!(table.insert(net("10.1.2.3/16"), 3), Some(1));
+        assert_eq!(table.len(), 2);
+        // The joining 10.0.0.0/14 node holds no value.
+        assert_eq!(table.exact_match(net("10.0.0.0/14")), None);
+        assert_eq!(table.remove(net("10.0.0.0/14")), None);
+        assert_eq!(table.remove(net("10.0.0.0/8")), None);
+
+        assert_eq!(table.remove(net("10.1.0.0/16")), Some(3));
+        assert_eq!(table.exact_match(net("10.2.0.0/16")), Some(&2));
+        assert_eq!(table.longest_match("10.1.0.1".parse().unwrap()), None);
+        assert_eq!(table.remove(net("10.2.0.0/16")), Some(2));
+        assert!(table.is_empty());
+        assert!(table.v4.is_none());
+
+        table.insert(net("::/0"), 0);
+        table.insert(net("::/1"), 1);
+        *table.entry(net("::1/1")).or_default() += 10;
+        *table.entry(net("8000::/1")).or_default() += 10;
+        assert_eq!(table.get_mut(net("8000::/1")), Some(&mut 10));
+        assert_eq!(table.remove(net("8000::/1")), Some(10));
+        assert_eq!(table.values_mut().len(), 2);
+        assert_eq!(table.remove(net("::/0")), Some(0));
+        assert_eq!(table.iter().collect::<Vec<_>>(), [(net("::/1"), &11)]);
+    }
+
+    #[test]
+    fn table_retain() {
+        let networks = [
+            "10.0.0.0/8",
+            "10.0.0.0/9",
+            "10.128.0.0/9",
+            "10.64.0.0/10",
+            "192.0.2.0/24",
+            "::/0",
+            "::1/128",
+        ];
+        let mut table: IpNetworkTable<usize> = networks
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (net(s), i))
+            .collect();
+        let mut seen = Vec::new();
+        table.retain(|n, i| {
+            seen.push(n);
+            *i += 100;
+            n.prefix() != 9 && n != net("::/0")
+        });
+        let mut sorted: Vec<IpNetwork> = networks.iter().map(|s| net(s)).collect();
+        sorted.sort();
+        assert_eq!(seen, sorted);
+        let kept: Vec<(IpNetwork, usize)> = table.iter().map(|(n, &i)| (n, i)).collect();
+        assert_eq!(
+            kept,
+            [
+                (net("10.0.0.0/8"), 100),
+                (net("10.64.0.0/10"), 103),
+                (net("192.0.2.0/24"), 104),
+                (net("::1/128"), 106),
+            ]
+        );
+        assert_eq!(table.len(), 4);
+        assert_eq!(
+            table.longest_match("10.64.1.1".parse().unwrap()),
+            Some((net("10.64.0.0/10"), &103))
+        );
+        table.retain(|_, _| false);
+        assert!(table.is_empty());
+        assert!(table.v4.is_none() && table.v6.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn table_serde_round_trip() {
+        let table: IpNetworkTable<u32> = [(net("10.0.0.0/8"), 1), (net("::/0"), 2)]
+            .into_iter()
+            .collect();
+        let json = serde_json::to_string(&table).unwrap();
+        assert_eq!(json, r#"{"10.0.0.0/8":1,"::/0":2}"#);
+        let restored: IpNetworkTable<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.iter().collect::<Vec<_>>(),
+            table.iter().collect::<Vec<_>>()
+        );
+        assert!(serde_json::from_str::<IpNetworkTable<u32>>(r#"{"10.0.0.0/33":1}"#).is_err());
+    }
+}
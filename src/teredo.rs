@@ -0,0 +1,141 @@
+use crate::Ipv6Network;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// The fields embedded in a Teredo address, as defined in [RFC 4380, section 4].
+///
+/// The client port and address are stored obfuscated (with all bits inverted) in the address;
+/// the accessors return them in their usual form.
+///
+/// [RFC 4380, section 4]: https://www.rfc-editor.org/rfc/rfc4380#section-4
+///
+/// # Examples
+///
+/// ```
+/// use std::net::{Ipv4Addr, Ipv6Addr};
+/// use ipnetwork::TeredoAddr;
+///
+/// let addr: Ipv6Addr = "2001:0:4136:e378:8000:63bf:3fff:fdd2".parse().unwrap();
+/// let teredo = TeredoAddr::from_ipv6(addr).unwrap();
+/// assert_eq!(teredo.server(), Ipv4Addr::new(65, 54, 227, 120));
+/// assert_eq!(teredo.client(), Ipv4Addr::new(192, 0, 2, 45));
+/// assert_eq!(teredo.port(), 40000);
+/// assert!(teredo.is_cone());
+/// assert_eq!(Ipv6Addr::from(teredo), addr);
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct TeredoAddr {
+    server: Ipv4Addr,
+    flags: u16,
+    port: u16,
+    client: Ipv4Addr,
+}
+
+impl TeredoAddr {
+    /// The `2001::/32` prefix all Teredo addresses are allocated from.
+    pub const PREFIX: Ipv6Network =
+        match Ipv6Network::new_checked(Ipv6Addr::new(0x2001, 0, 0, 0, 0, 0, 0, 0), 32) {
+            Some(net) => net,
+            None => unreachable!(),
+        };
+
+    /// The flag bit set by clients behind a cone NAT.
+    const CONE_FLAG: u16 = 0x8000;
+
+    /// Constructs a new `TeredoAddr` from its server address, flags, and the unobfuscated client
+    /// port and address.
+    pub const fn new(server: Ipv4Addr, flags: u16, port: u16, client: Ipv4Addr) -> Self {
+        TeredoAddr {
+            server,
+            flags,
+            port,
+            client,
+        }
+    }
+
+    /// Extracts the Teredo fields from `addr`.
+    ///
+    /// Returns `None` if `addr` is not within `2001::/32`.
+    pub const fn from_ipv6(addr: Ipv6Addr) -> Option<Self> {
+        if !Self::PREFIX.contains(addr) {
+            return None;
+        }
+        let bits = addr.to_bits();
+        Some(TeredoAddr {
+            server: Ipv4Addr::from_bits((bits >> 64) as u32),
+            flags: (bits >> 48) as u16,
+            port: !(bits >> 32) as u16,
+            client: Ipv4Addr::from_bits(!bits as u32),
+        })
+    }
+
+    /// Returns the IPv4 address of the Teredo server the client is using.
+    pub const fn server(&self) -> Ipv4Addr {
+        self.server
+    }
+
+    /// Returns the raw flags field.
+    pub const fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    /// Checks if the cone flag is set, meaning the client is behind a cone NAT.
+    pub const fn is_cone(&self) -> bool {
+        self.flags & Self::CONE_FLAG != 0
+    }
+
+    /// Returns the external UDP port of the client, with the obfuscation removed.
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the external IPv4 address of the client, with the obfuscation removed.
+    pub const fn client(&self) -> Ipv4Addr {
+        self.client
+    }
+
+    /// Returns the Teredo address encoding these fields.
+    pub const fn to_ipv6(&self) -> Ipv6Addr {
+        let bits = Self::PREFIX.ip().to_bits()
+            | (self.server.to_bits() as u128) << 64
+            | (self.flags as u128) << 48
+            | (!self.port as u128) << 32
+            | !self.client.to_bits() as u128;
+        Ipv6Addr::from_bits(bits)
+    }
+}
+
+impl From<TeredoAddr> for Ipv6Addr {
+    fn from(teredo: TeredoAddr) -> Ipv6Addr {
+        teredo.to_ipv6()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn teredo_round_trip() {
+        let teredo = TeredoAddr::new(
+            Ipv4Addr::new(198, 51, 100, 1),
+            0,
+            0,
+            Ipv4Addr::new(255, 255, 255, 255),
+        );
+        let addr = teredo.to_ipv6();
+        assert_eq!(
+            addr,
+            "2001:0:c633:6401:0:ffff::".parse::<Ipv6Addr>().unwrap()
+        );
+        assert_eq!(TeredoAddr::from_ipv6(addr), Some(teredo));
+        assert!(!teredo.is_cone());
+    }
+
+    #[test]
+    fn teredo_outside_prefix() {
+        let addr: Ipv6Addr = "2001:1::1".parse().unwrap();
+        assert_eq!(TeredoAddr::from_ipv6(addr), None);
+        let addr: Ipv6Addr = "2002::1".parse().unwrap();
+        assert_eq!(TeredoAddr::from_ipv6(addr), None);
+    }
+}
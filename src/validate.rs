@@ -0,0 +1,130 @@
+//! Validation functions for fields holding networks as strings, for use as custom validators
+//! with the `validator` and `garde` derive macros.
+//!
+//! A value is valid if it parses as an [`IpNetwork`]. The strict variants additionally reject
+//! values with host bits set, such as `10.0.0.1/8`.
+
+use crate::IpNetwork;
+
+/// Returns a message describing why `value` is not a valid network.
+fn check(value: &str, strict: bool) -> Result<(), String> {
+    let net: IpNetwork = value
+        .parse()
+        .map_err(|_| format!("{value:?} is not a valid network"))?;
+    if strict && net.ip() != net.network() {
+        return Err(format!(
+            "{value:?} has host bits set, the network is {}/{}",
+            net.network(),
+            net.prefix()
+        ));
+    }
+    Ok(())
+}
+
+/// Validates that `value` is a network in CIDR notation, for use with
+/// `#[validate(custom(function = "ipnetwork::validate_cidr"))]`.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::validate_cidr;
+///
+/// assert!(validate_cidr("10.0.0.1/8").is_ok());
+/// assert!(validate_cidr("10.0.0.0/33").is_err());
+/// ```
+#[cfg(feature = "validator")]
+pub fn validate_cidr<T: AsRef<str> + ?Sized>(value: &T) -> Result<(), validator::ValidationError> {
+    check(value.as_ref(), false).map_err(validation_error)
+}
+
+/// Validates that `value` is a network in CIDR notation without host bits set, for use with
+/// `#[validate(custom(function = "ipnetwork::validate_cidr_strict"))]`.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::validate_cidr_strict;
+///
+/// assert!(validate_cidr_strict("10.0.0.0/8").is_ok());
+/// assert!(validate_cidr_strict("10.0.0.1/8").is_err());
+/// ```
+#[cfg(feature = "validator")]
+pub fn validate_cidr_strict<T: AsRef<str> + ?Sized>(
+    value: &T,
+) -> Result<(), validator::ValidationError> {
+    check(value.as_ref(), true).map_err(validation_error)
+}
+
+#[cfg(feature = "validator")]
+fn validation_error(message: String) -> validator::ValidationError {
+    validator::ValidationError::new("cidr").with_message(message.into())
+}
+
+/// Validates that `value` is a network in CIDR notation, for use with
+/// `#[garde(custom(ipnetwork::garde_cidr))]`.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::garde_cidr;
+///
+/// assert!(garde_cidr("2001:db8::1/32", &()).is_ok());
+/// assert!(garde_cidr("2001:db8::/129", &()).is_err());
+/// ```
+#[cfg(feature = "garde")]
+pub fn garde_cidr<T: AsRef<str> + ?Sized, C>(value: &T, _context: &C) -> garde::Result {
+    check(value.as_ref(), false).map_err(garde::Error::new)
+}
+
+/// Validates that `value` is a network in CIDR notation without host bits set, for use with
+/// `#[garde(custom(ipnetwork::garde_cidr_strict))]`.
+///
+/// # Examples
+///
+/// ```
+/// use ipnetwork::garde_cidr_strict;
+///
+/// assert!(garde_cidr_strict("2001:db8::/32", &()).is_ok());
+/// assert!(garde_cidr_strict("2001:db8::1/32", &()).is_err());
+/// ```
+#[cfg(feature = "garde")]
+pub fn garde_cidr_strict<T: AsRef<str> + ?Sized, C>(value: &T, _context: &C) -> garde::Result {
+    check(value.as_ref(), true).map_err(garde::Error::new)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_messages() {
+        assert_eq!(check("192.168.1.0/24", true), Ok(()));
+        assert_eq!(check("192.168.1.1", true), Ok(()));
+        assert_eq!(
+            check("192.168.1.1/24", true),
+            Err("\"192.168.1.1/24\" has host bits set, the network is 192.168.1.0/24".to_owned())
+        );
+        assert_eq!(
+            check("192.168.1.0/33", false),
+            Err("\"192.168.1.0/33\" is not a valid network".to_owned())
+        );
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn validator_error() {
+        let err = validate_cidr(&String::from("not a network")).unwrap_err();
+        assert_eq!(err.code, "cidr");
+        assert!(err.message.is_some());
+    }
+
+    #[cfg(feature = "garde")]
+    #[test]
+    fn garde_error() {
+        let err = garde_cidr_strict(&String::from("::1/64"), &()).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "\"::1/64\" has host bits set, the network is ::/64"
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! clap value parsers that explain why a network failed to parse.
+
+use crate::parse::cidr_parts;
+use crate::range::{IPV4_BITS, IPV6_BITS};
+use crate::{IpNetwork, Ipv4Network, Ipv6Network};
+use clap::builder::{
+    MapValueParser, NonEmptyStringValueParser, PossibleValue, TypedValueParser, ValueParserFactory,
+};
+use clap::{Arg, Command, ValueEnum};
+use std::{ffi::OsStr, net::IpAddr};
+
+/// An IP address family, for selecting which kind of network a command line argument accepts.
+///
+/// Parsed from `ipv4` or `ipv6` (or `4` and `6`) when used as a clap [`ValueEnum`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum IpFamily {
+    V4,
+    V6,
+}
+
+impl IpFamily {
+    fn name(self) -> &'static str {
+        match self {
+            IpFamily::V4 => "IPv4",
+            IpFamily::V6 => "IPv6",
+        }
+    }
+}
+
+impl ValueEnum for IpFamily {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[IpFamily::V4, IpFamily::V6]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            IpFamily::V4 => PossibleValue::new("ipv4").alias("4"),
+            IpFamily::V6 => PossibleValue::new("ipv6").alias("6"),
+        })
+    }
+}
+
+/// A clap [`TypedValueParser`] for networks in CIDR notation.
+///
+/// Accepts exactly what [`IpNetwork`]'s `FromStr` implementation accepts, but reports what is
+/// wrong with a rejected value, such as `prefix must be between 0 and 32`, instead of the
+/// generic parse error. This is the parser clap picks for [`IpNetwork`], [`Ipv4Network`] and
+/// [`Ipv6Network`] arguments by default.
+///
+/// # Examples
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use ipnetwork::{IpFamily, IpNetwork, IpNetworkValueParser};
+///
+/// let cmd = Command::new("route").arg(
+///     Arg::new("net").value_parser(IpNetworkValueParser::new().family(IpFamily::V4)),
+/// );
+///
+/// let matches = cmd.clone().try_get_matches_from(["route", "10.0.0.0/8"]).unwrap();
+/// let net: &IpNetwork = matches.get_one("net").unwrap();
+/// assert_eq!(net.prefix(), 8);
+///
+/// let err = cmd.try_get_matches_from(["route", "10.0.0.0/33"]).unwrap_err();
+/// assert!(err.to_string().contains("prefix must be between 0 and 32"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IpNetworkValueParser {
+    family: Option<IpFamily>,
+}
+
+impl IpNetworkValueParser {
+    /// Constructs a parser accepting networks of either family.
+    pub const fn new() -> Self {
+        IpNetworkValueParser { family: None }
+    }
+
+    /// Restricts the parser to networks of `family`.
+    pub const fn family(self, family: IpFamily) -> Self {
+        IpNetworkValueParser {
+            family: Some(family),
+        }
+    }
+
+    fn parse_str(&self, s: &str) -> Result<IpNetwork, String> {
+        let parsed = match self.family {
+            None => s.parse(),
+            Some(IpFamily::V4) => s.parse().map(IpNetwork::V4),
+            Some(IpFamily::V6) => s.parse().map(IpNetwork::V6),
+        };
+        parsed.map_err(|e| self.explain(s).unwrap_or_else(|| e.to_string()))
+    }
+
+    /// Describes what makes `s` invalid, if it is one of the common mistakes.
+    fn explain(&self, s: &str) -> Option<String> {
+        let (addr, prefix) = match cidr_parts(s) {
+            Ok(parts) => parts,
+            Err(_) => return Some("expected a single '/' before the prefix".to_owned()),
+        };
+        let family = match addr.parse() {
+            Ok(IpAddr::V4(_)) => IpFamily::V4,
+            Ok(IpAddr::V6(_)) => IpFamily::V6,
+            Err(_) => {
+                let expected = self.family.map_or("IPv4 or IPv6", IpFamily::name);
+                return Some(format!("'{addr}' is not a valid {expected} address"));
+            }
+        };
+        if let Some(expected) = self.family.filter(|&expected| expected != family) {
+            return Some(format!(
+                "expected an {} network, found an {} address",
+                expected.name(),
+                family.name()
+            ));
+        }
+
+        let max = match family {
+            IpFamily::V4 => IPV4_BITS,
+            IpFamily::V6 => IPV6_BITS,
+        };
+        let prefix = prefix?;
+        match prefix.parse::<u32>() {
+            Ok(len) if len > u32::from(max) => Some(format!("prefix must be between 0 and {max}")),
+            Ok(_) => None,
+            Err(_) if family == IpFamily::V4 => Some(format!(
+                "'{prefix}' is not a valid prefix length or netmask"
+            )),
+            Err(_) => Some(format!("'{prefix}' is not a valid prefix length")),
+        }
+    }
+}
+
+impl TypedValueParser for IpNetworkValueParser {
+    type Value = IpNetwork;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<IpNetwork, clap::Error> {
+        let parser = *self;
+        NonEmptyStringValueParser::new()
+            .try_map(move |s| parser.parse_str(&s))
+            .parse_ref(cmd, arg, value)
+    }
+}
+
+impl ValueParserFactory for IpNetwork {
+    type Parser = IpNetworkValueParser;
+
+    fn value_parser() -> Self::Parser {
+        IpNetworkValueParser::new()
+    }
+}
+
+impl ValueParserFactory for Ipv4Network {
+    type Parser = MapValueParser<IpNetworkValueParser, fn(IpNetwork) -> Ipv4Network>;
+
+    fn value_parser() -> Self::Parser {
+        IpNetworkValueParser::new()
+            .family(IpFamily::V4)
+            .map(|net| match net {
+                IpNetwork::V4(net) => net,
+                IpNetwork::V6(_) => unreachable!("the parser only accepts IPv4 networks"),
+            })
+    }
+}
+
+impl ValueParserFactory for Ipv6Network {
+    type Parser = MapValueParser<IpNetworkValueParser, fn(IpNetwork) -> Ipv6Network>;
+
+    fn value_parser() -> Self::Parser {
+        IpNetworkValueParser::new()
+            .family(IpFamily::V6)
+            .map(|net| match net {
+                IpNetwork::V6(net) => net,
+                IpNetwork::V4(_) => unreachable!("the parser only accepts IPv6 networks"),
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn explain(parser: IpNetworkValueParser, s: &str) -> String {
+        parser.parse_str(s).unwrap_err()
+    }
+
+    #[test]
+    fn value_parser_explains_errors() {
+        let any = IpNetworkValueParser::new();
+        let v4 = any.family(IpFamily::V4);
+        assert_eq!(
+            explain(any, "10.0.0.0/33"),
+            "prefix must be between 0 and 32"
+        );
+        assert_eq!(explain(any, "::/129"), "prefix must be between 0 and 128");
+        assert_eq!(
+            explain(any, "10.0.0.0/8/8"),
+            "expected a single '/' before the prefix"
+        );
+        assert_eq!(
+            explain(any, "10.0.0/8"),
+            "'10.0.0' is not a valid IPv4 or IPv6 address"
+        );
+        assert_eq!(explain(v4, "foo/8"), "'foo' is not a valid IPv4 address");
+        assert_eq!(
+            explain(v4, "::/64"),
+            "expected an IPv4 network, found an IPv6 address"
+        );
+        assert_eq!(
+            explain(v4, "10.0.0.0/255.0.255.0"),
+            "'255.0.255.0' is not a valid prefix length or netmask"
+        );
+        assert_eq!(explain(any, "::/x"), "'x' is not a valid prefix length");
+    }
+
+    #[test]
+    fn value_parser_factory() {
+        let cmd = Command::new("test")
+            .arg(
+                Arg::new("v4")
+                    .long("v4")
+                    .value_parser(clap::value_parser!(Ipv4Network)),
+            )
+            .arg(
+                Arg::new("v6")
+                    .long("v6")
+                    .value_parser(clap::value_parser!(Ipv6Network)),
+            )
+            .arg(
+                Arg::new("family")
+                    .long("family")
+                    .value_parser(clap::value_parser!(IpFamily)),
+            );
+        let matches = cmd
+            .clone()
+            .try_get_matches_from([
+                "test",
+                "--v4",
+                "10.0.0.0/255.0.0.0",
+                "--v6",
+                "2001:db8::/32",
+                "--family",
+                "6",
+            ])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Ipv4Network>("v4"),
+            Some(&"10.0.0.0/8".parse().unwrap())
+        );
+        assert_eq!(
+            matches.get_one::<Ipv6Network>("v6"),
+            Some(&"2001:db8::/32".parse().unwrap())
+        );
+        assert_eq!(matches.get_one::<IpFamily>("family"), Some(&IpFamily::V6));
+
+        assert!(cmd
+            .try_get_matches_from(["test", "--v6", "10.0.0.0/8"])
+            .is_err());
+    }
+}
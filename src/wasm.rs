@@ -0,0 +1,72 @@
+//! Conversions between the network types and `JsValue` for use with `wasm-bindgen`.
+//!
+//! Networks cross the boundary as strings in CIDR notation, which JavaScript code can display
+//! and send back unchanged.
+//!
+//! ```no_run
+//! use std::{convert::TryFrom, str::FromStr};
+//! use ipnetwork::IpNetwork;
+//! use wasm_bindgen::JsValue;
+//!
+//! let net: IpNetwork = "10.0.0.0/8".parse().unwrap();
+//! let value = JsValue::from(net);
+//! assert_eq!(value.as_string().as_deref(), Some("10.0.0.0/8"));
+//! assert_eq!(IpNetwork::try_from(value), Ok(net));
+//! ```
+
+use crate::{IpNetwork, IpNetworkError, Ipv4Network, Ipv6Network};
+use std::{convert::TryFrom, str::FromStr};
+use wasm_bindgen::JsValue;
+
+/// Parses `value` if it is a JavaScript string, and rejects every other value.
+fn parse_js<T>(value: JsValue) -> Result<T, IpNetworkError>
+where
+    T: FromStr<Err = IpNetworkError>,
+{
+    match value.as_string() {
+        Some(s) => s.parse(),
+        None => Err(IpNetworkError::InvalidAddr(format!("{:?}", value))),
+    }
+}
+
+impl From<Ipv4Network> for JsValue {
+    fn from(net: Ipv4Network) -> JsValue {
+        JsValue::from_str(&net.to_string())
+    }
+}
+
+impl TryFrom<JsValue> for Ipv4Network {
+    type Error = IpNetworkError;
+
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        parse_js(value)
+    }
+}
+
+impl From<Ipv6Network> for JsValue {
+    fn from(net: Ipv6Network) -> JsValue {
+        JsValue::from_str(&net.to_string())
+    }
+}
+
+impl TryFrom<JsValue> for Ipv6Network {
+    type Error = IpNetworkError;
+
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        parse_js(value)
+    }
+}
+
+impl From<IpNetwork> for JsValue {
+    fn from(net: IpNetwork) -> JsValue {
+        JsValue::from_str(&net.to_string())
+    }
+}
+
+impl TryFrom<JsValue> for IpNetwork {
+    type Error = IpNetworkError;
+
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        parse_js(value)
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "serde")]
+
+#[cfg(test)]
+mod tests {
+    use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
+    use serde_test::{assert_de_tokens_error, assert_tokens, Compact, Configure, Token};
+
+    #[test]
+    fn test_ipv4_compact() {
+        let net: Ipv4Network = "192.0.2.1/24".parse().unwrap();
+        assert_tokens(
+            &net.compact(),
+            &[
+                Token::Tuple { len: 2 },
+                Token::Tuple { len: 4 },
+                Token::U8(192),
+                Token::U8(0),
+                Token::U8(2),
+                Token::U8(1),
+                Token::TupleEnd,
+                Token::U8(24),
+                Token::TupleEnd,
+            ],
+        );
+        assert_tokens(&net.readable(), &[Token::Str("192.0.2.1/24")]);
+        assert_de_tokens_error::<Compact<Ipv4Network>>(
+            &[
+                Token::Tuple { len: 2 },
+                Token::Tuple { len: 4 },
+                Token::U8(0),
+                Token::U8(0),
+                Token::U8(0),
+                Token::U8(0),
+                Token::TupleEnd,
+                Token::U8(33),
+                Token::TupleEnd,
+            ],
+            "invalid prefix",
+        );
+    }
+
+    #[test]
+    fn test_ipv6_compact() {
+        let net: Ipv6Network = "2001:db8::1/64".parse().unwrap();
+        let mut tokens = vec![Token::Tuple { len: 2 }, Token::Tuple { len: 16 }];
+        tokens.extend(net.ip().octets().map(Token::U8));
+        tokens.extend([Token::TupleEnd, Token::U8(64), Token::TupleEnd]);
+        assert_tokens(&net.compact(), &tokens);
+    }
+
+    #[test]
+    fn test_ipnetwork_compact() {
+        let net: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        assert_tokens(
+            &net.compact(),
+            &[
+                Token::Tuple { len: 2 },
+                Token::NewtypeVariant {
+                    name: "IpAddr",
+                    variant: "V4",
+                },
+                Token::Tuple { len: 4 },
+                Token::U8(10),
+                Token::U8(0),
+                Token::U8(0),
+                Token::U8(0),
+                Token::TupleEnd,
+                Token::U8(8),
+                Token::TupleEnd,
+            ],
+        );
+        assert_tokens(&net.readable(), &[Token::Str("10.0.0.0/8")]);
+    }
+}
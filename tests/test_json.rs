@@ -83,6 +83,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ipnetwork_tagged_json() {
+        let json_string = r#"{"ipnetwork":[{"v4":"127.1.0.0/24"},{"v6":"::1/0"}]}"#;
+
+        #[derive(Serialize, Deserialize)]
+        struct Tagged(#[serde(with = "ipnetwork::serde_tagged")] IpNetwork);
+
+        #[derive(Serialize, Deserialize)]
+        struct MyStruct {
+            ipnetwork: Vec<Tagged>,
+        }
+
+        let mystruct: MyStruct = ::serde_json::from_str(json_string).unwrap();
+
+        assert_eq!(mystruct.ipnetwork[0].0, "127.1.0.0/24".parse().unwrap());
+        assert_eq!(mystruct.ipnetwork[1].0, "::1/0".parse().unwrap());
+
+        assert_eq!(::serde_json::to_string(&mystruct).unwrap(), json_string);
+
+        let mismatched = r#"{"ipnetwork":[{"v6":"127.1.0.0/24"}]}"#;
+        assert!(::serde_json::from_str::<MyStruct>(mismatched).is_err());
+        let unknown = r#"{"ipnetwork":[{"v5":"127.1.0.0/24"}]}"#;
+        assert!(::serde_json::from_str::<MyStruct>(unknown).is_err());
+    }
+
 
     #[test]
     fn test_ipnetwork_size_with_prefix_0() {